@@ -18,8 +18,11 @@ assert(div(8u, 4u) == 2uL)
 try {
     div(8u, 0u)
     throw RuntimeException("Should have panicked when dividing by zero")
-} catch (e: InternalException) {
-    // It's okay!
+} catch (e: RustPanic) {
+    // It's okay! The exception says where the Rust code panicked.
+    assert(e.message == "Can't divide by zero")
+    assert(e.file!!.endsWith("lib.rs"))
+    assert(e.line!! > 0)
 }
 
 assert(equal(2u, 2uL))
@@ -0,0 +1,22 @@
+import assert from "node:assert/strict";
+import { add, sub, div, equal, ArithmeticError, InternalError } from "./arithmetic.mjs";
+
+assert.throws(() => add(18446744073709551615n, 1n), ArithmeticError.IntegerOverflow);
+
+assert.equal(add(2n, 4n), 6n);
+assert.equal(add(4n, 8n), 12n);
+
+assert.throws(() => sub(0n, 1n), ArithmeticError.IntegerOverflow);
+
+assert.equal(sub(4n, 2n), 2n);
+assert.equal(sub(8n, 4n), 4n);
+
+assert.equal(div(8n, 4n), 2n);
+
+assert.throws(() => div(8n, 0n), InternalError);
+
+assert.ok(equal(2n, 2n));
+assert.ok(equal(4n, 4n));
+
+assert.ok(!equal(2n, 4n));
+assert.ok(!equal(4n, 8n));
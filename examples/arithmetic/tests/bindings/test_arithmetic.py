@@ -24,9 +24,13 @@ assert div(8, 4) == 2
 
 try:
     div(8, 0)
-except InternalError:
-    # It's okay!
-    pass
+except RustPanic as e:
+    # It's okay! The exception says where the Rust code panicked.
+    assert isinstance(e, InternalError)
+    assert e.message == "Can't divide by zero"
+    assert e.file.endswith("lib.rs")
+    assert e.line > 0
+    assert str(e).startswith("Can't divide by zero at ")
 else:
     assert(not("Should have panicked when dividing by zero"))
 
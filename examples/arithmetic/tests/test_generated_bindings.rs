@@ -4,5 +4,6 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_arithmetic.py",
         "tests/bindings/test_arithmetic.kts",
         "tests/bindings/test_arithmetic.swift",
+        "tests/bindings/test_arithmetic.mjs",
     ]
 );
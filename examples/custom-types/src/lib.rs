@@ -0,0 +1,68 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use uniffi::deps::anyhow::{bail, Result};
+
+// A rather minimal URL type, which only guarantees that it has a scheme.
+// The foreign-language bindings are configured to use their own URL types
+// for it, and pass it to us as a plain string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Url(String);
+
+impl uniffi::CustomTypeConverter for Url {
+    type Builtin = String;
+
+    fn into_custom(val: String) -> Result<Self> {
+        if !val.contains("://") {
+            bail!("invalid url: {}", val);
+        }
+        Ok(Url(val))
+    }
+
+    fn from_custom(obj: &Self) -> String {
+        obj.0.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Handle(i64);
+
+impl uniffi::CustomTypeConverter for Handle {
+    type Builtin = i64;
+
+    fn into_custom(val: i64) -> Result<Self> {
+        Ok(Handle(val))
+    }
+
+    fn from_custom(obj: &Self) -> i64 {
+        obj.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomTypesDemo {
+    url: Url,
+    handle: Handle,
+}
+
+fn get_custom_types_demo(demo: Option<CustomTypesDemo>) -> CustomTypesDemo {
+    demo.unwrap_or_else(|| CustomTypesDemo {
+        url: Url("http://example.com/".into()),
+        handle: Handle(123),
+    })
+}
+
+fn get_url_with_path(url: Url, path: String) -> Url {
+    Url(format!("{}/{}", url.0.trim_end_matches('/'), path))
+}
+
+fn get_next_handle(handle: Handle) -> Handle {
+    Handle(handle.0 + 1)
+}
+
+fn get_urls(urls: Vec<Option<Url>>) -> Vec<Option<Url>> {
+    urls
+}
+
+include!(concat!(env!("OUT_DIR"), "/custom_types.uniffi.rs"));
@@ -0,0 +1,23 @@
+import java.net.URL
+import uniffi.custom_types.*
+
+// Note that we compare URLs as strings, since `URL.equals()` does DNS lookups.
+
+// The default demo record comes back with our custom types.
+val demo = getCustomTypesDemo(null)
+assert(demo.url.toString() == "http://example.com/")
+assert(demo.handle == 123L)
+
+// And custom types survive the round-trip inside a record.
+val demo2 = getCustomTypesDemo(CustomTypesDemo(URL("http://new.example.com/"), 456L))
+assert(demo2.url.toString() == "http://new.example.com/")
+assert(demo2.handle == 456L)
+
+// They can be passed directly as arguments and return values.
+val url = getUrlWithPath(URL("https://example.com/"), "path")
+assert(url.path == "/path")
+assert(getNextHandle(41L) == 42L)
+
+// As well as inside optionals and sequences.
+val urls = listOf(URL("https://example.com/a"), null, URL("https://example.com/b"))
+assert(getUrls(urls).map { it?.toString() } == urls.map { it?.toString() })
@@ -0,0 +1,22 @@
+import assert from "node:assert/strict";
+import { CustomTypesDemo, getCustomTypesDemo, getNextHandle, getUrlWithPath, getUrls } from "./custom_types.mjs";
+
+// The default demo record comes back with our custom types.
+let demo = getCustomTypesDemo(null);
+assert.ok(demo.url instanceof URL);
+assert.equal(demo.url.href, "http://example.com/");
+assert.equal(demo.handle, 123n);
+
+// And custom types survive the round-trip inside a record.
+demo = getCustomTypesDemo(new CustomTypesDemo(new URL("http://new.example.com/"), 456n));
+assert.equal(demo.url.href, "http://new.example.com/");
+assert.equal(demo.handle, 456n);
+
+// They can be passed directly as arguments and return values.
+const url = getUrlWithPath(new URL("https://example.com/"), "path");
+assert.equal(url.pathname, "/path");
+assert.equal(getNextHandle(41n), 42n);
+
+// As well as inside optionals and sequences.
+const urls = getUrls([new URL("https://example.com/a"), null, new URL("https://example.com/b")]);
+assert.deepEqual(urls.map((u) => (u === null ? null : u.href)), ["https://example.com/a", null, "https://example.com/b"]);
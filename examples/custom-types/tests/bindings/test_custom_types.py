@@ -0,0 +1,22 @@
+import urllib.parse
+from custom_types import *
+
+# The default demo record comes back with our custom types.
+demo = get_custom_types_demo(None)
+assert demo.url == urllib.parse.urlparse("http://example.com/")
+assert demo.handle == 123
+
+# And custom types survive the round-trip inside a record.
+demo = get_custom_types_demo(CustomTypesDemo(urllib.parse.urlparse("http://new.example.com/"), 456))
+assert demo.url == urllib.parse.urlparse("http://new.example.com/")
+assert demo.handle == 456
+
+# They can be passed directly as arguments and return values.
+url = get_url_with_path(urllib.parse.urlparse("https://example.com/"), "path")
+assert url.geturl() == "https://example.com/path"
+assert url.path == "/path"
+assert get_next_handle(41) == 42
+
+# As well as inside optionals and sequences.
+urls = [urllib.parse.urlparse("https://example.com/a"), None, urllib.parse.urlparse("https://example.com/b")]
+assert get_urls(urls) == urls
@@ -0,0 +1,21 @@
+import custom_types
+import Foundation
+
+// The default demo record comes back with our custom types.
+var demo = getCustomTypesDemo(demo: nil)
+assert(demo.url == URL(string: "http://example.com/"))
+assert(demo.handle == 123)
+
+// And custom types survive the round-trip inside a record.
+demo = getCustomTypesDemo(demo: CustomTypesDemo(url: URL(string: "http://new.example.com/")!, handle: 456))
+assert(demo.url == URL(string: "http://new.example.com/"))
+assert(demo.handle == 456)
+
+// They can be passed directly as arguments and return values.
+let url = getUrlWithPath(url: URL(string: "https://example.com/")!, path: "path")
+assert(url.path == "/path")
+assert(getNextHandle(handle: 41) == 42)
+
+// As well as inside optionals and sequences.
+let urls = [URL(string: "https://example.com/a"), nil, URL(string: "https://example.com/b")]
+assert(getUrls(urls: urls) == urls)
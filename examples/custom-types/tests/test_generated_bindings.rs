@@ -0,0 +1,9 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/custom_types.udl",
+    [
+        "tests/bindings/test_custom_types.py",
+        "tests/bindings/test_custom_types.kts",
+        "tests/bindings/test_custom_types.swift",
+        "tests/bindings/test_custom_types.mjs",
+    ]
+);
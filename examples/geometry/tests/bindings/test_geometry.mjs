@@ -0,0 +1,11 @@
+import assert from "node:assert/strict";
+import { Line, Point, gradient, intersection } from "./geometry.mjs";
+
+const ln1 = new Line(new Point(0, 0), new Point(1, 2));
+const ln2 = new Line(new Point(1, 1), new Point(2, 2));
+
+assert.equal(gradient(ln1), 2);
+assert.equal(gradient(ln2), 1);
+
+assert.deepEqual(intersection(ln1, ln2), new Point(0, 0));
+assert.equal(intersection(ln1, ln1), null);
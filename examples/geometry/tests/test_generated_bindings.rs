@@ -4,5 +4,6 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_geometry.py",
         "tests/bindings/test_geometry.kts",
         "tests/bindings/test_geometry.swift",
+        "tests/bindings/test_geometry.mjs",
     ]
 );
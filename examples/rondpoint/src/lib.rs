@@ -3,6 +3,8 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use uniffi::Union2;
 
 #[derive(Debug, Clone)]
 pub struct Dictionnaire {
@@ -28,7 +30,7 @@ pub struct DictionnaireNombresSignes {
     gros_nombre: i64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Enumeration {
     Un,
     Deux,
@@ -58,6 +60,14 @@ fn copie_carte(e: HashMap<String, Enumeration>) -> HashMap<String, Enumeration>
     e
 }
 
+fn copie_carte_nombres(c: HashMap<u64, String>) -> HashMap<u64, String> {
+    c
+}
+
+fn copie_carte_enumerations(c: HashMap<Enumeration, u32>) -> HashMap<Enumeration, u32> {
+    c
+}
+
 fn copie_dictionnaire(d: Dictionnaire) -> Dictionnaire {
     d
 }
@@ -111,6 +121,51 @@ impl Retourneur {
     fn identique_string(&self, value: String) -> String {
         value
     }
+    fn identique_chaine_empruntee(&self, value: &str) -> String {
+        value.to_string()
+    }
+    fn identique_octets_empruntes(&self, value: &[u8]) -> Vec<u8> {
+        value.to_vec()
+    }
+    fn identique_tampon(&self, value: Vec<u8>) -> Vec<u8> {
+        value
+    }
+    fn identique_tampon_emprunte(&self, value: &[u8]) -> Vec<u8> {
+        value.to_vec()
+    }
+    fn identique_tampons(&self, value: Vec<Option<Vec<u8>>>) -> Vec<Option<Vec<u8>>> {
+        value
+    }
+    fn identique_horodatage(&self, value: SystemTime) -> SystemTime {
+        value
+    }
+    fn identique_duree(&self, value: Duration) -> Duration {
+        value
+    }
+    fn identique_horodatages(&self, value: Vec<SystemTime>) -> Vec<SystemTime> {
+        value
+    }
+    fn identique_union(&self, value: Union2<String, Dictionnaire>) -> Union2<String, Dictionnaire> {
+        value
+    }
+    fn identique_tuple(&self, value: (String, u32)) -> (String, u32) {
+        value
+    }
+    fn identique_quadruplet(
+        &self,
+        value: (u8, u16, u32, Option<u64>),
+    ) -> (u8, u16, u32, Option<u64>) {
+        value
+    }
+    fn identique_tuples(&self, value: Vec<Option<(String, u32)>>) -> Vec<Option<(String, u32)>> {
+        value
+    }
+    fn identique_unions(
+        &self,
+        value: Vec<Option<Union2<u32, String>>>,
+    ) -> Vec<Option<Union2<u32, String>>> {
+        value
+    }
     fn identique_nombres_signes(
         &self,
         value: DictionnaireNombresSignes,
@@ -166,6 +221,13 @@ impl Stringifier {
     fn to_string_double(&self, value: f64) -> String {
         value.to_string()
     }
+    // Timestamps are stringified as milliseconds since the epoch, which is easy to check.
+    fn to_string_timestamp(&self, value: SystemTime) -> String {
+        match value.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => after.as_millis().to_string(),
+            Err(e) => format!("-{}", e.duration().as_millis()),
+        }
+    }
     fn to_string_boolean(&self, value: bool) -> String {
         value.to_string()
     }
@@ -1,5 +1,12 @@
 import uniffi.rondpoint.*
 
+assert(NOM == "rondpoint")
+assert(VRAI)
+assert(NEGATIF == -42)
+assert(GRAND == ULong.MAX_VALUE)
+assert(DEMI == 0.5f)
+assert(GABARIT == "\$NOM \\(NOM) C:\\dossier\\n")
+
 val dico = Dictionnaire(Enumeration.DEUX, true, 0u, 123456789u)
 val copyDico = copieDictionnaire(dico)
 assert(dico == copyDico)
@@ -7,6 +14,8 @@ assert(dico == copyDico)
 assert(copieEnumeration(Enumeration.DEUX) == Enumeration.DEUX)
 assert(copieEnumerations(listOf(Enumeration.UN, Enumeration.DEUX)) == listOf(Enumeration.UN, Enumeration.DEUX))
 assert(copieCarte(mapOf("1" to Enumeration.UN, "2" to Enumeration.DEUX)) == mapOf("1" to Enumeration.UN, "2" to Enumeration.DEUX))
+assert(copieCarteNombres(mapOf(0UL to "zéro", ULong.MAX_VALUE to "max")) == mapOf(0UL to "zéro", ULong.MAX_VALUE to "max"))
+assert(copieCarteEnumerations(mapOf(Enumeration.UN to 1u, Enumeration.TROIS to 3u)) == mapOf(Enumeration.UN to 1u, Enumeration.TROIS to 3u))
 
 assert(switcheroo(false))
 
@@ -51,6 +60,54 @@ listOf(0.0, 1.0, Double.MIN_VALUE, Double.MAX_VALUE).affirmAllerRetour(rt::ident
 listOf("", "abc", "null\u0000byte", "été", "ښي لاس ته لوستلو لوستل", "😻emoji 👨‍👧‍👦multi-emoji, 🇨🇭a flag, a canal, panama")
     .affirmAllerRetour(rt::identiqueString)
 
+// Borrowed strings and bytes
+listOf("", "abc", "null\u0000byte", "été", "😻emoji 👨‍👧‍👦multi-emoji")
+    .affirmAllerRetour(rt::identiqueChaineEmpruntee)
+listOf(listOf(), listOf(0x00u), listOf(0x01u, 0xFFu, 0x00u, 0x80u))
+    .map { it.map { b -> b.toUByte() } }
+    .affirmAllerRetour(rt::identiqueOctetsEmpruntes)
+
+// Bytes. `ByteArray` doesn't have value equality, so we compare the contents.
+listOf(byteArrayOf(), byteArrayOf(0x00), byteArrayOf(0x01, 0xFF.toByte(), 0x00, 0x80.toByte())).forEach { v ->
+    assert(rt.identiqueTampon(v).contentEquals(v)) { "Round-trip failure: ${v.contentToString()}" }
+    assert(rt.identiqueTamponEmprunte(v).contentEquals(v)) { "Round-trip failure: ${v.contentToString()}" }
+}
+rt.identiqueTampons(listOf(null, byteArrayOf(0x61))).let {
+    assert(it[0] == null && it[1]!!.contentEquals(byteArrayOf(0x61)))
+}
+
+// Timestamps and durations
+val timestamps = listOf(
+    java.time.Instant.EPOCH,
+    java.time.Instant.ofEpochMilli(1500),
+    java.time.Instant.ofEpochMilli(-1500),
+    java.time.Instant.ofEpochSecond(1614834367, 891011121)
+)
+timestamps.affirmAllerRetour(rt::identiqueHorodatage)
+listOf(timestamps).affirmAllerRetour(rt::identiqueHorodatages)
+listOf(java.time.Duration.ZERO, java.time.Duration.ofNanos(1), java.time.Duration.ofSeconds(3 * 86400, 500_000_000))
+    .affirmAllerRetour(rt::identiqueDuree)
+try {
+    rt.identiqueDuree(java.time.Duration.ofSeconds(-1))
+    throw RuntimeException("Should have rejected a negative duration")
+} catch (e: IllegalArgumentException) {
+    // It's okay!
+}
+
+// Unions
+listOf(ChaineOuDictionnaire.A("abc"), ChaineOuDictionnaire.B(dico))
+    .affirmAllerRetour(rt::identiqueUnion)
+listOf(listOf(), listOf(null, Unionu32Orstring.A(42u), Unionu32Orstring.B("42")))
+    .affirmAllerRetour(rt::identiqueUnions)
+
+// Tuples
+listOf<ChaineEtNombre>(Pair("", 0u), Pair("abc", 42u)).affirmAllerRetour(rt::identiqueTuple)
+listOf(
+    Quadruplet(0u, 0u, 0u, null),
+    Quadruplet(UByte.MAX_VALUE, UShort.MAX_VALUE, UInt.MAX_VALUE, ULong.MAX_VALUE)
+).affirmAllerRetour(rt::identiqueQuadruplet)
+listOf(listOf(), listOf(null, Pair("abc", 42u))).affirmAllerRetour(rt::identiqueTuples)
+
 listOf(-1, 0, 1).map { DictionnaireNombresSignes(it.toByte(), it.toShort(), it.toInt(), it.toLong()) }
     .affirmAllerRetour(rt::identiqueNombresSignes)
 
@@ -118,6 +175,10 @@ listOf(0.0F, 1.0F, -1.0F, Float.MIN_VALUE, Float.MAX_VALUE).affirmEnchaine(st::t
 // MIN_VALUE is 4.9E-324. Accuracy and formatting get weird at small sizes.
 listOf(0.0, 1.0, -1.0, Double.MIN_VALUE, Double.MAX_VALUE).affirmEnchaine(st::toStringDouble)  { s, n -> s.toDouble() == n }
 
+// Timestamps
+listOf(0L, 1500L, -1500L).map { java.time.Instant.ofEpochMilli(it) }
+    .affirmEnchaine(st::toStringTimestamp) { s, n -> s.toLong() == n.toEpochMilli() }
+
 st.destroy()
 
 // Prove to ourselves that default arguments are being used.
@@ -0,0 +1,169 @@
+import assert from "node:assert/strict";
+import {
+    copieCarte, copieCarteEnumerations, copieCarteNombres, copieDictionnaire, copieEnumeration, copieEnumerations, switcheroo,
+    DEMI, GABARIT, GRAND, NEGATIF, NOM, VRAI,
+    Dictionnaire, Enumeration, Optionneur, OptionneurDictionnaire, Retourneur, Stringifier,
+    ChaineOuDictionnaire, Unionu32Orstring,
+} from "./rondpoint.mjs";
+
+const dico = new Dictionnaire(Enumeration.DEUX, true, 0, 123456789n);
+const copyDico = copieDictionnaire(dico);
+assert.deepEqual(copyDico, dico);
+
+assert.equal(copieEnumeration(Enumeration.DEUX), Enumeration.DEUX);
+
+assert.equal(NOM, "rondpoint");
+assert.equal(VRAI, true);
+assert.equal(NEGATIF, -42);
+assert.equal(GRAND, 2n ** 64n - 1n);
+assert.equal(DEMI, 0.5);
+assert.equal(GABARIT, "$NOM \\(NOM) C:\\dossier\\n");
+assert.deepEqual(copieEnumerations([Enumeration.UN, Enumeration.DEUX]), [Enumeration.UN, Enumeration.DEUX]);
+const carte = new Map([["1", Enumeration.UN], ["2", Enumeration.DEUX]]);
+assert.deepEqual(copieCarte(carte), carte);
+const carteNombres = new Map([[0n, "zéro"], [2n ** 64n - 1n, "max"]]);
+assert.deepEqual(copieCarteNombres(carteNombres), carteNombres);
+const carteEnumerations = new Map([[Enumeration.UN, 1], [Enumeration.TROIS, 3]]);
+assert.deepEqual(copieCarteEnumerations(carteEnumerations), carteEnumerations);
+
+assert.equal(switcheroo(false), true);
+
+// Test the roundtrip across the FFI.
+// This shows that the values we send come back in exactly the same state as we sent them.
+// i.e. it shows that lowering from JS and lifting into rust is symmetrical with
+//      lowering from rust and lifting into JS.
+const rt = new Retourneur();
+
+function affirmAllerRetour(vals, identique) {
+    for (const v of vals) {
+        assert.equal(identique.call(rt, v), v, `Round-trip failure: ${v}`);
+    }
+}
+
+const MIN_I8 = -(2 ** 7);
+const MAX_I8 = 2 ** 7 - 1;
+const MIN_I16 = -(2 ** 15);
+const MAX_I16 = 2 ** 15 - 1;
+const MIN_I32 = -(2 ** 31);
+const MAX_I32 = 2 ** 31 - 1;
+const MIN_I64 = -(2n ** 63n);
+const MAX_I64 = 2n ** 63n - 1n;
+
+// JS numbers are always doubles, so won't round-trip through f32 correctly.
+// This truncates them appropriately.
+const F32_ONE_THIRD = Math.fround(1.0 / 3);
+
+affirmAllerRetour([true, false], rt.identiqueBoolean);
+affirmAllerRetour([MIN_I8, -1, 0, 1, MAX_I8], rt.identiqueI8);
+affirmAllerRetour([0x00, 0x12, 0xFF], rt.identiqueU8);
+affirmAllerRetour([MIN_I16, -1, 0, 1, MAX_I16], rt.identiqueI16);
+affirmAllerRetour([0x0000, 0x1234, 0xFFFF], rt.identiqueU16);
+affirmAllerRetour([MIN_I32, -1, 0, 1, MAX_I32], rt.identiqueI32);
+affirmAllerRetour([0x00000000, 0x12345678, 0xFFFFFFFF], rt.identiqueU32);
+affirmAllerRetour([MIN_I64, -1n, 0n, 1n, MAX_I64], rt.identiqueI64);
+affirmAllerRetour([0x0n, 0x1234567890ABCDEFn, 0xFFFFFFFFFFFFFFFFn], rt.identiqueU64);
+affirmAllerRetour([0.0, 0.5, 0.25, 1.0, F32_ONE_THIRD], rt.identiqueFloat);
+affirmAllerRetour([0.0, 0.5, 0.25, 1.0, 1.0 / 3, Number.MAX_VALUE, Number.MIN_VALUE], rt.identiqueDouble);
+affirmAllerRetour(
+    ["", "abc", "été", "ښي لاس ته لوستلو لوستل", "😻emoji 👨‍👧‍👦multi-emoji, 🇨🇭a flag, a canal, panama"],
+    rt.identiqueString,
+);
+affirmAllerRetour(["", "abc", "été", "😻emoji 👨‍👧‍👦multi-emoji"], rt.identiqueChaineEmpruntee);
+for (const v of [[], [0x00], [0x01, 0xFF, 0x00, 0x80]]) {
+    assert.deepEqual(rt.identiqueOctetsEmpruntes(v), v, `Round-trip failure: ${v}`);
+}
+for (const v of [new Uint8Array(), Uint8Array.of(0x00), Uint8Array.of(0x01, 0xFF, 0x00, 0x80)]) {
+    assert.deepEqual(rt.identiqueTampon(v), v, `Round-trip failure: ${v}`);
+    assert.deepEqual(rt.identiqueTamponEmprunte(v), v, `Round-trip failure: ${v}`);
+}
+assert.deepEqual(rt.identiqueTampons([null, new Uint8Array(), Uint8Array.of(0x61)]), [null, new Uint8Array(), Uint8Array.of(0x61)]);
+
+// Timestamps are `Date`s, and durations are numbers of milliseconds.
+// Rust's `SystemTime` can't represent times before the epoch on `wasm32-unknown-unknown`,
+// so unlike the other bindings, we don't test those here.
+const timestamps = [new Date(0), new Date(1500), new Date("2021-03-04T05:06:07.891Z")];
+for (const v of timestamps) {
+    assert.deepEqual(rt.identiqueHorodatage(v), v, `Round-trip failure: ${v}`);
+}
+assert.deepEqual(rt.identiqueHorodatages(timestamps), timestamps);
+affirmAllerRetour([0, 0.000001, 1500, 3 * 86400 * 1000 + 1500], rt.identiqueDuree);
+assert.throws(() => rt.identiqueDuree(-1), RangeError);
+
+// Unions have a variant subclass for each member type.
+for (const v of [new ChaineOuDictionnaire.A("abc"), new ChaineOuDictionnaire.B(dico)]) {
+    const copy = rt.identiqueUnion(v);
+    assert.ok(copy instanceof v.constructor, `Round-trip failure: ${v}`);
+    assert.deepEqual(copy.value, v.value, `Round-trip failure: ${v}`);
+}
+const unions = rt.identiqueUnions([null, new Unionu32Orstring.A(42), new Unionu32Orstring.B("42")]);
+assert.equal(unions[0], null);
+assert.ok(unions[1] instanceof Unionu32Orstring.A && unions[1].value === 42);
+assert.ok(unions[2] instanceof Unionu32Orstring.B && unions[2].value === "42");
+assert.throws(() => rt.identiqueUnion("abc"), TypeError);
+
+// Tuples are arrays with one item per element.
+for (const v of [["", 0], ["abc", 42]]) {
+    assert.deepEqual(rt.identiqueTuple(v), v, `Round-trip failure: ${v}`);
+}
+for (const v of [[0, 0, 0, null], [0xFF, 0xFFFF, 0xFFFFFFFF, 2n ** 64n - 1n]]) {
+    assert.deepEqual(rt.identiqueQuadruplet(v), v, `Round-trip failure: ${v}`);
+}
+for (const v of [[], [null, ["abc", 42]]]) {
+    assert.deepEqual(rt.identiqueTuples(v), v, `Round-trip failure: ${v}`);
+}
+assert.throws(() => rt.identiqueTuple(["abc"]), TypeError);
+
+const dicoDefaults = new OptionneurDictionnaire();
+assert.deepEqual(rt.identiqueOptionneurDictionnaire(dicoDefaults), dicoDefaults);
+
+// Test one way across the FFI.
+//
+// We send one representation of a value to lib.rs, and it transforms it into another, a string.
+// lib.rs sends the string back, and then we compare here in JS.
+const st = new Stringifier();
+
+function affirmEnchaine(vals, toString) {
+    for (const v of vals) {
+        assert.equal(toString.call(st, v), String(v), `String compare error ${v}`);
+    }
+}
+
+assert.equal(st.wellKnownString("javascript"), "uniffi 💚 javascript!");
+
+affirmEnchaine([true, false], st.toStringBoolean);
+affirmEnchaine([MIN_I8, -1, 0, 1, MAX_I8], st.toStringI8);
+affirmEnchaine([0x00, 0x12, 0xFF], st.toStringU8);
+affirmEnchaine([MIN_I16, -1, 0, 1, MAX_I16], st.toStringI16);
+affirmEnchaine([0x0000, 0x1234, 0xFFFF], st.toStringU16);
+affirmEnchaine([MIN_I32, -1, 0, 1, MAX_I32], st.toStringI32);
+affirmEnchaine([0x00000000, 0x12345678, 0xFFFFFFFF], st.toStringU32);
+affirmEnchaine([MIN_I64, -1n, 0n, 1n, MAX_I64], st.toStringI64);
+affirmEnchaine([0x0n, 0x1234567890ABCDEFn, 0xFFFFFFFFFFFFFFFFn], st.toStringU64);
+affirmEnchaine([0.0, 0.5, 0.25, 1.0], st.toStringFloat);
+assert.equal(st.toStringFloat(F32_ONE_THIRD), "0.33333334");
+affirmEnchaine([0.0, 0.5, 0.25, 1.0, 1.0 / 3], st.toStringDouble);
+assert.equal(st.toStringTimestamp(new Date(0)), "0");
+assert.equal(st.toStringTimestamp(new Date(1500)), "1500");
+
+// Default arguments are applied on the JS side.
+const op = new Optionneur();
+assert.equal(op.sinonBoolean(), false);
+assert.equal(op.sinonString(), "default");
+assert.deepEqual(op.sinonSequence(), []);
+assert.equal(op.sinonNull(), null);
+assert.equal(op.sinonZero(), 0);
+assert.equal(op.sinonU8Dec(), 42);
+assert.equal(op.sinonI8Dec(), -42);
+assert.equal(op.sinonU64Dec(), 42n);
+assert.equal(op.sinonU8Hex(), 0xff);
+assert.equal(op.sinonI8Hex(), -0x7f);
+assert.equal(op.sinonU32Hex(), 0xffffffff);
+assert.equal(op.sinonU64Hex(), 0xffffffffffffffffn);
+assert.equal(op.sinonI64Hex(), 0x7fffffffffffffffn);
+assert.equal(op.sinonU32Oct(), 0o755);
+assert.equal(op.sinonF64(), 42.1);
+assert.equal(op.sinonEnum(), Enumeration.TROIS);
+
+assert.equal(op.sinonString("foo"), "foo");
+assert.equal(op.sinonNull("bar"), "bar");
+assert.equal(op.sinonZero(null), null);
@@ -1,14 +1,30 @@
 import sys
 import ctypes
+import datetime
 from rondpoint import *
 
 dico = Dictionnaire(Enumeration.DEUX, True, 0, 123456789)
 copyDico = copie_dictionnaire(dico)
 assert dico == copyDico
 
+# If the bindings pass an argument that Rust can't lift, they get a specific error saying which
+# field it was. We simulate a bug in the bindings by making them write an invalid boolean.
+import rondpoint
+writeBool = rondpoint.RustBufferBuilder.writeBool
+rondpoint.RustBufferBuilder.writeBool = lambda self, v: self._pack_into(1, ">b", 2)
+try:
+    copie_dictionnaire(dico)
+    raise AssertionError("Should have thrown an InvalidArgumentError")
+except InvalidArgumentError as e:
+    assert "`d.deux`: unexpected byte for Boolean" in str(e)
+finally:
+    rondpoint.RustBufferBuilder.writeBool = writeBool
+
 assert copie_enumeration(Enumeration.DEUX) == Enumeration.DEUX
 assert copie_enumerations([Enumeration.UN, Enumeration.DEUX]) == [Enumeration.UN, Enumeration.DEUX]
 assert copie_carte({"1": Enumeration.UN, "2": Enumeration.DEUX}) == {"1": Enumeration.UN, "2": Enumeration.DEUX}
+assert copie_carte_nombres({0: "zéro", 0xFFFFFFFFFFFFFFFF: "max"}) == {0: "zéro", 0xFFFFFFFFFFFFFFFF: "max"}
+assert copie_carte_enumerations({Enumeration.UN: 1, Enumeration.TROIS: 3}) == {Enumeration.UN: 1, Enumeration.TROIS: 3}
 
 assert switcheroo(False) is True
 
@@ -70,6 +86,60 @@ affirmAllerRetour(
   rt.identique_string
 )
 
+# Borrowed strings and bytes
+affirmAllerRetour(["", "abc", "été", "😻emoji 👨‍👧‍👦multi-emoji"], rt.identique_chaine_empruntee)
+affirmAllerRetour([[], [0x00], [0x01, 0xFF, 0x00, 0x80]], rt.identique_octets_empruntes)
+
+# Bytes
+affirmAllerRetour([b"", b"\x00", b"\x01\xff\x00\x80", bytes(range(256)) * 100], rt.identique_tampon)
+affirmAllerRetour([b"", b"\x00", b"\x01\xff\x00\x80"], rt.identique_tampon_emprunte)
+affirmAllerRetour([[], [None, b"", b"abc"]], rt.identique_tampons)
+
+# Timestamps and durations
+EPOCH = datetime.datetime.fromtimestamp(0, tz=datetime.timezone.utc)
+TIMESTAMPS = [
+  EPOCH,
+  EPOCH + datetime.timedelta(seconds=1.5),
+  EPOCH - datetime.timedelta(seconds=1.5),
+  datetime.datetime(2021, 3, 4, 5, 6, 7, 891011, tzinfo=datetime.timezone.utc),
+]
+affirmAllerRetour(TIMESTAMPS, rt.identique_horodatage)
+affirmAllerRetour([TIMESTAMPS], rt.identique_horodatages)
+affirmAllerRetour(
+  [datetime.timedelta(0), datetime.timedelta(microseconds=1), datetime.timedelta(days=3, seconds=1.5)],
+  rt.identique_duree
+)
+try:
+  rt.identique_duree(datetime.timedelta(seconds=-1))
+  assert False, "negative durations should be rejected"
+except ValueError:
+  pass
+
+# Unions, which have a variant class for each member type.
+affirmAllerRetour(
+  [ChaineOuDictionnaire.A("abc"), ChaineOuDictionnaire.B(dico)],
+  rt.identique_union
+)
+affirmAllerRetour([[], [None, Unionu32Orstring.A(42), Unionu32Orstring.B("42")]], rt.identique_unions)
+try:
+  rt.identique_union("abc")
+  assert False, "union values must be one of the variant classes"
+except TypeError:
+  pass
+
+# Tuples
+affirmAllerRetour([("", 0), ("abc", 42)], rt.identique_tuple)
+affirmAllerRetour([(0, 0, 0, None), (0xFF, 0xFFFF, 0xFFFFFFFF, 0xFFFFFFFFFFFFFFFF)], rt.identique_quadruplet)
+affirmAllerRetour([[], [None, ("abc", 42)]], rt.identique_tuples)
+
+# Constants
+assert NOM == "rondpoint"
+assert VRAI is True
+assert NEGATIF == -42
+assert GRAND == 0xFFFFFFFFFFFFFFFF
+assert DEMI == 0.5
+assert GABARIT == "$NOM \\(NOM) C:\\dossier\\n"
+
 # Test one way across the FFI.
 #
 # We send one representation of a value to lib.rs, and it transforms it into another, a string.
@@ -132,3 +202,8 @@ affirmEnchaine(
   st.to_string_double,
   rustyFloatToStr,
 )
+
+# Timestamps
+assert st.to_string_timestamp(EPOCH) == "0"
+assert st.to_string_timestamp(EPOCH + datetime.timedelta(seconds=1.5)) == "1500"
+assert st.to_string_timestamp(EPOCH - datetime.timedelta(seconds=1.5)) == "-1500"
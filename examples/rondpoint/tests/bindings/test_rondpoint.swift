@@ -1,5 +1,13 @@
+import Foundation
 import rondpoint
 
+assert(nom == "rondpoint")
+assert(vrai)
+assert(negatif == -42)
+assert(grand == UInt64.max)
+assert(demi == 0.5)
+assert(gabarit == "$NOM \\(NOM) C:\\dossier\\n")
+
 let dico = Dictionnaire(un: .deux, deux: false, petitNombre: 0, grosNombre: 123456789)
 let copyDico = copieDictionnaire(d: dico)
 assert(dico == copyDico)
@@ -7,6 +15,8 @@ assert(dico == copyDico)
 assert(copieEnumeration(e: .deux) == .deux)
 assert(copieEnumerations(e: [.un, .deux]) == [.un, .deux])
 assert(copieCarte(c: ["1": .un, "2": .deux]) == ["1": .un, "2": .deux])
+assert(copieCarteNombres(c: [0: "zéro", .max: "max"]) == [0: "zéro", .max: "max"])
+assert(copieCarteEnumerations(c: [.un: 1, .trois: 3]) == [.un: 1, .trois: 3])
 
 assert(switcheroo(b: false))
 
@@ -45,6 +55,40 @@ let rt = Retourneur()
 ["", "abc", "null\0byte", "été", "ښي لاس ته لوستلو لوستل", "😻emoji 👨‍👧‍👦multi-emoji, 🇨🇭a flag, a canal, panama"]
     .affirmAllerRetour(rt.identiqueString)
 
+// Borrowed strings and bytes
+["", "abc", "null\0byte", "été", "😻emoji 👨‍👧‍👦multi-emoji"]
+    .affirmAllerRetour(rt.identiqueChaineEmpruntee)
+[[], [0x00], [0x01, 0xFF, 0x00, 0x80]]
+    .affirmAllerRetour(rt.identiqueOctetsEmpruntes)
+
+// Bytes
+[Data(), Data([0x00]), Data([0x01, 0xFF, 0x00, 0x80])]
+    .affirmAllerRetour(rt.identiqueTampon)
+[Data(), Data([0x00]), Data([0x01, 0xFF, 0x00, 0x80])]
+    .affirmAllerRetour(rt.identiqueTamponEmprunte)
+[[], [nil, Data(), Data([0x61])]]
+    .affirmAllerRetour(rt.identiqueTampons)
+
+// Timestamps and durations
+let timestamps = [Date(timeIntervalSince1970: 0), Date(timeIntervalSince1970: 1.5), Date(timeIntervalSince1970: -1.5)]
+timestamps.affirmAllerRetour(rt.identiqueHorodatage)
+[timestamps].affirmAllerRetour(rt.identiqueHorodatages)
+[0, 1.0e-9, 3 * 86400 + 0.5].affirmAllerRetour(rt.identiqueDuree)
+
+// Unions
+[ChaineOuDictionnaire.a("abc"), .b(dico)].affirmAllerRetour(rt.identiqueUnion)
+[Unionu32Orstring.a(42)].affirmAllerRetour { rt.identiqueUnions(value: [$0])[0]! }
+[[], [nil, .a(42), .b("42")]].affirmAllerRetour(rt.identiqueUnions)
+
+// Tuples, which aren't `Equatable` so we compare them directly.
+assert(rt.identiqueTuple(value: ("abc", 42)) == ("abc", 42))
+assert(rt.identiqueQuadruplet(value: (.max, .max, .max, nil)) == (.max, .max, .max, nil))
+assert(rt.identiqueQuadruplet(value: (0, 0, 0, .max)) == (0, 0, 0, .max))
+let quadruplet: Quadruplet = (1, 2, 3, 4)
+assert(rt.identiqueQuadruplet(value: quadruplet) == quadruplet)
+let tuples = rt.identiqueTuples(value: [nil, ("abc", 42)])
+assert(tuples.count == 2 && tuples[0] == nil && tuples[1]! == ("abc", 42))
+
 // Test one way across the FFI.
 //
 // We send one representation of a value to lib.rs, and it transforms it into another, a string.
@@ -90,6 +134,10 @@ assert("uniffi 💚 swift!" == wellKnown, "wellKnownString 'uniffi 💚 swift!'
 // Doubles
 [.zero, 1, -1, .leastNonzeroMagnitude, .greatestFiniteMagnitude].affirmEnchaine(st.toStringDouble) { Double.init($0) == $1 }
 
+// Timestamps
+[0, 1.5, -1.5].map { Date(timeIntervalSince1970: $0) }
+    .affirmEnchaine(st.toStringTimestamp) { Int64($0)! == Int64(($1.timeIntervalSince1970 * 1000).rounded()) }
+
 // Some extension functions for testing the results of roundtripping and stringifying
 extension Array where Element: Equatable {
     static func defaultEquals(_ observed: String, expected: Element) -> Bool {
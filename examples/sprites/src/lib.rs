@@ -2,6 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::sync::atomic::{AtomicU64, Ordering};
+
 // A point in two-dimensional space.
 #[derive(Debug, Clone)]
 pub struct Point {
@@ -25,23 +27,63 @@ pub fn translate(p: &Point, v: Vector) -> Point {
     }
 }
 
+// The number of sprites that haven't been dropped yet, so that the tests can
+// check that the bindings free them.
+static LIVE_SPRITES: AtomicU64 = AtomicU64::new(0);
+
+fn live_sprites() -> u64 {
+    LIVE_SPRITES.load(Ordering::SeqCst)
+}
+
+// Counts itself in `LIVE_SPRITES` for as long as the sprite that owns it is alive.
+#[derive(Debug)]
+struct LiveSprite;
+
+impl LiveSprite {
+    fn new() -> Self {
+        LIVE_SPRITES.fetch_add(1, Ordering::SeqCst);
+        LiveSprite
+    }
+}
+
+impl Clone for LiveSprite {
+    fn clone(&self) -> Self {
+        LiveSprite::new()
+    }
+}
+
+impl Drop for LiveSprite {
+    fn drop(&mut self) {
+        LIVE_SPRITES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 // An entity in our imaginary world, which occupies a position in space
 // and which can move about over time.
 #[derive(Debug, Clone)]
 pub struct Sprite {
     current_position: Point,
+    name: String,
+    move_count: u32,
+    _live: LiveSprite,
 }
 
 impl Sprite {
     fn new(initial_position: Option<Point>) -> Sprite {
         Sprite {
             current_position: initial_position.unwrap_or_else(|| Point { x: 0.0, y: 0.0 }),
+            name: String::new(),
+            move_count: 0,
+            _live: LiveSprite::new(),
         }
     }
 
     fn new_relative_to(reference: Point, direction: Vector) -> Sprite {
         Sprite {
             current_position: translate(&reference, direction),
+            name: String::new(),
+            move_count: 0,
+            _live: LiveSprite::new(),
         }
     }
 
@@ -51,10 +93,31 @@ impl Sprite {
 
     fn move_to(&mut self, position: Point) {
         self.current_position = position;
+        self.move_count += 1;
     }
 
     fn move_by(&mut self, direction: Vector) {
-        self.current_position = translate(&self.current_position, direction)
+        self.current_position = translate(&self.current_position, direction);
+        self.move_count += 1;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    fn get_move_count(&self) -> u32 {
+        self.move_count
+    }
+
+    fn direction_between(start: Point, end: Point) -> Vector {
+        Vector {
+            dx: end.x - start.x,
+            dy: end.y - start.y,
+        }
     }
 }
 
@@ -11,6 +11,11 @@ assert( s.getPosition() == Point(1.0, 2.0) )
 
 s.moveBy(Vector(-4.0, 2.0))
 assert( s.getPosition() == Point(-3.0, 4.0) )
+assert( s.moveCount == 2 )
+
+assert( s.name == "" )
+s.name = "ghost"
+assert( s.name == "ghost" )
 
 s.destroy()
 try {
@@ -20,6 +25,60 @@ try {
     assert(true)
 }
 
+// Objects can be closed like any other `AutoCloseable`, including more than once.
+val sclosed = Sprite(null)
+sclosed.use {
+    assert( it.getPosition() == Point(0.0, 0.0) )
+}
+sclosed.close()
+try {
+    sclosed.getPosition()
+    assert(false) { "Should not be able to call anything after `close`" }
+} catch(e: IllegalStateException) {
+    assert(true)
+}
+
+val liveBefore = liveSprites()
+
+// Destroying an object while other threads are calling its methods lets the calls in
+// progress finish, and frees it once they have. Any calls after that fail cleanly.
+val shared = Sprite(null)
+val started = java.util.concurrent.CountDownLatch(4)
+val failure = java.util.concurrent.atomic.AtomicReference<Throwable?>(null)
+val movers = (1..4).map {
+    kotlin.concurrent.thread {
+        started.countDown()
+        try {
+            while (true) {
+                shared.moveBy(Vector(1.0, 0.0))
+            }
+        } catch (e: IllegalStateException) {
+            // The sprite has been destroyed.
+        } catch (e: Throwable) {
+            failure.set(e)
+        }
+    }
+}
+started.await()
+Thread.sleep(10)
+shared.destroy()
+movers.forEach { it.join() }
+failure.get()?.let { throw it }
+assert( liveSprites() == liveBefore )
+
+// Objects that are garbage-collected without being destroyed are freed automatically.
+for (i in 1..1000) {
+    Sprite(Point(i.toDouble(), 0.0))
+}
+assert( liveSprites() == liveBefore + 1000uL )
+val deadline = System.currentTimeMillis() + 10_000
+while (liveSprites() > liveBefore && System.currentTimeMillis() < deadline) {
+    System.gc()
+    Thread.sleep(10)
+}
+assert( liveSprites() == liveBefore ) { "Garbage-collected sprites should have been freed" }
+
 val srel = Sprite.newRelativeTo(Point(0.0, 1.0), Vector(1.0, 1.5))
 assert( srel.getPosition() == Point(1.0, 2.5) )
 
+assert( Sprite.directionBetween(Point(0.0, 1.0), Point(2.0, 0.0)) == Vector(2.0, -1.0) )
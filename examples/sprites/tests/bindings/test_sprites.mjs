@@ -0,0 +1,29 @@
+import assert from "node:assert/strict";
+import { Point, Sprite, Vector } from "./sprites.mjs";
+
+const sempty = new Sprite(null);
+assert.deepEqual(sempty.getPosition(), new Point(0, 0));
+
+const s = new Sprite(new Point(0, 1));
+assert.deepEqual(s.getPosition(), new Point(0, 1));
+
+s.moveTo(new Point(1, 2));
+assert.deepEqual(s.getPosition(), new Point(1, 2));
+
+s.moveBy(new Vector(-4, 2));
+assert.deepEqual(s.getPosition(), new Point(-3, 4));
+assert.equal(s.moveCount, 2);
+
+assert.equal(s.name, "");
+s.name = "ghost";
+assert.equal(s.name, "ghost");
+
+const srel = Sprite.newRelativeTo(new Point(0, 1), new Vector(1, 1.5));
+assert.deepEqual(srel.getPosition(), new Point(1, 2.5));
+
+assert.deepEqual(Sprite.directionBetween(new Point(0, 1), new Point(2, 0)), new Vector(2, -1));
+
+// Ensure that destroying is idempotent, and that using a destroyed object throws.
+s.destroy();
+s.destroy();
+assert.throws(() => s.getPosition(), /destroyed/);
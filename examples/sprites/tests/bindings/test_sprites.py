@@ -1,3 +1,6 @@
+import threading
+import time
+
 from sprites import *
 
 sempty = Sprite(None)
@@ -11,7 +14,76 @@ assert s.get_position() == Point(1, 2)
 
 s.move_by(Vector(-4, 2))
 assert s.get_position() == Point(-3, 4)
+assert s.move_count == 2
+
+assert s.name == ""
+s.name = "ghost"
+assert s.name == "ghost"
 
 srel = Sprite.new_relative_to(Point(0, 1), Vector(1, 1.5))
 assert srel.get_position() == Point(1, 2.5)
 
+assert Sprite.direction_between(Point(0, 1), Point(2, 0)) == Vector(2, -1)
+
+# Objects can be freed deterministically, either explicitly or with a `with` block,
+# and using them afterwards raises a clear error rather than confusing Rust.
+s.destroy()
+s.destroy()
+try:
+    s.move_by(Vector(0, 0))
+    assert(not("Should not be able to call anything after `destroy`"))
+except ValueError as e:
+    assert "destroyed" in str(e)
+
+with Sprite(Point(0, 1)) as swith:
+    assert swith.get_position() == Point(0, 1)
+try:
+    swith.name
+    assert(not("Should not be able to call anything after the `with` block"))
+except ValueError:
+    pass
+
+sclosed = Sprite(None)
+sclosed.close()
+try:
+    sclosed.name = "ghost"
+    assert(not("Should not be able to call anything after `close`"))
+except ValueError:
+    pass
+
+# Destroying an object during a call leaves it alive until the call returns.
+live_before = live_sprites()
+sbusy = Sprite(None)
+with sbusy._uniffi_calling():
+    sbusy.destroy()
+    assert live_sprites() == live_before + 1
+assert live_sprites() == live_before
+
+# So other threads can carry on with their calls while one destroys the object,
+# after which their calls fail cleanly, and the object is freed exactly once.
+shared = Sprite(None)
+errors = []
+
+def keep_moving():
+    try:
+        while True:
+            shared.move_by(Vector(1, 0))
+    except ValueError:
+        pass
+    except Exception as e:
+        errors.append(e)
+
+movers = [threading.Thread(target=keep_moving) for _ in range(4)]
+for mover in movers:
+    mover.start()
+time.sleep(0.01)
+shared.destroy()
+for mover in movers:
+    mover.join()
+assert not errors
+assert live_sprites() == live_before
+
+assert translate.__doc__ == "Move from the given Point, according to the given Vector."
+assert Point.__doc__ == "A point in two-dimensional space."
+assert Sprite.move_to.__doc__ == "Move directly to the given position."
+assert Sprite.name.__doc__ == "A name to help tell sprites apart."
@@ -11,6 +11,13 @@ assert( s.getPosition() == Point(x: 1, y: 2))
 
 s.moveBy(direction: Vector(dx: -4, dy: 2))
 assert( s.getPosition() == Point(x: -3, y: 4))
+assert( s.moveCount == 2 )
+
+assert( s.name == "" )
+s.name = "ghost"
+assert( s.name == "ghost" )
 
 let srel = Sprite.newRelativeTo(reference: Point(x: 0.0, y: 1.0), direction: Vector(dx: 1, dy: 1.5))
 assert( srel.getPosition() == Point(x: 1.0, y: 2.5) )
+
+assert( Sprite.directionBetween(start: Point(x: 0, y: 1), end: Point(x: 2, y: 0)) == Vector(dx: 2, dy: -1) )
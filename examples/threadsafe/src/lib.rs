@@ -2,7 +2,10 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::cell::Cell;
+use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Arc;
 
 /// Simulation of a task doing something to keep a thread busy.
 /// Up until now, everything has been synchronous and blocking, so
@@ -85,4 +88,120 @@ impl ThreadsafeCounter {
     }
 }
 
+// This is the same as the `ThreadsafeCounter`, but uses the `PointerHandle` attribute
+// to tell uniffi to hand out pointers to it rather than storing it in a handle map,
+// so calling its methods doesn't need to take any locks at all.
+struct PointerHandleCounter {
+    inner: ThreadsafeCounter,
+}
+
+static_assertions::assert_impl_all!(PointerHandleCounter: Send, Sync);
+
+impl PointerHandleCounter {
+    fn new() -> Self {
+        Self {
+            inner: ThreadsafeCounter::new(),
+        }
+    }
+
+    fn busy_wait(&self, ms: i32) {
+        self.inner.busy_wait(ms)
+    }
+
+    fn increment_if_busy(&self) -> i32 {
+        self.inner.increment_if_busy()
+    }
+}
+
+// This counter does its busy waiting on a background thread, so that the caller doesn't
+// have to. Its constructor returns an `Arc<Self>`, and `busy_wait_in_background` takes
+// `self: Arc<Self>`, so that it can give the background thread its own reference to the
+// counter. That reference keeps the counter alive until the thread finishes, even if the
+// foreign language code frees it in the meantime.
+struct BackgroundCounter {
+    inner: ThreadsafeCounter,
+}
+
+static_assertions::assert_impl_all!(BackgroundCounter: Send, Sync);
+
+impl BackgroundCounter {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            inner: ThreadsafeCounter::new(),
+        })
+    }
+
+    fn busy_wait_in_background(self: Arc<Self>, ms: i32) {
+        // Mark the counter as busy before returning, so the caller sees it straight away.
+        self.inner.is_busy.store(true, Ordering::SeqCst);
+        std::thread::spawn(move || self.inner.busy_wait(ms));
+    }
+
+    fn is_busy(&self) -> bool {
+        self.inner.is_busy.load(Ordering::SeqCst)
+    }
+
+    fn increment_if_busy(&self) -> i32 {
+        self.inner.increment_if_busy()
+    }
+}
+
+// This counter relies on uniffi to do the locking, like the first one, but uses the
+// `RwLock` attribute to tell uniffi to use an `RwLock` rather than a `Mutex`. Its
+// `busy_wait` and `is_busy` methods are declared with `[Self=ByRef]` in the UDL, so
+// they only take the read lock and can be called at the same time, while `increment`
+// takes `&mut self` and so waits for the write lock. (The `is_busy` flag is atomic
+// only because it's written by a method that takes `&self`.)
+struct RwLockCounter {
+    is_busy: AtomicBool,
+    count: i32,
+}
+
+static_assertions::assert_impl_all!(RwLockCounter: Send, Sync);
+
+impl RwLockCounter {
+    fn new() -> Self {
+        Self {
+            is_busy: AtomicBool::new(false),
+            count: 0,
+        }
+    }
+
+    fn busy_wait(&self, ms: i32) {
+        self.is_busy.store(true, Ordering::SeqCst);
+        busy_wait(ms);
+        self.is_busy.store(false, Ordering::SeqCst);
+    }
+
+    fn is_busy(&self) -> bool {
+        self.is_busy.load(Ordering::SeqCst)
+    }
+
+    fn increment(&mut self) -> i32 {
+        self.count += 1;
+        self.count
+    }
+}
+
+// This counter isn't `Send`, because it uses an `Rc`, so it has to be marked with the
+// `ThreadConfined` attribute. uniffi then only lets it be used on the thread that created it.
+struct ThreadConfinedCounter {
+    count: Rc<Cell<i32>>,
+}
+
+static_assertions::assert_not_impl_any!(ThreadConfinedCounter: Send, Sync);
+
+impl ThreadConfinedCounter {
+    fn new() -> Self {
+        Self {
+            count: Rc::new(Cell::new(0)),
+        }
+    }
+
+    fn increment(&mut self) -> i32 {
+        self.count.set(self.count.get() + 1);
+        self.count.get()
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/threadsafe.uniffi.rs"));
@@ -62,4 +62,67 @@ ThreadsafeCounter().use { counter ->
         { counter.incrementIfBusy() }
     )
     assert(count > 0) { "Counter doing the locking: incrementIfBusy=$count" }
-}
\ No newline at end of file
+}
+
+/// The third implementation is also marked `[PointerHandle]`, so uniffi doesn't
+/// need to take any locks to find it either.
+PointerHandleCounter().use { counter ->
+    val count = countWhileBusy(
+        { counter.busyWait(WAIT_FOR) },
+        { counter.incrementIfBusy() }
+    )
+    assert(count > 0) { "Counter doing the locking: incrementIfBusy=$count" }
+}
+/// The fourth implementation does its busy waiting on a background thread, which
+/// holds its own reference to the counter, so we don't need a thread to wait on.
+BackgroundCounter().use { counter ->
+    counter.busyWaitInBackground(WAIT_FOR)
+    var count = 0
+    while (counter.isBusy()) {
+        count = counter.incrementIfBusy()
+        Thread.sleep(10)
+    }
+    assert(count > 0) { "Counter doing the locking: incrementIfBusy=$count" }
+}
+
+/// The fifth implementation is marked `[RwLock]`, so uniffi does the locking, but
+/// methods declared with `[Self=ByRef]` only take the read lock, and so don't block
+/// each other.
+RwLockCounter().use { counter ->
+    val executor = Executors.newFixedThreadPool(1)
+    try {
+        val busyWaiting: Future<Unit> = executor.submit(Callable {
+            counter.busyWait(WAIT_FOR)
+        })
+        var seenBusy = false
+        while (!busyWaiting.isDone()) {
+            seenBusy = seenBusy || counter.isBusy()
+            Thread.sleep(10)
+        }
+        assert(seenBusy) { "Readers should not block each other" }
+    } finally {
+        executor.shutdown()
+    }
+    assert(counter.increment() == 1)
+}
+
+/// The last implementation isn't threadsafe at all, so it's marked `[ThreadConfined]`,
+/// and can only be used on the thread that created it.
+ThreadConfinedCounter().use { counter ->
+    assert(counter.increment() == 1)
+    val executor = Executors.newFixedThreadPool(1)
+    try {
+        val incrementing: Future<Unit> = executor.submit(Callable {
+            try {
+                counter.increment()
+                throw RuntimeException("Should not be able to call it on another thread")
+            } catch (e: WrongThreadException) {
+                // It's ok!
+            }
+        })
+        incrementing.get()
+    } finally {
+        executor.shutdown()
+    }
+    assert(counter.increment() == 2)
+}
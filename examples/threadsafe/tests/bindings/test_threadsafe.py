@@ -0,0 +1,98 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import threading
+import time
+
+from threadsafe import *
+
+# See `test_threadsafe.kts` for what this is testing. Python releases the GIL
+# while calling into Rust, so the calls can run on multiple threads at once.
+def count_while_busy(counter):
+    waiting = threading.Thread(target=counter.busy_wait, args=(WAIT_FOR,))
+    waiting.start()
+    count = 0
+    while waiting.is_alive():
+        count = counter.increment_if_busy()
+        time.sleep(0.01)
+    return count
+
+# `busy_wait` will block the thread while sleeping for this many milliseconds.
+WAIT_FOR = 300
+
+with Counter() as counter:
+    count = count_while_busy(counter)
+    assert count == 0, "Uniffi doing the locking: increment_if_busy={}".format(count)
+
+with ThreadsafeCounter() as counter:
+    count = count_while_busy(counter)
+    assert count > 0, "Counter doing the locking: increment_if_busy={}".format(count)
+
+with PointerHandleCounter() as counter:
+    count = count_while_busy(counter)
+    assert count > 0, "Counter doing the locking: increment_if_busy={}".format(count)
+
+# Using a pointer handle after it's been freed is caught by the bindings,
+# rather than being passed on to Rust.
+counter = PointerHandleCounter()
+counter.destroy()
+try:
+    counter.increment_if_busy()
+    assert(not("Should not be able to call anything after `destroy`"))
+except ValueError:
+    pass
+
+# The `BackgroundCounter` does its busy waiting on a thread of its own, which
+# holds a reference to the counter until it's finished.
+with BackgroundCounter() as counter:
+    counter.busy_wait_in_background(WAIT_FOR)
+    count = 0
+    while counter.is_busy():
+        count = counter.increment_if_busy()
+        time.sleep(0.01)
+    assert count > 0, "Counter doing the locking: increment_if_busy={}".format(count)
+
+# Freeing the counter while the background thread is running doesn't free it
+# out from under the thread.
+counter = BackgroundCounter()
+counter.busy_wait_in_background(WAIT_FOR)
+counter.destroy()
+time.sleep(WAIT_FOR * 2 / 1000)
+
+# The `RwLockCounter` is locked by uniffi, but its `busy_wait` and `is_busy`
+# methods only take the read lock, so they can run at the same time.
+with RwLockCounter() as counter:
+    waiting = threading.Thread(target=counter.busy_wait, args=(WAIT_FOR,))
+    waiting.start()
+    seen_busy = False
+    while waiting.is_alive():
+        seen_busy = seen_busy or counter.is_busy()
+        time.sleep(0.01)
+    assert seen_busy, "Readers should not block each other"
+    assert counter.increment() == 1
+    assert counter.increment() == 2
+
+# The `ThreadConfinedCounter` can only be used on the thread that created it.
+def increment_on_another_thread(counter):
+    errors = []
+    def increment():
+        try:
+            counter.increment()
+        except WrongThreadError as e:
+            errors.append(e)
+    thread = threading.Thread(target=increment)
+    thread.start()
+    thread.join()
+    return errors
+
+with ThreadConfinedCounter() as counter:
+    assert counter.increment() == 1
+    assert len(increment_on_another_thread(counter)) == 1
+    assert counter.increment() == 2
+
+# But it can be freed on any thread, since the garbage collector might do that.
+counter = ThreadConfinedCounter()
+thread = threading.Thread(target=counter.destroy)
+thread.start()
+thread.join()
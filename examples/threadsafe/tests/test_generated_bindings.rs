@@ -1,7 +1,7 @@
 uniffi_macros::build_foreign_language_testcases!(
     "src/threadsafe.udl",
     [
-        // "tests/bindings/test_sprites.py",
+        "tests/bindings/test_threadsafe.py",
         "tests/bindings/test_threadsafe.kts",
         // "tests/bindings/test_threadsafe.swift",
     ]
@@ -34,11 +34,17 @@ fn create_entry_with<S: Into<String>>(item: S) -> Result<TodoEntry> {
 type Result<T, E = TodoError> = std::result::Result<T, E>;
 
 // I am a simple Todolist
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TodoList {
     items: Vec<String>,
 }
 
+impl std::fmt::Display for TodoList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TodoList with {} items", self.items.len())
+    }
+}
+
 impl TodoList {
     fn new() -> Self {
         Self { items: Vec::new() }
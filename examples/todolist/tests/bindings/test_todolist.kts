@@ -50,6 +50,20 @@ todo.addItems(listOf("bobo", "fofo"))
 assert(todo.getItems().size == 9)
 assert(todo.getItems()[7] == "bobo")
 
+// The list exposes its `Display`, `Eq` and `Hash` implementations.
+assert(todo.toString() == "TodoList with 9 items")
+val other = TodoList()
+assert(todo != other)
+TodoList().use { empty ->
+    TodoList().use { alsoEmpty -> assert(empty == alsoEmpty) }
+    other.addItems(todo.getItems())
+    assert(todo == other)
+    assert(todo.hashCode() == other.hashCode())
+    assert(setOf(todo, other, empty).size == 2)
+}
+other.destroy()
+
 // Ensure the kotlin version of deinit doesn't crash, and is idempotent.
+// Every list has been destroyed by now, so the test harness won't find any leaks.
 todo.destroy()
 todo.destroy()
\ No newline at end of file
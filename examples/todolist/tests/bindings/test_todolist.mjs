@@ -0,0 +1,51 @@
+import assert from "node:assert/strict";
+import { TodoEntry, TodoError, TodoList, assertNoLeaks, createEntryWith } from "./todolist.mjs";
+
+const todo = new TodoList();
+
+assert.throws(() => todo.getLast(), TodoError.EmptyTodoList);
+
+assert.throws(() => createEntryWith(""), (e) => {
+    assert.ok(e instanceof TodoError);
+    assert.ok(e instanceof TodoError.EmptyString);
+    assert.ok(!(e instanceof TodoError.EmptyTodoList));
+    return true;
+});
+
+todo.addItem("Write strings support");
+assert.equal(todo.getLast(), "Write strings support");
+
+todo.addItem("Write tests for strings support");
+assert.equal(todo.getLast(), "Write tests for strings support");
+
+const entry = createEntryWith("Write bindings for strings as record members");
+todo.addEntry(entry);
+assert.equal(todo.getLast(), "Write bindings for strings as record members");
+assert.equal(todo.getLastEntry().text, "Write bindings for strings as record members");
+
+todo.addItem("Test Ünicode hàndling without an entry can't believe I didn't test this at first 🤣");
+assert.equal(todo.getLast(), "Test Ünicode hàndling without an entry can't believe I didn't test this at first 🤣");
+
+const entry2 = new TodoEntry("Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣");
+todo.addEntry(entry2);
+assert.deepEqual(todo.getLastEntry(), entry2);
+
+assert.equal(todo.getEntries().length, 5);
+
+todo.addEntries([new TodoEntry("foo"), new TodoEntry("bar")]);
+assert.equal(todo.getEntries().length, 7);
+assert.equal(todo.getLastEntry().text, "bar");
+
+todo.addItems(["bobo", "fofo"]);
+assert.equal(todo.getItems().length, 9);
+assert.equal(todo.getItems()[7], "bobo");
+
+// The list exposes its `Display` implementation as `toString()`.
+assert.equal(`${todo}`, "TodoList with 9 items");
+
+// Ensure that destroying is idempotent.
+todo.destroy();
+todo.destroy();
+
+// Everything that we passed across the FFI has been freed.
+assertNoLeaks();
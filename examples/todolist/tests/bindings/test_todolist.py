@@ -23,3 +23,20 @@ assert(todo.get_last() == "Test Ünicode hàndling without an entry can't believ
 entry2 = TodoEntry("Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣")
 todo.add_entry(entry2)
 assert(todo.get_last_entry().text == "Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣")
+
+# The list exposes its `Debug`, `Display`, `Eq` and `Hash` implementations.
+assert str(todo) == "TodoList with 5 items"
+assert repr(TodoList()) == 'TodoList { items: [] }'
+other = TodoList()
+assert todo != other
+assert TodoList() == TodoList()
+other.add_items(todo.get_items())
+assert todo == other
+assert hash(todo) == hash(other)
+assert len({todo, other, TodoList()}) == 2
+assert todo != "not a list"
+
+# Everything that we passed across the FFI has been freed.
+todo.close()
+other.close()
+assert_no_leaks()
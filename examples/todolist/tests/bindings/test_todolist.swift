@@ -1,47 +1,62 @@
 import todolist
 
 
-let todo = TodoList()
+// The lists are scoped to this block, so that they have been freed by the time
+// the test harness checks for leaks at the end of the script.
 do {
-    let _ = try todo.getLast()
-    fatalError("Should have thrown an EmptyTodoList error!")
-} catch TodoError.EmptyTodoList{
-    //It's okay! There are not todos!
-}
-try! todo.addItem(todo: "Write swift bindings")
-assert( try! todo.getLast() == "Write swift bindings")
+    let todo = TodoList()
+    do {
+        let _ = try todo.getLast()
+        fatalError("Should have thrown an EmptyTodoList error!")
+    } catch TodoError.EmptyTodoList{
+        //It's okay! There are not todos!
+    }
+    try! todo.addItem(todo: "Write swift bindings")
+    assert( try! todo.getLast() == "Write swift bindings")
 
-try! todo.addItem(todo: "Write tests for bindings")
-assert(try! todo.getLast() == "Write tests for bindings")
+    try! todo.addItem(todo: "Write tests for bindings")
+    assert(try! todo.getLast() == "Write tests for bindings")
 
-let entry = TodoEntry(text: "Write bindings for strings as record members")
-try! todo.addEntry(entry: entry)
-assert(try! todo.getLast() == "Write bindings for strings as record members")
+    let entry = TodoEntry(text: "Write bindings for strings as record members")
+    try! todo.addEntry(entry: entry)
+    assert(try! todo.getLast() == "Write bindings for strings as record members")
 
-try! todo.addItem(todo: "Test Ünicode hàndling without an entry can't believe I didn't test this at first 🤣")
-assert(try! todo.getLast() == "Test Ünicode hàndling without an entry can't believe I didn't test this at first 🤣")
+    try! todo.addItem(todo: "Test Ünicode hàndling without an entry can't believe I didn't test this at first 🤣")
+    assert(try! todo.getLast() == "Test Ünicode hàndling without an entry can't believe I didn't test this at first 🤣")
 
-do {
-    let _ = try createEntryWith(todo: "")
-    fatalError("Should have thrown an EmptyString error!")
-} catch TodoError.EmptyString {
-    // It's okay! It was an empty string
-}
+    do {
+        let _ = try createEntryWith(todo: "")
+        fatalError("Should have thrown an EmptyString error!")
+    } catch TodoError.EmptyString {
+        // It's okay! It was an empty string
+    }
 
-let entry2 = TodoEntry(text: "Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣")
-try! todo.addEntry(entry: entry2)
-assert(try! todo.getLastEntry() == entry2)
+    let entry2 = TodoEntry(text: "Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣")
+    try! todo.addEntry(entry: entry2)
+    assert(try! todo.getLastEntry() == entry2)
 
-assert(todo.getEntries().count == 5)
+    assert(todo.getEntries().count == 5)
 
-todo.addEntries(entries: [TodoEntry(text: "foo"), TodoEntry(text: "bar")])
-assert(todo.getEntries().count == 7)
-assert(todo.getItems().count == 7)
-assert(try! todo.getLast() == "bar")
+    todo.addEntries(entries: [TodoEntry(text: "foo"), TodoEntry(text: "bar")])
+    assert(todo.getEntries().count == 7)
+    assert(todo.getItems().count == 7)
+    assert(try! todo.getLast() == "bar")
 
-todo.addItems(items: ["bobo", "fofo"])
-assert(todo.getItems().count == 9)
-assert(todo.getItems()[7] == "bobo")
+    todo.addItems(items: ["bobo", "fofo"])
+    assert(todo.getItems().count == 9)
+    assert(todo.getItems()[7] == "bobo")
+
+    // The list exposes its `Debug`, `Display`, `Eq` and `Hash` implementations.
+    assert(String(describing: todo) == "TodoList with 9 items")
+    assert(String(reflecting: TodoList()) == "TodoList { items: [] }")
+    let other = TodoList()
+    assert(todo != other)
+    assert(TodoList() == TodoList())
+    other.addItems(items: todo.getItems())
+    assert(todo == other)
+    assert(todo.hashValue == other.hashValue)
+    assert(Set([todo, other, TodoList()]).count == 2)
+}
 
 // Ensure deinit doesn't crash.
 for _ in 0..<10 {
@@ -0,0 +1,21 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Bundle the `profiles` component into this crate's cdylib, since the
+// bindings for this component use its bindings for the `ProfileInfo` type.
+pub use profiles;
+use profiles::ProfileInfo;
+
+fn birthday(profile: ProfileInfo) -> ProfileInfo {
+    ProfileInfo {
+        age: profile.age + 1,
+        ..profile
+    }
+}
+
+fn oldest(profiles: Vec<ProfileInfo>) -> Option<ProfileInfo> {
+    profiles.into_iter().max_by_key(|profile| profile.age)
+}
+
+include!(concat!(env!("OUT_DIR"), "/app.uniffi.rs"));
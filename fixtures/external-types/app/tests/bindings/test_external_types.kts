@@ -0,0 +1,15 @@
+import uniffi.app.*
+
+// The app component uses the class from the profiles component for the external type.
+val alice: uniffi.profiles.ProfileInfo = ProfileInfo("Alice", 30u)
+
+// Pass a record created by the profiles component into the app component, and back.
+val profile = birthday(uniffi.profiles.defaultProfile())
+assert(profile.name == "Anonymous")
+assert(profile.age == 1u)
+assert(uniffi.profiles.describeProfile(profile) == "Anonymous, aged 1")
+
+// Nested in other types, the record is read and written alongside the other values.
+val bob = ProfileInfo("Bob", 42u)
+assert(oldest(listOf(alice, bob, profile))?.name == "Bob")
+assert(oldest(listOf()) == null)
@@ -0,0 +1,14 @@
+import app
+import profiles
+
+# Pass a record created by the profiles component into the app component, and back.
+profile = app.birthday(profiles.default_profile())
+assert profile.name == "Anonymous"
+assert profile.age == 1
+assert profiles.describe_profile(profile) == "Anonymous, aged 1"
+
+# Nested in other types, the record is read and written alongside the other values.
+alice = profiles.ProfileInfo("Alice", 30)
+bob = profiles.ProfileInfo("Bob", 42)
+assert app.oldest([alice, bob, profile]).name == "Bob"
+assert app.oldest([]) is None
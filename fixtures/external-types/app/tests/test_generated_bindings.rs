@@ -0,0 +1,7 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["../profiles/src/profiles.udl", "src/app.udl"],
+    [
+        "tests/bindings/test_external_types.py",
+        "tests/bindings/test_external_types.kts",
+    ]
+);
@@ -0,0 +1,24 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The `app` component uses this record as an external type,
+// so it must be available at the root of the crate.
+#[derive(Debug, Clone)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub age: u32,
+}
+
+fn default_profile() -> ProfileInfo {
+    ProfileInfo {
+        name: "Anonymous".into(),
+        age: 0,
+    }
+}
+
+fn describe_profile(profile: ProfileInfo) -> String {
+    format!("{}, aged {}", profile.name, profile.age)
+}
+
+include!(concat!(env!("OUT_DIR"), "/profiles.uniffi.rs"));
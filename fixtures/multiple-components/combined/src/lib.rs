@@ -0,0 +1,8 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Bundle both components into this crate's cdylib. We have to actually use
+// each crate, otherwise the linker won't include their scaffolding at all.
+pub use uniffi_first;
+pub use uniffi_second;
@@ -0,0 +1,19 @@
+import uniffi.first.*;
+import uniffi.second.*;
+
+assert(greet("World") == "Hello, World!")
+
+val counter = Counter()
+counter.increment()
+counter.increment()
+assert(counter.getCount() == 2u)
+
+assert(split("a,b,c", ",") == listOf("a", "b", "c"))
+assert(parse("42") == 42u)
+
+try {
+    parse("forty-two")
+    throw RuntimeException("Should have thrown a NotANumber exception!")
+} catch (e: ParseErrorException) {
+    // It's okay!
+}
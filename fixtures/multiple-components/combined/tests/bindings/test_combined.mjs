@@ -0,0 +1,17 @@
+import assert from "node:assert/strict";
+import { greet, Counter } from "./first.mjs";
+import { split, parse, ParseError } from "./second.mjs";
+
+assert.equal(greet("World"), "Hello, World!");
+
+const counter = new Counter();
+counter.increment();
+counter.increment();
+assert.equal(counter.getCount(), 2);
+
+// Strings returned by one component are allocated in the same
+// linear memory that the other component passes its arguments in.
+assert.deepEqual(split(greet("a,b"), ","), ["Hello", " a", "b!"]);
+assert.equal(parse("42"), 42);
+
+assert.throws(() => parse("forty-two"), ParseError.NotANumber);
@@ -0,0 +1,23 @@
+import first
+import second
+
+# Both components are loaded from the same library,
+# and share the builtin functions for managing its buffers.
+assert first._UniFFILib._name == second._UniFFILib._name
+
+assert first.greet("World") == "Hello, World!"
+
+counter = first.Counter()
+counter.increment()
+counter.increment()
+assert counter.get_count() == 2
+
+assert second.split("a,b,c", ",") == ["a", "b", "c"]
+assert second.parse("42") == 42
+
+try:
+    second.parse("forty-two")
+    assert(not("Should have thrown a NotANumber exception!"))
+except second.ParseError.NotANumber:
+    # It's okay!
+    pass
@@ -0,0 +1,19 @@
+import first
+import second
+
+assert(greet(name: "World") == "Hello, World!")
+
+let counter = Counter()
+counter.increment()
+counter.increment()
+assert(counter.getCount() == 2)
+
+assert(split(text: "a,b,c", separator: ",") == ["a", "b", "c"])
+assert(try! parse(text: "42") == 42)
+
+do {
+    let _ = try parse(text: "forty-two")
+    fatalError("Should have thrown a NotANumber exception!")
+} catch ParseError.NotANumber {
+    // It's okay!
+}
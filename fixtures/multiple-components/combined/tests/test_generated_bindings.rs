@@ -0,0 +1,9 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["../first/src/first.udl", "../second/src/second.udl"],
+    [
+        "tests/bindings/test_combined.py",
+        "tests/bindings/test_combined.kts",
+        "tests/bindings/test_combined.swift",
+        "tests/bindings/test_combined.mjs",
+    ]
+);
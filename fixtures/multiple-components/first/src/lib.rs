@@ -0,0 +1,28 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+fn greet(name: String) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[derive(Debug, Default)]
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Counter::default()
+    }
+
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    fn get_count(&self) -> u32 {
+        self.count
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/first.uniffi.rs"));
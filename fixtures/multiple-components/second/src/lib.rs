@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error)]
+enum ParseError {
+    #[error("Not a number: {0}")]
+    NotANumber(String),
+}
+
+fn split(text: String, separator: String) -> Vec<String> {
+    text.split(separator.as_str()).map(String::from).collect()
+}
+
+fn parse(text: String) -> Result<u32, ParseError> {
+    text.parse().map_err(|_| ParseError::NotANumber(text))
+}
+
+include!(concat!(env!("OUT_DIR"), "/second.uniffi.rs"));
@@ -0,0 +1,183 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The builtin FFI functions that every component's bindings rely on.
+//!
+//! These are the same for every component, so rather than having each component's scaffolding
+//! define its own copy, we define them once here. When several components are bundled into a
+//! single shared library, that library then contains just one copy of each function, which the
+//! bindings for all of the components use.
+//!
+//! The names of these functions must match the ones in `uniffi_bindgen`'s `ComponentInterface`.
+//! They end with the major and minor version of this crate, so that components built with
+//! incompatible versions of uniffi can be linked into the same library without their builtin
+//! functions clashing.
+
+use ffi_support::{ErrorCode, ExternError};
+
+use super::{ForeignBytes, RustBuffer};
+
+// The exported name of a builtin function, like `uniffi_rustbuffer_alloc_0_7`.
+macro_rules! versioned_name {
+    ($name:literal) => {
+        concat!(
+            $name,
+            "_",
+            env!("CARGO_PKG_VERSION_MAJOR"),
+            "_",
+            env!("CARGO_PKG_VERSION_MINOR")
+        )
+    };
+}
+
+/// This helper allocates a new byte buffer owned by the Rust code, and returns it
+/// to the foreign-language code as a `RustBuffer` struct. Callers must eventually
+/// free the resulting buffer, either by explicitly calling the destructor defined below,
+/// or by passing ownership of the buffer back into Rust code.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_rustbuffer_alloc")]
+pub extern "C" fn uniffi_rustbuffer_alloc(size: i32, err: &mut ExternError) -> RustBuffer {
+    ffi_support::call_with_output(err, || RustBuffer::new_with_size(size.max(0) as usize))
+}
+
+/// This helper copies bytes owned by the foreign-language code into a new byte buffer owned
+/// by the Rust code, and returns it as a `RustBuffer` struct. Callers must eventually
+/// free the resulting buffer, either by explicitly calling the destructor defined below,
+/// or by passing ownership of the buffer back into Rust code.
+///
+/// # Safety
+/// This function will dereference a provided pointer in order to copy bytes from it, so
+/// make sure the `ForeignBytes` struct contains a valid pointer and length.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_rustbuffer_from_bytes")]
+pub unsafe extern "C" fn uniffi_rustbuffer_from_bytes(
+    bytes: ForeignBytes,
+    err: &mut ExternError,
+) -> RustBuffer {
+    ffi_support::call_with_output(err, || {
+        let bytes = bytes.as_slice();
+        RustBuffer::from_vec(bytes.to_vec())
+    })
+}
+
+/// Free a byte buffer that had previously been passed to the foreign language code.
+///
+/// # Safety
+/// The argument *must* be a uniquely-owned `RustBuffer` previously obtained from a call
+/// into the Rust code that returned a buffer, or you'll risk freeing unowned memory or
+/// corrupting the allocator state.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_rustbuffer_free")]
+pub unsafe extern "C" fn uniffi_rustbuffer_free(buf: RustBuffer, err: &mut ExternError) {
+    ffi_support::call_with_output(err, || RustBuffer::destroy(buf))
+}
+
+/// Reserve additional capacity in a byte buffer that had previously been passed to the
+/// foreign language code.
+///
+/// The first argument *must* be a uniquely-owned `RustBuffer` previously
+/// obtained from a call into the Rust code that returned a buffer. Its underlying data pointer
+/// will be reallocated if necessary and returned in a new `RustBuffer` struct.
+///
+/// The second argument must be the minimum number of *additional* bytes to reserve
+/// capacity for in the buffer; it is likely to reserve additional capacity in practice
+/// due to amortized growth strategy of Rust vectors.
+///
+/// # Safety
+/// The first argument *must* be a uniquely-owned `RustBuffer` previously obtained from a call
+/// into the Rust code that returned a buffer, or you'll risk freeing unowned memory or
+/// corrupting the allocator state.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_rustbuffer_reserve")]
+pub unsafe extern "C" fn uniffi_rustbuffer_reserve(
+    buf: RustBuffer,
+    additional: i32,
+    err: &mut ExternError,
+) -> RustBuffer {
+    ffi_support::call_with_output(err, || {
+        use std::convert::TryInto;
+        let additional: usize = additional
+            .try_into()
+            .expect("additional buffer length negative or overflowed");
+        let mut v = buf.destroy_into_vec();
+        v.reserve(additional);
+        RustBuffer::from_vec(v)
+    })
+}
+
+/// Free a String that had previously been passed to the foreign language code.
+///
+/// # Safety
+///
+/// In order to free the string, Rust takes ownership of a raw pointer which is an
+/// unsafe operation. The argument *must* be a uniquely-owned pointer previously
+/// obtained from a call into the rust code that returned a string.
+/// (In practice that means you got it from the `message` field of an `ExternError`,
+/// because that's currently the only place we use `char*` types in our API).
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_string_free")]
+pub unsafe extern "C" fn uniffi_string_free(
+    cstr: *mut std::os::raw::c_char,
+    err: &mut ExternError,
+) {
+    ffi_support::call_with_output(err, || ffi_support::destroy_c_string(cstr))
+}
+
+/// Install the panic hook that records structured reports of Rust panics.
+/// The bindings call this when they load the library.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_install_panic_hook")]
+pub extern "C" fn uniffi_install_panic_hook(err: &mut ExternError) {
+    ffi_support::call_with_output(err, super::panics::install_hook)
+}
+
+/// Take the structured report for the most recent Rust panic on the current thread, which the
+/// bindings call when a call fails with a panic. This returns an empty buffer if there isn't one.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_take_panic_report")]
+pub extern "C" fn uniffi_take_panic_report(err: &mut ExternError) -> RustBuffer {
+    ffi_support::call_with_output(err, super::panics::take_serialized_panic_report)
+}
+
+/// Report the objects and buffers that are still live across the FFI, for the `assertNoLeaks()`
+/// helper in the generated bindings. This returns an empty string if nothing has leaked, and fails
+/// with an error if uniffi wasn't built with the `leak-tracking` feature.
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_leak_report")]
+pub extern "C" fn uniffi_leak_report(err: &mut ExternError) -> RustBuffer {
+    ffi_support::call_with_result(err, || -> Result<_, ExternError> {
+        let report = super::leaks::leak_report().ok_or_else(|| {
+            ExternError::new_error(
+                ErrorCode::new(1),
+                "Leak tracking isn't enabled; build uniffi with the `leak-tracking` feature",
+            )
+        })?;
+        Ok(RustBuffer::from_vec(report.into_bytes()))
+    })
+}
+
+// When compiled to WebAssembly, the JS bindings need some help to allocate the structs
+// that they pass into Rust, since those have to live in the module's linear memory.
+
+/// This helper allocates zeroed scratch memory in the module's linear memory, for the
+/// JS bindings to use when passing structs or error out-params over the FFI.
+/// Callers must free the memory with the matching `scratch_free` function below.
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_wasm_scratch_alloc")]
+pub extern "C" fn uniffi_wasm_scratch_alloc(size: i32) -> *mut u8 {
+    super::wasm::scratch_alloc(size)
+}
+
+/// Free scratch memory that was previously allocated for the JS bindings.
+///
+/// # Safety
+/// The arguments *must* be a pointer previously obtained from the scratch allocation
+/// function above, and the size that was requested when allocating it.
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[export_name = versioned_name!("uniffi_wasm_scratch_free")]
+pub unsafe extern "C" fn uniffi_wasm_scratch_free(ptr: *mut u8, size: i32) {
+    super::wasm::scratch_free(ptr, size)
+}
@@ -70,6 +70,18 @@ impl ForeignBytes {
         }
     }
 
+    /// View the foreign bytes as a `&str`.
+    ///
+    /// This lets us receive borrowed string arguments without copying them.
+    /// It's an error if the bytes are not valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as `as_slice()`.
+    pub fn as_str(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(self.as_slice())
+    }
+
     /// Get the length of this slice of bytes.
     ///
     /// # Panics
@@ -92,12 +104,22 @@ mod test {
     use super::*;
     #[test]
     fn test_foreignbytes_access() {
-        let v = vec![1u8, 2, 3];
+        let v = [1u8, 2, 3];
         let fbuf = unsafe { ForeignBytes::from_raw_parts(v.as_ptr(), 3) };
         assert_eq!(fbuf.len(), 3);
         assert_eq!(fbuf.as_slice(), &[1u8, 2, 3]);
     }
 
+    #[test]
+    fn test_foreignbytes_as_str() {
+        let v = "été".as_bytes();
+        let fbuf = unsafe { ForeignBytes::from_raw_parts(v.as_ptr(), v.len() as i32) };
+        assert_eq!(fbuf.as_str().unwrap(), "été");
+        let v = [0xFFu8, 0xFE];
+        let fbuf = unsafe { ForeignBytes::from_raw_parts(v.as_ptr(), 2) };
+        assert!(fbuf.as_str().is_err());
+    }
+
     #[test]
     fn test_foreignbytes_empty() {
         let v = Vec::<u8>::new();
@@ -122,7 +144,7 @@ mod test {
     #[test]
     #[should_panic]
     fn test_foreignbytes_provided_len_must_be_non_negative() {
-        let v = vec![0u8, 1, 2];
+        let v = [0u8, 1, 2];
         let fbuf = unsafe { ForeignBytes::from_raw_parts(v.as_ptr(), -1) };
         fbuf.as_slice();
     }
@@ -14,7 +14,11 @@ use std::sync::{Arc, RwLock};
 /// objects with `mut` methods can be used, but the objects can only be accessed from one thread
 /// at a time.
 ///
-/// The `Threadsafe` annotation is used to choose which handle map `uniffi` uses.
+/// The `RwLockHandleMap` sits in between: it wraps each object in an `RwLock`, so that methods
+/// taking `&self` can be called from several threads at once, while methods taking `&mut self`
+/// get exclusive access.
+///
+/// The `Threadsafe` and `RwLock` annotations are used to choose which handle map `uniffi` uses.
 ///
 /// This module also provides the `UniffiMethodCall` trait, which allows generated scaffolding
 /// to switch almost seemlessly.
@@ -60,8 +64,16 @@ impl<T: Sync + Send> ArcHandleMap<T> {
     ///
     /// This takes the map's `write` lock which the object is inserted.
     pub fn insert(&self, v: T) -> Handle {
+        self.insert_arc(Arc::new(v))
+    }
+
+    /// Insert an item that's already in an `Arc` into the map, returning the newly
+    /// allocated handle to the item.
+    ///
+    /// This takes the map's `write` lock while the object is inserted.
+    pub fn insert_arc(&self, v: Arc<T>) -> Handle {
         let mut map = self.map.write().unwrap();
-        map.insert(Arc::new(v))
+        map.insert(v)
     }
 
     /// Remove an item from the map.
@@ -169,16 +181,20 @@ impl<T: Sync + Send> ArcHandleMap<T> {
         call_with_result(out_error, || -> Result<_, ExternError> {
             // We can't reuse `get` here because it would require E:
             // From<HandleError>, which is inconvenient...
-            let h = Handle::from_u64(h)?;
-            let obj = {
-                let map = self.map.read().unwrap();
-                let obj = map.get(h)?;
-                Arc::clone(&obj)
-            };
+            let obj = self.clone_arc_u64(h)?;
             Ok(callback(&*obj)?)
         })
     }
 
+    /// Get a new reference to the item with the given handle, taking the map's `read` lock
+    /// only for as long as needed to clone the inner `Arc`.
+    fn clone_arc_u64(&self, h: u64) -> Result<Arc<T>, HandleError> {
+        let h = Handle::from_u64(h)?;
+        let map = self.map.read().unwrap();
+        let obj = map.get(h)?;
+        Ok(Arc::clone(&obj))
+    }
+
     /// Helper that performs both a [`call_with_output`] and [`get`](ArcHandleMap::get).
     pub fn call_with_output<R, F>(
         &self,
@@ -231,6 +247,32 @@ impl<T: Sync + Send> ArcHandleMap<T> {
             Ok(constructor())
         })
     }
+
+    /// The same as [`insert_with_result`](ArcHandleMap::insert_with_result), but for
+    /// constructors that return an `Arc<T>`, which is inserted into the map as-is.
+    pub fn insert_arc_with_result<E, F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Result<Arc<T>, E>,
+        ExternError: From<E>,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            let to_insert = constructor()?;
+            Ok(self.insert_arc(to_insert))
+        })
+    }
+
+    /// Equivalent to
+    /// [`insert_arc_with_result`](ArcHandleMap::insert_arc_with_result) for the
+    /// case where the constructor cannot produce an error.
+    pub fn insert_arc_with_output<F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Arc<T>,
+    {
+        self.insert_arc_with_result(out_error, || -> Result<_, HandleError> {
+            Ok(constructor())
+        })
+    }
 }
 
 impl<T: Sync + Send> Default for ArcHandleMap<T> {
@@ -240,17 +282,122 @@ impl<T: Sync + Send> Default for ArcHandleMap<T> {
     }
 }
 
+/// `RwLockHandleMap` is an `ArcHandleMap` of objects wrapped in an `RwLock`.
+///
+/// Like the `MutexHandleMap`, this does the locking for the objects, so they don't need
+/// interior mutability. But calls that only need a `&T` take the read lock, and so can run
+/// on several threads at the same time, while calls that need a `&mut T` take the write lock.
+/// Since several threads can share a `&T` at once, this needs the objects to be `Sync`.
+pub struct RwLockHandleMap<T>
+where
+    T: Sync + Send,
+{
+    /// The underlying map. Public so that more advanced use-cases
+    /// may use it as they please.
+    pub map: ArcHandleMap<RwLock<T>>,
+}
+
+impl<T: Sync + Send> RwLockHandleMap<T> {
+    /// Construct a new `RwLockHandleMap`.
+    pub fn new() -> Self {
+        RwLockHandleMap {
+            map: ArcHandleMap::new(),
+        }
+    }
+
+    /// Insert an item into the map, returning the newly allocated handle to the
+    /// item.
+    pub fn insert(&self, v: T) -> Handle {
+        self.map.insert(RwLock::new(v))
+    }
+
+    /// Remove an item from the map, given its handle as a `u64`.
+    pub fn delete_u64(&self, h: u64) -> Result<(), HandleError> {
+        self.map.delete_u64(h)
+    }
+
+    /// Use `constructor` to create and insert a `T`, while inside a
+    /// [`call_with_result`] call (to handle panics and map errors onto an
+    /// `ExternError`).
+    pub fn insert_with_result<E, F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Result<T, E>,
+        ExternError: From<E>,
+    {
+        self.map
+            .insert_with_result(out_error, || constructor().map(RwLock::new))
+    }
+
+    /// Equivalent to
+    /// [`insert_with_result`](RwLockHandleMap::insert_with_result) for the
+    /// case where the constructor cannot produce an error.
+    pub fn insert_with_output<F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> T,
+    {
+        self.map
+            .insert_with_output(out_error, || RwLock::new(constructor()))
+    }
+
+    /// Call `callback` with a shared reference to the item with the given handle,
+    /// while holding the item's read lock.
+    ///
+    /// This is for methods that take `&self`; methods that take `&mut self` use the
+    /// `UniffiMethodCall` trait, which takes the write lock.
+    pub fn read_method_call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        self.map
+            .call_with_result(out_error, h, |obj| callback(&*obj.read().unwrap()))
+    }
+
+    /// Equivalent to
+    /// [`read_method_call_with_result`](RwLockHandleMap::read_method_call_with_result) for the
+    /// case where the method cannot produce an error.
+    pub fn read_method_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T) -> R,
+        R: IntoFfi,
+    {
+        self.map
+            .call_with_output(out_error, h, |obj| callback(&*obj.read().unwrap()))
+    }
+}
+
+impl<T: Sync + Send> Default for RwLockHandleMap<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Make a type alias to contrast the two handle map implementations.
 pub type MutexHandleMap<T> = ffi_support::ConcurrentHandleMap<T>;
 
 /// A trait to help the code generation a bit tidier.
 ///
-/// We'll implement it only for the `MutexHandleMap` which asks for `FnOnce(&mut T)`
-/// callbacks, but we'll give `ArcHandleMap` very similar looking methods, that
-/// accept `FnOnce(&T)` callbacks.
+/// We'll implement it only for the `MutexHandleMap` and `RwLockHandleMap` which ask for
+/// `FnOnce(&mut T)` callbacks, but we'll give `ArcHandleMap` very similar looking methods,
+/// that accept `FnOnce(&T)` callbacks.
 ///
 /// When generating the code in the `to_rs_method_call` macro in `macros.rs`, the code will be lexically
 /// identical.
+///
+/// It also provides `pair_call_with_output`, for calling trait methods like `PartialEq::eq` that
+/// take two items. That gives the callback shared references, since the two items might be the same.
 pub trait UniffiMethodCall<T> {
     fn method_call_with_result<R, E, F>(
         &self,
@@ -272,6 +419,17 @@ pub trait UniffiMethodCall<T> {
     where
         F: std::panic::UnwindSafe + FnOnce(&mut T) -> R,
         R: IntoFfi;
+
+    fn pair_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h1: u64,
+        h2: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T, &T) -> R,
+        R: IntoFfi;
 }
 
 impl<T> UniffiMethodCall<T> for MutexHandleMap<T> {
@@ -301,6 +459,104 @@ impl<T> UniffiMethodCall<T> for MutexHandleMap<T> {
     {
         self.call_with_output_mut(out_error, h, callback)
     }
+
+    /// This takes the map's read lock once for both items, and locks the items in the
+    /// order of their handles, so that it can't deadlock with another call for the same
+    /// two items the other way round.
+    fn pair_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h1: u64,
+        h2: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T, &T) -> R,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, HandleError> {
+            let map = self.map.read().unwrap();
+            let (mtx1, mtx2) = (
+                map.get(Handle::from_u64(h1)?)?,
+                map.get(Handle::from_u64(h2)?)?,
+            );
+            if h1 == h2 {
+                let obj = mtx1.lock().unwrap();
+                return Ok(callback(&*obj, &*obj));
+            }
+            let (obj1, obj2) = if h1 < h2 {
+                let obj1 = mtx1.lock().unwrap();
+                (obj1, mtx2.lock().unwrap())
+            } else {
+                let obj2 = mtx2.lock().unwrap();
+                (mtx1.lock().unwrap(), obj2)
+            };
+            Ok(callback(&*obj1, &*obj2))
+        })
+    }
+}
+
+impl<T: Sync + Send> UniffiMethodCall<T> for RwLockHandleMap<T> {
+    fn method_call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&mut T) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        self.map
+            .call_with_result(out_error, h, |obj| callback(&mut *obj.write().unwrap()))
+    }
+
+    fn method_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&mut T) -> R,
+        R: IntoFfi,
+    {
+        self.map
+            .call_with_output(out_error, h, |obj| callback(&mut *obj.write().unwrap()))
+    }
+
+    /// This takes the items' read locks in the order of their handles, so that it
+    /// can't deadlock with another call for the same two items the other way round.
+    fn pair_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h1: u64,
+        h2: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T, &T) -> R,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, HandleError> {
+            let (lock1, lock2) = (self.map.clone_arc_u64(h1)?, self.map.clone_arc_u64(h2)?);
+            if h1 == h2 {
+                let obj = lock1.read().unwrap();
+                return Ok(callback(&*obj, &*obj));
+            }
+            let (obj1, obj2) = if h1 < h2 {
+                let obj1 = lock1.read().unwrap();
+                (obj1, lock2.read().unwrap())
+            } else {
+                let obj2 = lock2.read().unwrap();
+                (lock1.read().unwrap(), obj2)
+            };
+            Ok(callback(&*obj1, &*obj2))
+        })
+    }
 }
 
 /// The faux implementation of `UniffiMethodCall` which differs from the real one
@@ -332,6 +588,147 @@ impl<T: Sync + Send> ArcHandleMap<T> {
     {
         self.call_with_output(out_error, h, callback)
     }
+
+    /// Like `method_call_with_result`, but for methods that take `self: Arc<Self>`.
+    ///
+    /// The callback is given its own reference to the item, which it can keep
+    /// after the call returns (for example, by handing it to another thread).
+    pub fn arc_method_call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(Arc<T>) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            let obj = self.clone_arc_u64(h)?;
+            Ok(callback(obj)?)
+        })
+    }
+
+    /// Like `method_call_with_output`, but for methods that take `self: Arc<Self>`.
+    pub fn arc_method_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(Arc<T>) -> R,
+        R: IntoFfi,
+    {
+        self.arc_method_call_with_result(out_error, h, |r| -> Result<_, HandleError> {
+            Ok(callback(r))
+        })
+    }
+
+    /// Call `callback` with references to the two items with the given handles,
+    /// for trait methods like `PartialEq::eq` that take two items.
+    pub fn pair_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h1: u64,
+        h2: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T, &T) -> R,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, HandleError> {
+            let (obj1, obj2) = (self.clone_arc_u64(h1)?, self.clone_arc_u64(h2)?);
+            Ok(callback(&*obj1, &*obj2))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ArcHandleMap, MutexHandleMap, RwLockHandleMap, UniffiMethodCall};
+    use ffi_support::{ErrorCode, ExternError};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_arcs_are_passed_through_unwrapped() {
+        let map = ArcHandleMap::new();
+        let obj = Arc::new(1u32);
+        let mut e = ExternError::success();
+        let h = map.insert_arc_with_output(&mut e, || Arc::clone(&obj));
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        let value = map.arc_method_call_with_output(&mut e, h, |this| {
+            assert!(Arc::ptr_eq(&this, &obj));
+            *this
+        });
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 1);
+        assert!(map.delete_u64(h).is_ok());
+        assert_eq!(Arc::strong_count(&obj), 1);
+    }
+
+    #[test]
+    fn test_rwlock_reads_can_run_at_the_same_time() {
+        let map = Arc::new(RwLockHandleMap::new());
+        let mut e = ExternError::success();
+        let h = map.insert_with_output(&mut e, || 1u32);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        // If reads were exclusive, the read on the other thread would wait for this one forever.
+        let value = map.read_method_call_with_output(&mut e, h, |outer| {
+            let map = Arc::clone(&map);
+            let inner = std::thread::spawn(move || {
+                let mut e = ExternError::success();
+                let value = map.read_method_call_with_output(&mut e, h, |inner| *inner);
+                assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+                value
+            });
+            *outer + inner.join().unwrap()
+        });
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 2);
+        map.method_call_with_output(&mut e, h, |obj| *obj = 3);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        let value = map.read_method_call_with_output(&mut e, h, |obj| *obj);
+        assert_eq!(value, 3);
+        assert!(map.delete_u64(h).is_ok());
+    }
+
+    #[test]
+    fn test_pair_calls_keep_the_order_of_their_arguments() {
+        let map = MutexHandleMap::new();
+        let mut e = ExternError::success();
+        let h1 = map.insert(1u32).into_u64();
+        let h2 = map.insert(2u32).into_u64();
+        let value = map.pair_call_with_output(&mut e, h1, h2, |a, b| a * 10 + b);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 12);
+        let value = map.pair_call_with_output(&mut e, h2, h1, |a, b| a * 10 + b);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 21);
+        // Passing the same handle twice mustn't try to lock the item twice.
+        let value = map.pair_call_with_output(&mut e, h1, h1, |a, b| a * 10 + b);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 11);
+        assert!(map.delete_u64(h2).is_ok());
+        map.pair_call_with_output(&mut e, h1, h2, |a, b| a * 10 + b);
+        assert_eq!(e.get_code(), ErrorCode::INVALID_HANDLE);
+        let _ = unsafe { e.get_and_consume_message() };
+
+        let map = RwLockHandleMap::new();
+        let mut e = ExternError::success();
+        let h1 = map.insert(1u32).into_u64();
+        let h2 = map.insert(2u32).into_u64();
+        let value = map.pair_call_with_output(&mut e, h2, h1, |a, b| a * 10 + b);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 21);
+        let value = map.pair_call_with_output(&mut e, h2, h2, |a, b| a * 10 + b);
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(value, 22);
+    }
 }
 
 /// Tests that check our behavior when panicking.
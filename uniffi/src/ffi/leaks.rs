@@ -0,0 +1,133 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Optional tracking of the objects and buffers that are live across the FFI.
+//!
+//! It's easy for foreign language code to forget to free an object or a `RustBuffer`, and
+//! nothing will complain, the memory just leaks. So that tests can check for that, enabling
+//! the `leak-tracking` feature makes the generated scaffolding count the live handles for
+//! each type of object, and makes `RustBuffer` count the buffers that it has allocated but
+//! not yet freed. The builtin `uniffi_leak_report` FFI function reports any that are still
+//! live, which the bindings use to implement their `assertNoLeaks()` helpers.
+//!
+//! Without the feature, the counting functions do nothing and `leak_report` returns `None`,
+//! so this costs nothing in normal builds.
+
+#[cfg(feature = "leak-tracking")]
+mod counts {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicIsize, Ordering};
+    use std::sync::Mutex;
+
+    lazy_static::lazy_static! {
+        // The number of live handles for each type of object, by name.
+        static ref LIVE_OBJECTS: Mutex<BTreeMap<&'static str, isize>> = Default::default();
+    }
+
+    // The number of `RustBuffer`s that have been allocated but not yet freed.
+    static LIVE_BUFFERS: AtomicIsize = AtomicIsize::new(0);
+
+    pub fn object_created(name: &'static str) {
+        *LIVE_OBJECTS.lock().unwrap().entry(name).or_default() += 1;
+    }
+
+    pub fn object_freed(name: &'static str) {
+        *LIVE_OBJECTS.lock().unwrap().entry(name).or_default() -= 1;
+    }
+
+    pub fn buffer_created() {
+        LIVE_BUFFERS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn buffer_freed() {
+        LIVE_BUFFERS.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    pub fn leak_report() -> String {
+        let mut leaks: Vec<_> = LIVE_OBJECTS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, count)| **count != 0)
+            .map(|(name, count)| format!("{}: {}", name, count))
+            .collect();
+        let buffers = LIVE_BUFFERS.load(Ordering::SeqCst);
+        if buffers != 0 {
+            leaks.push(format!("RustBuffer: {}", buffers));
+        }
+        leaks.join(", ")
+    }
+}
+
+/// Record that a new handle has been created for an object of the given type.
+///
+/// This takes and returns the handle, so that it can wrap the call that creates it.
+/// A zero handle means that creating the object failed, so it isn't counted.
+#[inline]
+pub fn object_created(name: &'static str, handle: u64) -> u64 {
+    #[cfg(feature = "leak-tracking")]
+    {
+        if handle != 0 {
+            counts::object_created(name);
+        }
+    }
+    #[cfg(not(feature = "leak-tracking"))]
+    let _ = name;
+    handle
+}
+
+/// Record that a handle for an object of the given type has been freed.
+#[inline]
+pub fn object_freed(name: &'static str) {
+    #[cfg(feature = "leak-tracking")]
+    counts::object_freed(name);
+    #[cfg(not(feature = "leak-tracking"))]
+    let _ = name;
+}
+
+/// Record that a new `RustBuffer` has been allocated.
+#[inline]
+pub(crate) fn buffer_created() {
+    #[cfg(feature = "leak-tracking")]
+    counts::buffer_created();
+}
+
+/// Record that a `RustBuffer` has been freed.
+#[inline]
+pub(crate) fn buffer_freed() {
+    #[cfg(feature = "leak-tracking")]
+    counts::buffer_freed();
+}
+
+/// Describe the objects and buffers that are still live, like `"example::Counter: 2, RustBuffer: 1"`,
+/// or return an empty string if there aren't any.
+///
+/// This returns `None` if the `leak-tracking` feature isn't enabled, since nothing is being counted.
+pub fn leak_report() -> Option<String> {
+    #[cfg(feature = "leak-tracking")]
+    return Some(counts::leak_report());
+    #[cfg(not(feature = "leak-tracking"))]
+    None
+}
+
+#[cfg(all(test, feature = "leak-tracking"))]
+mod test {
+    use super::*;
+
+    // The counts are global and other tests allocate buffers concurrently,
+    // so this only checks the objects that it creates itself.
+    #[test]
+    fn test_leak_report_counts_live_objects() {
+        assert!(!leak_report().unwrap().contains("test::Thing"));
+        assert_eq!(object_created("test::Thing", 0), 0);
+        assert!(!leak_report().unwrap().contains("test::Thing"));
+        assert_eq!(object_created("test::Thing", 1), 1);
+        object_created("test::Thing", 2);
+        assert!(leak_report().unwrap().contains("test::Thing: 2"));
+        object_freed("test::Thing");
+        assert!(leak_report().unwrap().contains("test::Thing: 1"));
+        object_freed("test::Thing");
+        assert!(!leak_report().unwrap().contains("test::Thing"));
+    }
+}
@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Reporting values from the foreign language code that we couldn't lift.
+//!
+//! The generated bindings should only ever pass us values that we can lift, so failing to lift
+//! one means that the bindings have a bug (or that they're out of date with the Rust code).
+//! Rather than panicking, the scaffolding reports this with an error whose code is
+//! [`INVALID_ARGUMENT_ERROR_CODE`], which the foreign language bindings turn into a specific
+//! exception, separate from the one they use for Rust panics. The error message says which
+//! argument it was, and for records, the path to the field that we couldn't lift.
+
+use anyhow::Result;
+use ffi_support::{ErrorCode, ExternError};
+
+/// The error code for an argument that we couldn't lift.
+///
+/// Like [`WRONG_THREAD_ERROR_CODE`](super::WRONG_THREAD_ERROR_CODE), this is negative so
+/// that it doesn't clash with the codes for the errors that a function is declared to throw.
+pub const INVALID_ARGUMENT_ERROR_CODE: i32 = -3;
+
+/// An error lifting one of the fields of a record, which records the field's name so that
+/// we can report the path to the value that we couldn't lift.
+#[derive(Debug)]
+pub struct InvalidField {
+    name: &'static str,
+    source: anyhow::Error,
+}
+
+impl std::fmt::Display for InvalidField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid value for field `{}`", self.name)
+    }
+}
+
+impl std::error::Error for InvalidField {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Wrap the result of lifting (or reading) the named field of a record, so that if it failed,
+/// the error says which field it was.
+pub fn lift_field<T>(name: &'static str, result: Result<T>) -> Result<T> {
+    result.map_err(|source| InvalidField { name, source }.into())
+}
+
+/// Turn the result of lifting the named argument into an `ExternError` with the
+/// [`INVALID_ARGUMENT_ERROR_CODE`], so that the scaffolding can return it to the foreign
+/// language code rather than panicking.
+pub fn lift_argument<T, E: Into<anyhow::Error>>(
+    name: &str,
+    result: std::result::Result<T, E>,
+) -> std::result::Result<T, ExternError> {
+    result.map_err(|e| {
+        ExternError::new_error(
+            ErrorCode::new(INVALID_ARGUMENT_ERROR_CODE),
+            format!(
+                "Invalid argument from foreign code: {}",
+                describe(name, &e.into())
+            ),
+        )
+    })
+}
+
+/// Read a value that a foreign callback returned to us, panicking if we can't.
+///
+/// Callback interface methods don't have a way to return errors to their Rust callers, so unlike
+/// arguments, we can't report this to the foreign language code as an `ExternError`. Instead
+/// the panic message says what we couldn't read, which helps with tracking down the bug.
+pub fn read_return_value<T: crate::ViaFfi, B: bytes::Buf>(buf: &mut B) -> T {
+    T::try_read(buf).unwrap_or_else(|e| {
+        panic!(
+            "Invalid return value from foreign callback: {}",
+            describe("return value", &e)
+        )
+    })
+}
+
+// Describe an error lifting a value, including the path to the field that failed, like
+// "`entry.text`: invalid utf-8".
+fn describe(name: &str, err: &anyhow::Error) -> String {
+    let mut path = name.to_string();
+    let mut err = err;
+    while let Some(field) = err.downcast_ref::<InvalidField>() {
+        path.push('.');
+        path.push_str(field.name);
+        err = &field.source;
+    }
+    format!("`{}`: {:#}", path, err)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lift_argument_reports_the_path_to_the_field() {
+        let result: Result<()> = lift_field(
+            "entry",
+            lift_field("text", Err(anyhow::anyhow!("invalid utf-8"))),
+        );
+        let err = lift_argument::<(), _>("list", result).unwrap_err();
+        assert_eq!(err.get_code(), ErrorCode::new(INVALID_ARGUMENT_ERROR_CODE));
+        assert_eq!(
+            unsafe { err.get_and_consume_message() }.unwrap(),
+            "Invalid argument from foreign code: `list.entry.text`: invalid utf-8"
+        );
+        assert!(lift_argument("list", Ok::<_, anyhow::Error>(1)).is_ok());
+        let bytes = vec![0xff];
+        let err = lift_argument::<&str, _>("name", std::str::from_utf8(&bytes)).unwrap_err();
+        assert!(unsafe { err.get_and_consume_message() }
+            .unwrap()
+            .starts_with("Invalid argument from foreign code: `name`: invalid utf-8"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid return value from foreign callback: `return value`")]
+    fn test_read_return_value_panics_if_it_cant_read_the_value() {
+        let mut buf: &[u8] = &[2];
+        read_return_value::<bool, _>(&mut buf);
+    }
+}
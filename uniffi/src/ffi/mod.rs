@@ -2,12 +2,24 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+pub mod builtins;
 pub mod foreignbytes;
 pub mod foreigncallbacks;
 pub mod handle_maps;
+pub mod leaks;
+pub mod lifting;
+pub mod panics;
+pub mod pointer_handles;
 pub mod rustbuffer;
+pub mod thread_confined;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
 
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
 pub use handle_maps::*;
+pub use lifting::*;
+pub use panics::*;
+pub use pointer_handles::*;
 pub use rustbuffer::*;
+pub use thread_confined::*;
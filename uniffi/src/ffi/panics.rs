@@ -0,0 +1,285 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Structured reports of Rust panics, for the foreign language code and for crash reporters.
+//!
+//! When Rust code panics during a call from the foreign language code, `ffi_support` catches
+//! the panic and turns it into an `ExternError` with the panic message, which on its own doesn't
+//! say much about where it happened. So the bindings install a panic hook (using [`install_hook`])
+//! when they load the library, which records a [`PanicReport`] with the location of the panic,
+//! the name of the thread and optionally a backtrace. When a call fails with a panic, the
+//! bindings fetch the report for their thread using the builtin `uniffi_take_panic_report`
+//! FFI function, and throw a `RustPanic` exception with its fields.
+//!
+//! Backtraces are slow to capture, so they're only captured if the `panic-backtraces`
+//! feature is enabled. Apps can also register a function with [`set_panic_reporter`] to
+//! pass every panic on to their crash reporter, whether or not the foreign language code
+//! sees it.
+
+use std::cell::RefCell;
+use std::sync::{Arc, Once, PoisonError, RwLock};
+
+use crate::ViaFfi;
+
+/// A structured report of a Rust panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    /// The panic message.
+    pub message: String,
+    /// The source file in which the panic happened.
+    pub file: String,
+    /// The line in the source file at which the panic happened.
+    pub line: u32,
+    /// The column in the source file at which the panic happened.
+    pub column: u32,
+    /// The name of the thread that panicked, if it has one.
+    pub thread_name: Option<String>,
+    /// A backtrace from the point of the panic, if the `panic-backtraces` feature is enabled.
+    pub backtrace: Option<String>,
+}
+
+impl PanicReport {
+    // Serialize the report for the foreign language code, which reads the fields in this order.
+    // The optional fields are written as empty strings when they're missing, so that the
+    // bindings don't need helpers for reading optional values.
+    fn write(&self, buf: &mut Vec<u8>) {
+        self.message.write(buf);
+        self.file.write(buf);
+        (self.line as i32).write(buf);
+        (self.column as i32).write(buf);
+        self.thread_name.clone().unwrap_or_default().write(buf);
+        self.backtrace.clone().unwrap_or_default().write(buf);
+    }
+}
+
+type PanicReporter = Arc<dyn Fn(&PanicReport) + Send + Sync>;
+
+lazy_static::lazy_static! {
+    static ref PANIC_REPORTER: RwLock<Option<PanicReporter>> = RwLock::new(None);
+}
+
+thread_local! {
+    // The report for the most recent panic on this thread, until the bindings take it.
+    static LAST_PANIC: RefCell<Option<PanicReport>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Install the panic hook that records structured reports of panics.
+///
+/// The bindings call this when they load the library, so you only need to call it yourself
+/// if you want reports of panics that happen before then. It's safe to call more than once.
+/// The hook calls the previously installed hook after recording the report, so panics are
+/// still printed as usual.
+pub fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                (*s).to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "Unknown panic!".to_string()
+            };
+            let (file, line, column) = info
+                .location()
+                .map(|l| (l.file().to_string(), l.line(), l.column()))
+                .unwrap_or_default();
+            let report = PanicReport {
+                message,
+                file,
+                line,
+                column,
+                thread_name: std::thread::current().name().map(str::to_string),
+                backtrace: capture_backtrace(),
+            };
+            // Panicking in the hook would abort the process, so we're careful not to. The
+            // reporter is cloned out of the lock before calling it, so that it can register
+            // another reporter without deadlocking.
+            let reporter = PANIC_REPORTER
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+            if let Some(reporter) = reporter {
+                reporter(&report);
+            }
+            // The thread-local is gone if the thread is exiting, and it's borrowed if the panic
+            // happened while taking a report. Either way, there's nowhere to put the report.
+            let _ = LAST_PANIC.try_with(|last| {
+                if let Ok(mut last) = last.try_borrow_mut() {
+                    *last = Some(report);
+                }
+            });
+            previous_hook(info);
+        }));
+    });
+}
+
+/// Register a function to call with the report for every Rust panic, for example to pass it on
+/// to a crash reporter. This replaces any previously registered function, and installs the
+/// panic hook if it isn't already installed.
+///
+/// The function is called from the panic hook on the thread that panicked, so it mustn't
+/// panic itself, since that would abort the process.
+pub fn set_panic_reporter(reporter: impl Fn(&PanicReport) + Send + Sync + 'static) {
+    install_hook();
+    // The previous reporter is dropped after releasing the lock, in case dropping it panics.
+    let _previous = PANIC_REPORTER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(Arc::new(reporter));
+}
+
+/// Take the report for the most recent panic on the current thread, if there is one.
+pub fn take_panic_report() -> Option<PanicReport> {
+    LAST_PANIC.with(|last| last.borrow_mut().take())
+}
+
+/// Take the report for the most recent panic on the current thread, serialized into a
+/// `RustBuffer` for the foreign language code, or an empty buffer if there isn't one.
+pub(crate) fn take_serialized_panic_report() -> crate::RustBuffer {
+    let mut buf = Vec::new();
+    if let Some(report) = take_panic_report() {
+        report.write(&mut buf);
+    }
+    crate::RustBuffer::from_vec(buf)
+}
+
+#[cfg(feature = "panic-backtraces")]
+fn capture_backtrace() -> Option<String> {
+    Some(std::backtrace::Backtrace::force_capture().to_string())
+}
+
+#[cfg(not(feature = "panic-backtraces"))]
+fn capture_backtrace() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_panics_are_reported() {
+        install_hook();
+        let result = std::thread::Builder::new()
+            .name("panicky".to_string())
+            .spawn(|| {
+                let result = std::panic::catch_unwind(|| panic!("oh no: {}", 42));
+                assert!(result.is_err());
+                let line = line!() - 2;
+                let report = take_panic_report().unwrap();
+                assert_eq!(report.message, "oh no: 42");
+                assert_eq!(report.file, file!());
+                assert_eq!(report.line, line);
+                assert_eq!(report.thread_name.as_deref(), Some("panicky"));
+                assert_eq!(
+                    report.backtrace.is_some(),
+                    cfg!(feature = "panic-backtraces")
+                );
+                // Taking the report clears it.
+                assert_eq!(take_panic_report(), None);
+            })
+            .unwrap()
+            .join();
+        assert!(result.is_ok());
+    }
+
+    lazy_static::lazy_static! {
+        // The tests that register a reporter mustn't run at the same time.
+        static ref REPORTER_TESTS: std::sync::Mutex<()> = Default::default();
+    }
+
+    #[test]
+    fn test_panic_reporter_is_called_for_every_panic() {
+        lazy_static::lazy_static! {
+            static ref REPORTED: std::sync::Mutex<Vec<String>> = Default::default();
+        }
+        let _guard = REPORTER_TESTS.lock().unwrap();
+        // Other tests may panic at the same time, so only look at the panics on our thread.
+        set_panic_reporter(|report| {
+            if report.thread_name.as_deref() == Some("reported") {
+                REPORTED.lock().unwrap().push(report.message.clone());
+            }
+        });
+        std::thread::Builder::new()
+            .name("reported".to_string())
+            .spawn(|| {
+                let _ = std::panic::catch_unwind(|| panic!("first"));
+                let _ = std::panic::catch_unwind(|| panic!("second"));
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(*REPORTED.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_panic_reporter_can_replace_itself() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        static REPLACED: AtomicBool = AtomicBool::new(false);
+        let _guard = REPORTER_TESTS.lock().unwrap();
+        set_panic_reporter(|report| {
+            if report.thread_name.as_deref() == Some("replacing") {
+                set_panic_reporter(|_| REPLACED.store(true, Ordering::SeqCst));
+            }
+        });
+        std::thread::Builder::new()
+            .name("replacing".to_string())
+            .spawn(|| {
+                let _ = std::panic::catch_unwind(|| panic!("first"));
+                assert_eq!(take_panic_report().unwrap().message, "first");
+                let _ = std::panic::catch_unwind(|| panic!("second"));
+                assert_eq!(take_panic_report().unwrap().message, "second");
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        assert!(REPLACED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_panics_while_taking_a_report_are_not_recorded() {
+        install_hook();
+        let result = std::thread::spawn(|| {
+            let result = LAST_PANIC.with(|last| {
+                let _borrowed = last.borrow_mut();
+                std::panic::catch_unwind(|| panic!("while borrowed"))
+            });
+            assert!(result.is_err());
+            assert_eq!(take_panic_report(), None);
+        })
+        .join();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_serialized_panic_report() {
+        let report = PanicReport {
+            message: "oh no".to_string(),
+            file: "src/lib.rs".to_string(),
+            line: 1,
+            column: 2,
+            thread_name: None,
+            backtrace: None,
+        };
+        let mut buf = Vec::new();
+        report.write(&mut buf);
+        let mut buf = buf.as_slice();
+        assert_eq!(String::try_read(&mut buf).unwrap(), "oh no");
+        assert_eq!(String::try_read(&mut buf).unwrap(), "src/lib.rs");
+        assert_eq!(i32::try_read(&mut buf).unwrap(), 1);
+        assert_eq!(i32::try_read(&mut buf).unwrap(), 2);
+        assert_eq!(String::try_read(&mut buf).unwrap(), "");
+        assert_eq!(String::try_read(&mut buf).unwrap(), "");
+        assert!(buf.is_empty());
+        // There's nothing to serialize if the thread hasn't panicked.
+        assert_eq!(
+            take_serialized_panic_report().destroy_into_vec(),
+            Vec::<u8>::new()
+        );
+    }
+}
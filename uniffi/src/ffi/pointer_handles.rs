@@ -0,0 +1,502 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `ArcPointerHandles` hands out objects to foreign language code as raw `Arc<T>` pointers.
+//!
+//! This is an alternative to the `ArcHandleMap`, for objects declared with the `[PointerHandle]`
+//! attribute. Rather than looking each handle up in a global map, and hence taking the map's
+//! lock on every method call, the handle *is* the pointer to the object. Calling a method
+//! increments the `Arc`'s reference count for the duration of the call, and freeing the object
+//! decrements it, so calls on unrelated objects (or on the same object) never contend.
+//!
+//! The cost is that there's no way to tell in general whether a handle is valid: passing in a
+//! handle for an object that has already been freed, or that was never a handle at all, is
+//! undefined behaviour. So the functions that take a handle are `unsafe`, and it's up to the
+//! foreign-language bindings to make sure that an object isn't freed while a call that is using
+//! its handle is still in progress, and that its handle is never used after it's been freed. The
+//! Kotlin and Python bindings count the calls in progress on each object, and leave freeing it to
+//! the last of them to return; in Swift, an object can't be deinitialized while one of its methods
+//! is running, and JavaScript only runs one call at a time. To help catch any bugs in them, debug
+//! builds keep a registry of the handles that are live, along with the type of object that each
+//! one points to, which is checked on every call and updated when a handle is freed, so that
+//! using a stale handle (or a handle for a different type of object) fails with an error rather
+//! than silently corrupting memory. Calls hold the registry's lock until they have their own
+//! reference to the object, so that it can't be freed on another thread in the meantime. (This
+//! means that in debug builds, calls do contend with each other.)
+//!
+//! Since the handle is exactly the pointer that `Arc::into_raw` gives us, constructors that
+//! return an `Arc<T>` and methods that take `self: Arc<Self>` can be handed that `Arc` directly.
+//!
+//! Like the `ArcHandleMap`, this is only suitable for objects that implement `Sync` and `Send`.
+
+use ffi_support::{ExternError, HandleError, IntoFfi};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+#[cfg(debug_assertions)]
+mod registry {
+    use ffi_support::HandleError;
+    use std::any::TypeId;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    lazy_static::lazy_static! {
+        // The number of live handles with each value, for each type of object. This can be more
+        // than one, because a constructor that returns an `Arc` might return a clone of one it
+        // returned before.
+        static ref LIVE_HANDLES: Mutex<HashMap<(TypeId, u64), usize>> = Default::default();
+    }
+
+    pub(super) fn register<T: 'static>(h: u64) {
+        *LIVE_HANDLES
+            .lock()
+            .unwrap()
+            .entry((TypeId::of::<T>(), h))
+            .or_insert(0) += 1;
+    }
+
+    /// Call `f` if `h` is a live handle for a `T`, holding the lock until it returns,
+    /// so that the handle can't be freed on another thread in the meantime.
+    pub(super) fn while_live<T: 'static, R>(
+        h: u64,
+        f: impl FnOnce() -> R,
+    ) -> Result<R, HandleError> {
+        let live = LIVE_HANDLES.lock().unwrap();
+        if live.contains_key(&(TypeId::of::<T>(), h)) {
+            Ok(f())
+        } else {
+            Err(HandleError::InvalidHandle)
+        }
+    }
+
+    pub(super) fn unregister<T: 'static>(h: u64) -> Result<(), HandleError> {
+        let mut live = LIVE_HANDLES.lock().unwrap();
+        let key = (TypeId::of::<T>(), h);
+        match live.get_mut(&key) {
+            None => Err(HandleError::InvalidHandle),
+            Some(1) => {
+                live.remove(&key);
+                Ok(())
+            }
+            Some(count) => {
+                *count -= 1;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Hands out objects of type `T` to foreign language code as raw `Arc<T>` pointers.
+///
+/// This holds no state, and exists so that the generated scaffolding can treat objects with
+/// pointer handles in the same way as ones stored in an `ArcHandleMap`.
+pub struct ArcPointerHandles<T> {
+    // Use a function pointer so that this is `Sync`, and hence can be a `static`, whatever `T` is.
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Sync + Send + 'static> ArcPointerHandles<T> {
+    /// Construct a new `ArcPointerHandles`.
+    pub const fn new() -> Self {
+        ArcPointerHandles {
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Move an item into a new `Arc`, returning a handle that owns one reference to it.
+    pub fn insert(&self, v: T) -> u64 {
+        self.insert_arc(Arc::new(v))
+    }
+
+    /// Return a handle that owns the given reference to an item.
+    pub fn insert_arc(&self, v: Arc<T>) -> u64 {
+        let h = Arc::into_raw(v) as usize as u64;
+        #[cfg(debug_assertions)]
+        registry::register::<T>(h);
+        h
+    }
+
+    /// Get the pointer from a handle, checking that it could be valid. In debug builds, the
+    /// registry then checks that it actually is.
+    fn pointer(h: u64) -> Result<*const T, HandleError> {
+        if h == 0 {
+            return Err(HandleError::NullHandle);
+        }
+        let ptr = h as usize as *const T;
+        if (ptr as usize) & (std::mem::align_of::<T>() - 1) != 0 {
+            return Err(HandleError::InvalidHandle);
+        }
+        Ok(ptr)
+    }
+
+    /// Get a new reference to the item with the given handle, leaving the handle's own reference alone.
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    unsafe fn clone_arc(h: u64) -> Result<Arc<T>, HandleError> {
+        let ptr = Self::pointer(h)?;
+        // We mustn't drop the `Arc` that the handle owns, so we clone it and forget the original.
+        let clone = || {
+            let arc = std::mem::ManuallyDrop::new(unsafe { Arc::from_raw(ptr) });
+            Arc::clone(&arc)
+        };
+        #[cfg(debug_assertions)]
+        return registry::while_live::<T, _>(h, clone);
+        #[cfg(not(debug_assertions))]
+        Ok(clone())
+    }
+
+    /// Release the handle's reference to its item, dropping the item if that was the last one.
+    ///
+    /// If a method call is in progress on another thread, the item won't be dropped until it finishes.
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`, there mustn't
+    /// be any calls in progress that are using it, and it mustn't be used again afterwards.
+    pub unsafe fn delete_u64(&self, h: u64) -> Result<(), HandleError> {
+        let ptr = Self::pointer(h)?;
+        // Unregistering the handle checks it atomically, which catches double frees. Once it's
+        // unregistered, no other thread can take a new reference through it.
+        #[cfg(debug_assertions)]
+        registry::unregister::<T>(h)?;
+        drop(unsafe { Arc::from_raw(ptr) });
+        Ok(())
+    }
+
+    /// Call `callback` with a reference to the item with the given handle, while inside a
+    /// [`call_with_result`] call (to handle panics and map errors onto an `ExternError`).
+    ///
+    /// This holds a reference to the item for as long as the callback is in use.
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            let obj = unsafe { Self::clone_arc(h)? };
+            Ok(callback(&*obj)?)
+        })
+    }
+
+    /// Helper that performs both a [`call_with_output`] and [`get`](ArcPointerHandles::call_with_result).
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T) -> R,
+        R: IntoFfi,
+    {
+        unsafe {
+            self.call_with_result(out_error, h, |r| -> Result<_, HandleError> {
+                Ok(callback(r))
+            })
+        }
+    }
+
+    /// Use `constructor` to create a `T` and return a handle to it, while inside a
+    /// [`call_with_result`] call (to handle panics and map errors onto an `ExternError`).
+    pub fn insert_with_result<E, F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Result<T, E>,
+        ExternError: From<E>,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            Ok(self.insert(constructor()?))
+        })
+    }
+
+    /// Equivalent to
+    /// [`insert_with_result`](ArcPointerHandles::insert_with_result) for the
+    /// case where the constructor cannot produce an error.
+    pub fn insert_with_output<F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> T,
+    {
+        self.insert_with_result(out_error, || -> Result<_, HandleError> {
+            Ok(constructor())
+        })
+    }
+
+    /// The same as [`insert_with_result`](ArcPointerHandles::insert_with_result), but for
+    /// constructors that return an `Arc<T>`, which the handle then points to.
+    pub fn insert_arc_with_result<E, F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Result<Arc<T>, E>,
+        ExternError: From<E>,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            Ok(self.insert_arc(constructor()?))
+        })
+    }
+
+    /// Equivalent to
+    /// [`insert_arc_with_result`](ArcPointerHandles::insert_arc_with_result) for the
+    /// case where the constructor cannot produce an error.
+    pub fn insert_arc_with_output<F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Arc<T>,
+    {
+        self.insert_arc_with_result(out_error, || -> Result<_, HandleError> {
+            Ok(constructor())
+        })
+    }
+
+    /// The same as `call_with_result`, named to match the `UniffiMethodCall` trait.
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn method_call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        unsafe { self.call_with_result(out_error, h, callback) }
+    }
+
+    /// The same as `call_with_output`, named to match the `UniffiMethodCall` trait.
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn method_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T) -> R,
+        R: IntoFfi,
+    {
+        unsafe { self.call_with_output(out_error, h, callback) }
+    }
+
+    /// Like `method_call_with_result`, but for methods that take `self: Arc<Self>`.
+    ///
+    /// The callback is given its own reference to the item, which it can keep
+    /// after the call returns (for example, by handing it to another thread).
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn arc_method_call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(Arc<T>) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            Ok(callback(unsafe { Self::clone_arc(h)? })?)
+        })
+    }
+
+    /// Like `method_call_with_output`, but for methods that take `self: Arc<Self>`.
+    ///
+    /// # Safety
+    ///
+    /// `h` must be a live handle returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn arc_method_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(Arc<T>) -> R,
+        R: IntoFfi,
+    {
+        unsafe {
+            self.arc_method_call_with_result(out_error, h, |r| -> Result<_, HandleError> {
+                Ok(callback(r))
+            })
+        }
+    }
+
+    /// Call `callback` with references to the two items with the given handles,
+    /// for trait methods like `PartialEq::eq` that take two items.
+    ///
+    /// # Safety
+    ///
+    /// `h1` and `h2` must both be live handles returned by this type's `insert` or `insert_arc`.
+    pub unsafe fn pair_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h1: u64,
+        h2: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T, &T) -> R,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, HandleError> {
+            let (obj1, obj2) = unsafe { (Self::clone_arc(h1)?, Self::clone_arc(h2)?) };
+            Ok(callback(&*obj1, &*obj2))
+        })
+    }
+}
+
+impl<T: Sync + Send + 'static> Default for ArcPointerHandles<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ArcPointerHandles;
+    use ffi_support::{ErrorCode, ExternError};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountDrops(Arc<AtomicUsize>);
+    impl Drop for CountDrops {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_handles_own_a_reference() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let handles = ArcPointerHandles::new();
+        let mut e = ExternError::success();
+        let h = handles.insert_with_output(&mut e, || CountDrops(Arc::clone(&drops)));
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_ne!(h, 0);
+        let count = unsafe {
+            handles.method_call_with_output(&mut e, h, |obj| obj.0.load(Ordering::SeqCst) as u32)
+        };
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(count, 0);
+        assert!(unsafe { handles.delete_u64(h) }.is_ok());
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_the_item_outlives_a_call_that_frees_it() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let handles = ArcPointerHandles::new();
+        let h = handles.insert(CountDrops(Arc::clone(&drops)));
+        let mut e = ExternError::success();
+        let count = unsafe {
+            handles.method_call_with_output(&mut e, h, |obj| {
+                assert!(handles.delete_u64(h).is_ok());
+                obj.0.load(Ordering::SeqCst) as u32
+            })
+        };
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(count, 0);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_null_handle() {
+        let handles = ArcPointerHandles::<u32>::new();
+        let mut e = ExternError::success();
+        unsafe { handles.method_call_with_output(&mut e, 0, |_| ()) };
+        assert_ne!(e.get_code(), ErrorCode::SUCCESS);
+        let _ = unsafe { e.get_and_consume_message() };
+        assert!(unsafe { handles.delete_u64(0) }.is_err());
+    }
+
+    // Using a stale handle breaks the safety contract, but debug builds promise to catch it.
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_stale_handles_are_caught_in_debug_builds() {
+        let handles = ArcPointerHandles::new();
+        let h = handles.insert(1u32);
+        // Keep the item alive, so that its address can't be reused for a new handle.
+        let keep_alive = unsafe { ArcPointerHandles::<u32>::clone_arc(h) }.unwrap();
+        assert!(unsafe { handles.delete_u64(h) }.is_ok());
+        let mut e = ExternError::success();
+        unsafe { handles.method_call_with_output(&mut e, h, |_| ()) };
+        assert_ne!(e.get_code(), ErrorCode::SUCCESS);
+        let _ = unsafe { e.get_and_consume_message() };
+        assert!(matches!(
+            unsafe { handles.delete_u64(h) },
+            Err(ffi_support::HandleError::InvalidHandle)
+        ));
+        assert_eq!(*keep_alive, 1);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_handles_for_other_types_are_caught_in_debug_builds() {
+        let handles = ArcPointerHandles::new();
+        let h = handles.insert(1u64);
+        let other_handles = ArcPointerHandles::<u32>::new();
+        let mut e = ExternError::success();
+        unsafe { other_handles.method_call_with_output(&mut e, h, |_| ()) };
+        assert_ne!(e.get_code(), ErrorCode::SUCCESS);
+        let _ = unsafe { e.get_and_consume_message() };
+        assert!(matches!(
+            unsafe { other_handles.delete_u64(h) },
+            Err(ffi_support::HandleError::InvalidHandle)
+        ));
+        assert!(unsafe { handles.delete_u64(h) }.is_ok());
+    }
+
+    #[test]
+    fn test_arcs_are_passed_through_unwrapped() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let handles = ArcPointerHandles::new();
+        let obj = Arc::new(CountDrops(Arc::clone(&drops)));
+        let mut e = ExternError::success();
+        let h = handles.insert_arc_with_output(&mut e, || Arc::clone(&obj));
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(h, Arc::as_ptr(&obj) as usize as u64);
+        let kept = std::sync::Mutex::new(None);
+        unsafe {
+            handles.arc_method_call_with_output(&mut e, h, |this| {
+                assert!(Arc::ptr_eq(&this, &obj));
+                *kept.lock().unwrap() = Some(this);
+            })
+        };
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert!(unsafe { handles.delete_u64(h) }.is_ok());
+        drop(obj);
+        // The method kept its own reference, so the item is still alive.
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(kept);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+}
@@ -139,6 +139,7 @@ impl RustBuffer {
         let capacity = i32::try_from(v.capacity()).expect("buffer capacity cannot fit into a i32.");
         let len = i32::try_from(v.len()).expect("buffer length cannot fit into a i32.");
         let mut v = std::mem::ManuallyDrop::new(v);
+        super::leaks::buffer_created();
         unsafe { Self::from_raw_parts(v.as_mut_ptr(), len, capacity) }
     }
 
@@ -169,6 +170,7 @@ impl RustBuffer {
                 .try_into()
                 .expect("buffer length negative or overflowed");
             assert!(len <= capacity, "RustBuffer length exceeds capacity");
+            super::leaks::buffer_freed();
             unsafe { Vec::from_raw_parts(self.data, len, capacity) }
         }
     }
@@ -0,0 +1,470 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `ThreadConfinedHandleMap` hands out objects that aren't `Send` to foreign language code.
+//!
+//! This is for objects declared with the `[ThreadConfined]` attribute. Since such an object
+//! can't be moved to (or even dropped on) another thread, it's stored in a map that belongs
+//! to the thread that created it, and calling one of its methods from any other thread fails
+//! with an error whose code is [`WRONG_THREAD_ERROR_CODE`], which the foreign language
+//! bindings turn into a dedicated exception. Calling a method of an object that's already in
+//! use further up the same thread's stack (for example, from a callback into foreign language
+//! code) fails in the same way, with an error whose code is [`REENTRANT_CALL_ERROR_CODE`].
+//!
+//! Freeing an object from another thread is allowed, since the foreign language's garbage
+//! collector might well do that. The handle becomes invalid straight away, but the object
+//! itself is dropped by the thread that owns it, the next time that thread uses a
+//! thread-confined object. Any objects that a thread still owns when it exits are dropped
+//! along with it, and their handles become invalid.
+//!
+//! Handles are allocated from a single counter, so a handle is never valid on more than one
+//! thread, and we can tell the difference between a handle for an object that belongs to
+//! another thread and one that isn't valid at all.
+
+use ffi_support::{ErrorCode, ExternError, HandleError, IntoFfi};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+use super::handle_maps::UniffiMethodCall;
+
+/// The error code for calling a method of a thread-confined object on the wrong thread.
+///
+/// This is negative to keep it apart from the codes of the errors declared in the UDL.
+pub const WRONG_THREAD_ERROR_CODE: i32 = -2;
+
+/// The error code for calling a method of a thread-confined object while it's already in use
+/// on the current thread.
+///
+/// Like [`WRONG_THREAD_ERROR_CODE`], this is negative to keep it apart from the codes of the
+/// errors declared in the UDL.
+pub const REENTRANT_CALL_ERROR_CODE: i32 = -4;
+
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+// The number of handles in `PENDING_FREES`, so that we only need to lock it when there are some.
+static PENDING_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static::lazy_static! {
+    // The thread that owns each live handle.
+    static ref OWNERS: Mutex<HashMap<u64, ThreadId>> = Default::default();
+    // The handles that were freed on a thread other than their owner, by owner.
+    static ref PENDING_FREES: Mutex<HashMap<ThreadId, Vec<u64>>> = Default::default();
+}
+
+thread_local! {
+    // The objects that belong to the current thread, of all types.
+    static OBJECTS: ThreadObjects = ThreadObjects {
+        thread: thread::current().id(),
+        objects: Default::default(),
+    };
+}
+
+struct ThreadObjects {
+    thread: ThreadId,
+    objects: RefCell<HashMap<u64, Rc<dyn Any>>>,
+}
+
+impl Drop for ThreadObjects {
+    // When the thread exits, its objects are dropped, and their handles are no longer valid
+    // anywhere. (We can't panic here, so we leave things be if the locks are poisoned).
+    fn drop(&mut self) {
+        if let Ok(mut owners) = OWNERS.lock() {
+            for h in self.objects.get_mut().keys() {
+                owners.remove(h);
+            }
+        }
+        if let Ok(mut pending) = PENDING_FREES.lock() {
+            if let Some(handles) = pending.remove(&self.thread) {
+                PENDING_COUNT.fetch_sub(handles.len(), Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+/// The ways that using a thread-confined object can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThreadConfinedError {
+    /// The handle isn't valid on any thread.
+    Handle(HandleError),
+    /// The handle belongs to a different thread.
+    WrongThread,
+    /// The object is already in use further up the current thread's stack.
+    AlreadyInUse,
+}
+
+impl std::error::Error for ThreadConfinedError {}
+
+impl std::fmt::Display for ThreadConfinedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThreadConfinedError::Handle(e) => e.fmt(f),
+            ThreadConfinedError::WrongThread => f.write_str(
+                "Tried to use a thread-confined object on a thread other than the one that created it",
+            ),
+            ThreadConfinedError::AlreadyInUse => f.write_str(
+                "Tried to use a thread-confined object that is already in use on the current thread",
+            ),
+        }
+    }
+}
+
+impl From<HandleError> for ThreadConfinedError {
+    fn from(e: HandleError) -> Self {
+        ThreadConfinedError::Handle(e)
+    }
+}
+
+impl From<ThreadConfinedError> for ExternError {
+    fn from(e: ThreadConfinedError) -> Self {
+        match e {
+            ThreadConfinedError::Handle(e) => e.into(),
+            ThreadConfinedError::WrongThread => {
+                ExternError::new_error(ErrorCode::new(WRONG_THREAD_ERROR_CODE), e.to_string())
+            }
+            ThreadConfinedError::AlreadyInUse => {
+                ExternError::new_error(ErrorCode::new(REENTRANT_CALL_ERROR_CODE), e.to_string())
+            }
+        }
+    }
+}
+
+/// Drop any objects belonging to the current thread that were freed on other threads.
+fn drop_pending_frees() {
+    if PENDING_COUNT.load(Ordering::SeqCst) == 0 {
+        return;
+    }
+    let handles = {
+        let mut pending = PENDING_FREES.lock().unwrap();
+        let handles = pending.remove(&thread::current().id()).unwrap_or_default();
+        PENDING_COUNT.fetch_sub(handles.len(), Ordering::SeqCst);
+        handles
+    };
+    // Don't drop the objects while the map is borrowed, in case their destructors use it.
+    let objects: Vec<_> = OBJECTS.with(|objects| {
+        let mut objects = objects.objects.borrow_mut();
+        handles.iter().filter_map(|h| objects.remove(h)).collect()
+    });
+    drop(objects);
+}
+
+/// Hands out objects of type `T`, which needn't be `Send`, to foreign language code,
+/// and only lets them be used on the thread that created them.
+///
+/// This holds no state, since the objects are stored in thread-local maps, and exists so
+/// that the generated scaffolding can treat thread-confined objects in the same way as
+/// ones stored in a `MutexHandleMap`.
+pub struct ThreadConfinedHandleMap<T> {
+    // Use a function pointer so that this is `Sync`, and hence can be a `static`, whatever `T` is.
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> ThreadConfinedHandleMap<T> {
+    /// Construct a new `ThreadConfinedHandleMap`.
+    pub const fn new() -> Self {
+        ThreadConfinedHandleMap {
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Insert an item into the current thread's map, returning the newly allocated handle to it.
+    pub fn insert(&self, v: T) -> u64 {
+        drop_pending_frees();
+        let h = NEXT_HANDLE.fetch_add(1, Ordering::SeqCst);
+        OWNERS.lock().unwrap().insert(h, thread::current().id());
+        OBJECTS.with(|objects| {
+            objects
+                .objects
+                .borrow_mut()
+                .insert(h, Rc::new(RefCell::new(v)) as Rc<dyn Any>)
+        });
+        h
+    }
+
+    /// Get the item with the given handle, if it belongs to the current thread.
+    fn get(h: u64) -> Result<Rc<RefCell<T>>, ThreadConfinedError> {
+        drop_pending_frees();
+        if h == 0 {
+            return Err(HandleError::NullHandle.into());
+        }
+        match OBJECTS.with(|objects| objects.objects.borrow().get(&h).cloned()) {
+            Some(obj) => obj.downcast().map_err(|_| HandleError::WrongMap.into()),
+            None => Err(match OWNERS.lock().unwrap().get(&h) {
+                Some(_) => ThreadConfinedError::WrongThread,
+                None => HandleError::InvalidHandle.into(),
+            }),
+        }
+    }
+
+    /// Free the item with the given handle.
+    ///
+    /// This can be called on any thread, but the item will only be dropped on the thread that
+    /// it belongs to. If that's a different thread, it'll happen the next time that thread uses
+    /// a thread-confined object, or when it exits.
+    pub fn delete_u64(&self, h: u64) -> Result<(), HandleError> {
+        if h == 0 {
+            return Err(HandleError::NullHandle);
+        }
+        let owner = OWNERS
+            .lock()
+            .unwrap()
+            .remove(&h)
+            .ok_or(HandleError::InvalidHandle)?;
+        if owner == thread::current().id() {
+            let obj = OBJECTS.with(|objects| objects.objects.borrow_mut().remove(&h));
+            drop(obj);
+        } else {
+            let mut pending = PENDING_FREES.lock().unwrap();
+            pending.entry(owner).or_default().push(h);
+            PENDING_COUNT.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Call `callback` with a mutable reference to the item with the given handle, while inside
+    /// a [`call_with_result`] call (to handle panics and map errors onto an `ExternError`).
+    ///
+    /// This fails if the item doesn't belong to the current thread, or if it's already in use
+    /// further up the current thread's stack (for example, by a callback into foreign language
+    /// code that then calls back into the item).
+    pub fn call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&mut T) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            let obj = Self::get(h)?;
+            let mut obj = obj
+                .try_borrow_mut()
+                .map_err(|_| ThreadConfinedError::AlreadyInUse)?;
+            let result = callback(&mut *obj);
+            Ok(result?)
+        })
+    }
+
+    /// Helper that performs both a [`call_with_output`] and [`get`](ThreadConfinedHandleMap::call_with_result).
+    pub fn call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&mut T) -> R,
+        R: IntoFfi,
+    {
+        self.call_with_result(out_error, h, |r| -> Result<_, HandleError> {
+            Ok(callback(r))
+        })
+    }
+
+    /// Use `constructor` to create a `T` and insert it into the current thread's map, while
+    /// inside a [`call_with_result`] call (to handle panics and map errors onto an `ExternError`).
+    pub fn insert_with_result<E, F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> Result<T, E>,
+        ExternError: From<E>,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ExternError> {
+            Ok(self.insert(constructor()?))
+        })
+    }
+
+    /// Equivalent to
+    /// [`insert_with_result`](ThreadConfinedHandleMap::insert_with_result) for the
+    /// case where the constructor cannot produce an error.
+    pub fn insert_with_output<F>(&self, out_error: &mut ExternError, constructor: F) -> u64
+    where
+        F: std::panic::UnwindSafe + FnOnce() -> T,
+    {
+        self.insert_with_result(out_error, || -> Result<_, HandleError> {
+            Ok(constructor())
+        })
+    }
+}
+
+impl<T: 'static> Default for ThreadConfinedHandleMap<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> UniffiMethodCall<T> for ThreadConfinedHandleMap<T> {
+    fn method_call_with_result<R, E, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&mut T) -> Result<R, E>,
+        ExternError: From<E>,
+        R: IntoFfi,
+    {
+        self.call_with_result(out_error, h, callback)
+    }
+
+    fn method_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&mut T) -> R,
+        R: IntoFfi,
+    {
+        self.call_with_output(out_error, h, callback)
+    }
+
+    fn pair_call_with_output<R, F>(
+        &self,
+        out_error: &mut ExternError,
+        h1: u64,
+        h2: u64,
+        callback: F,
+    ) -> R::Value
+    where
+        F: std::panic::UnwindSafe + FnOnce(&T, &T) -> R,
+        R: IntoFfi,
+    {
+        use ffi_support::call_with_result;
+        call_with_result(out_error, || -> Result<_, ThreadConfinedError> {
+            let (obj1, obj2) = (Self::get(h1)?, Self::get(h2)?);
+            let borrow =
+                |obj| RefCell::try_borrow(obj).map_err(|_| ThreadConfinedError::AlreadyInUse);
+            let result = callback(&*borrow(&obj1)?, &*borrow(&obj2)?);
+            Ok(result)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ThreadConfinedHandleMap, REENTRANT_CALL_ERROR_CODE, WRONG_THREAD_ERROR_CODE};
+    use crate::UniffiMethodCall;
+    use ffi_support::{ErrorCode, ExternError};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // An `Rc` makes this `!Send`, which is the point of thread-confined objects.
+    struct CountDrops(Rc<Cell<u32>>, Arc<AtomicUsize>);
+    impl Drop for CountDrops {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    static HANDLES: ThreadConfinedHandleMap<CountDrops> = ThreadConfinedHandleMap::new();
+
+    fn new_object(drops: &Arc<AtomicUsize>) -> u64 {
+        let mut e = ExternError::success();
+        let drops = Arc::clone(drops);
+        let h = HANDLES.insert_with_output(&mut e, || CountDrops(Rc::new(Cell::new(0)), drops));
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        h
+    }
+
+    fn increment(h: u64) -> Result<u32, ErrorCode> {
+        let mut e = ExternError::success();
+        let value = HANDLES.method_call_with_output(&mut e, h, |obj| {
+            obj.0.set(obj.0.get() + 1);
+            obj.0.get()
+        });
+        let code = e.get_code();
+        if code.is_success() {
+            Ok(value)
+        } else {
+            let _ = unsafe { e.get_and_consume_message() };
+            Err(code)
+        }
+    }
+
+    #[test]
+    fn test_objects_can_be_used_on_their_own_thread() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let h = new_object(&drops);
+        assert_eq!(increment(h), Ok(1));
+        assert_eq!(increment(h), Ok(2));
+        assert!(HANDLES.delete_u64(h).is_ok());
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert_eq!(increment(h), Err(ErrorCode::INVALID_HANDLE));
+        assert!(HANDLES.delete_u64(h).is_err());
+        assert_eq!(increment(0), Err(ErrorCode::INVALID_HANDLE));
+    }
+
+    #[test]
+    fn test_objects_cannot_be_used_on_other_threads() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let h = new_object(&drops);
+        let result = std::thread::spawn(move || increment(h)).join().unwrap();
+        assert_eq!(result, Err(ErrorCode::new(WRONG_THREAD_ERROR_CODE)));
+        assert_eq!(increment(h), Ok(1));
+        assert!(HANDLES.delete_u64(h).is_ok());
+    }
+
+    #[test]
+    fn test_objects_cannot_be_reentered() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let h = new_object(&drops);
+        let mut e = ExternError::success();
+        let inner_code = HANDLES.method_call_with_output(&mut e, h, |_| {
+            increment(h).err().map_or(0, |code| code.code())
+        });
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(inner_code, REENTRANT_CALL_ERROR_CODE);
+        let inner_code = HANDLES.method_call_with_output(&mut e, h, |_| {
+            let mut e = ExternError::success();
+            HANDLES.pair_call_with_output(&mut e, h, h, |_, _| ());
+            let code = e.get_code().code();
+            let _ = unsafe { e.get_and_consume_message() };
+            code
+        });
+        assert_eq!(e.get_code(), ErrorCode::SUCCESS);
+        assert_eq!(inner_code, REENTRANT_CALL_ERROR_CODE);
+        // Once the outer call has finished, the object can be used again.
+        assert_eq!(increment(h), Ok(1));
+        assert!(HANDLES.delete_u64(h).is_ok());
+    }
+
+    #[test]
+    fn test_objects_freed_on_other_threads_are_dropped_on_their_own() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let h = new_object(&drops);
+        assert!(std::thread::spawn(move || HANDLES.delete_u64(h).is_ok())
+            .join()
+            .unwrap());
+        // The handle is no longer valid, but the object is waiting for this thread to drop it.
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(increment(h), Err(ErrorCode::INVALID_HANDLE));
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_objects_are_dropped_when_their_thread_exits() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let thread_drops = Arc::clone(&drops);
+        let h = std::thread::spawn(move || new_object(&thread_drops))
+            .join()
+            .unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert_eq!(increment(h), Err(ErrorCode::INVALID_HANDLE));
+        assert!(HANDLES.delete_u64(h).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Support for calling the FFI from JavaScript when compiled to WebAssembly.
+//!
+//! On native platforms the foreign-language bindings can allocate the structs that the
+//! FFI passes around (`RustBuffer`, `ForeignBytes`, `ExternError`) in their own memory,
+//! and pass pointers to them into Rust. JavaScript code calling into a `wasm32` module
+//! has no such luxury: every pointer received by Rust must point into the module's linear
+//! memory. These helpers let the generated JS glue borrow some scratch space from the
+//! Rust allocator for the duration of a call.
+//!
+//! With the standard C ABI on `wasm32`, structs are passed to `extern "C"` functions
+//! as a pointer to a copy of the struct, and returned by writing them through a pointer
+//! that is passed as a hidden first argument. The JS glue uses this scratch memory for both.
+
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+
+/// The alignment of all scratch allocations.
+///
+/// This is the largest alignment of any struct that we pass over the FFI, since
+/// `RustBuffer` and `ForeignBytes` both contain an `i64` padding field.
+const SCRATCH_ALIGN: usize = 8;
+
+fn scratch_layout(size: i32) -> Layout {
+    // Zero-sized allocations are not allowed, so always allocate at least one byte.
+    Layout::from_size_align((size.max(1)) as usize, SCRATCH_ALIGN)
+        .expect("invalid size for scratch allocation")
+}
+
+/// Allocate `size` bytes of zeroed scratch memory, returning a pointer into linear memory.
+///
+/// The memory must be released by passing the same pointer and size to `scratch_free`.
+pub fn scratch_alloc(size: i32) -> *mut u8 {
+    // Safety: the layout always has a non-zero size.
+    unsafe { alloc_zeroed(scratch_layout(size)) }
+}
+
+/// Free scratch memory previously allocated by `scratch_alloc`.
+///
+/// # Safety
+/// The arguments *must* be a pointer obtained from `scratch_alloc` and the size that
+/// was passed when allocating it, or you'll risk corrupting the allocator state.
+pub unsafe fn scratch_free(ptr: *mut u8, size: i32) {
+    if !ptr.is_null() {
+        dealloc(ptr, scratch_layout(size))
+    }
+}
@@ -30,7 +30,12 @@
 use anyhow::{bail, Result};
 use bytes::buf::{Buf, BufMut};
 use paste::paste;
-use std::{collections::HashMap, convert::TryFrom};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    hash::Hash,
+    time::{Duration, SystemTime},
+};
 
 pub mod ffi;
 pub use ffi::*;
@@ -118,6 +123,52 @@ pub unsafe trait ViaFfi: Sized {
     /// Since we cannot statically guarantee that the foreign-language code will send valid
     /// serialized bytes for the target type, this method is fallible.
     fn try_read<B: Buf>(buf: &mut B) -> Result<Self>;
+
+    /// Write a slice of rust values into a buffer, as the items of a serialized `Vec`.
+    ///
+    /// The default implementation writes each item in turn, but types whose serialized
+    /// form is the same as their in-memory form can override it to copy them all at once.
+    fn write_slice<B: BufMut>(items: &[Self], buf: &mut B) {
+        for item in items {
+            ViaFfi::write(item, buf);
+        }
+    }
+
+    /// Read `len` rust values from a buffer, as the items of a serialized `Vec`.
+    ///
+    /// This is the counterpart to `write_slice`, and likewise defaults to reading each
+    /// item in turn.
+    fn try_read_vec<B: Buf>(buf: &mut B, len: usize) -> Result<Vec<Self>> {
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(<Self as ViaFfi>::try_read(buf)?)
+        }
+        Ok(vec)
+    }
+}
+
+/// Trait defining how to convert a custom type to and from the builtin type that it wraps.
+///
+/// A UDL declaration like `[Custom] typedef string Guid;` declares that the component's
+/// `Guid` type is passed over the FFI as a `string`. The component must implement this trait
+/// to say how to make that conversion, and the generated scaffolding will use it to implement
+/// `ViaFfi` for `Guid` in terms of the `ViaFfi` implementation for `String`.
+///
+/// Converting into the custom type is fallible, so that a custom type can enforce any
+/// invariants on the values that it holds. If the conversion fails, it is treated in the
+/// same way as receiving an invalid value of any other type over the FFI.
+pub trait CustomTypeConverter: Sized {
+    /// The builtin type that this custom type is passed over the FFI as.
+    type Builtin: ViaFfi;
+
+    /// Convert a value of the builtin type into the custom type.
+    fn into_custom(val: Self::Builtin) -> Result<Self>;
+
+    /// Convert a value of the custom type into the builtin type.
+    ///
+    /// This takes a reference rather than an owned value, since values that are part of
+    /// a compound data structure are written out from behind a reference.
+    fn from_custom(obj: &Self) -> Self::Builtin;
 }
 
 /// A helper function to lower a type by serializing it into a buffer.
@@ -197,7 +248,43 @@ macro_rules! impl_via_ffi_for_num_primitive {
 }
 
 impl_via_ffi_for_num_primitive! {
-    i8, u8, i16, u16, i32, u32, i64, u64, f32, f64
+    i8, i16, u16, i32, u32, i64, u64, f32, f64
+}
+
+/// Support for passing bytes via the FFI.
+///
+/// Individual bytes are passed like any other numeric primitive, but a `Vec<u8>`
+/// is serialized by copying all of its bytes at once rather than one at a time.
+unsafe impl ViaFfi for u8 {
+    type FfiType = Self;
+
+    fn lower(self) -> Self::FfiType {
+        self
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        Ok(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u8(*self);
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 1)?;
+        Ok(buf.get_u8())
+    }
+
+    fn write_slice<B: BufMut>(items: &[Self], buf: &mut B) {
+        buf.put_slice(items);
+    }
+
+    fn try_read_vec<B: Buf>(buf: &mut B, len: usize) -> Result<Vec<Self>> {
+        check_remaining(buf, len)?;
+        let mut vec = vec![0; len];
+        buf.copy_to_slice(&mut vec);
+        Ok(vec)
+    }
 }
 
 /// Support for passing boolean values via the FFI.
@@ -331,9 +418,8 @@ unsafe impl<T: ViaFfi> ViaFfi for Option<T> {
 /// We write a `i32` item count followed by each item in turn.
 /// (It's a signed type due to limits of the JVM).
 ///
-/// Ideally we would pass `Vec<u8>` directly as a `RustBuffer` rather
-/// than serializing, and perhaps even pass other vector types using a
-/// similar struct. But that's for future work.
+/// The items are written and read via `ViaFfi::write_slice` and `ViaFfi::try_read_vec`,
+/// which lets a `Vec<u8>` (the `bytes` type in UDL) be copied in bulk.
 unsafe impl<T: ViaFfi> ViaFfi for Vec<T> {
     type FfiType = RustBuffer;
 
@@ -349,31 +435,205 @@ unsafe impl<T: ViaFfi> ViaFfi for Vec<T> {
         // TODO: would be nice not to panic here :-/
         let len = i32::try_from(self.len()).unwrap();
         buf.put_i32(len); // We limit arrays to i32::MAX items
-        for item in self.iter() {
-            ViaFfi::write(item, buf);
-        }
+        <T as ViaFfi>::write_slice(self, buf);
     }
 
     fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
-        let mut vec = Vec::with_capacity(len);
-        for _ in 0..len {
-            vec.push(<T as ViaFfi>::try_read(buf)?)
+        <T as ViaFfi>::try_read_vec(buf, len)
+    }
+}
+
+/// Support for passing timestamps via the FFI.
+///
+/// Timestamps are passed by serializing into a buffer, as an `i64` count of whole seconds
+/// since the unix epoch (negative for timestamps before the epoch) followed by a `u32`
+/// count of nanoseconds to add to that, which is always less than one second.
+unsafe impl ViaFfi for SystemTime {
+    type FfiType = RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        try_lift_from_buffer(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        // Timestamps too far from the epoch for their seconds to fit in an `i64` are
+        // clamped to the earliest or latest timestamp that we can represent.
+        let (seconds, nanos) = match self.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => match i64::try_from(after.as_secs()) {
+                Ok(seconds) => (seconds, after.subsec_nanos()),
+                Err(_) => (i64::MAX, 999_999_999),
+            },
+            Err(e) => {
+                let before = e.duration();
+                match i64::try_from(before.as_secs()) {
+                    Err(_) => (i64::MIN, 0),
+                    Ok(seconds) => match before.subsec_nanos() {
+                        0 => (-seconds, 0),
+                        nanos => (-seconds - 1, 1_000_000_000 - nanos),
+                    },
+                }
+            }
+        };
+        buf.put_i64(seconds);
+        buf.put_u32(nanos);
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_i64();
+        let nanos = buf.get_u32();
+        if nanos >= 1_000_000_000 {
+            bail!("invalid nanoseconds in timestamp")
         }
-        Ok(vec)
+        let timestamp = if seconds >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(Duration::new(seconds as u64, nanos))
+        } else {
+            SystemTime::UNIX_EPOCH
+                .checked_sub(Duration::from_secs(seconds.unsigned_abs()))
+                .and_then(|t| t.checked_add(Duration::from_nanos(u64::from(nanos))))
+        };
+        match timestamp {
+            Some(timestamp) => Ok(timestamp),
+            None => bail!("timestamp out of range"),
+        }
+    }
+}
+
+/// Support for passing durations via the FFI.
+///
+/// Durations are passed by serializing into a buffer, as a `u64` count of whole seconds
+/// followed by a `u32` count of nanoseconds, which is always less than one second.
+unsafe impl ViaFfi for Duration {
+    type FfiType = RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        try_lift_from_buffer(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u64(self.as_secs());
+        buf.put_u32(self.subsec_nanos());
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_u64();
+        let nanos = buf.get_u32();
+        if nanos >= 1_000_000_000 {
+            bail!("invalid nanoseconds in duration")
+        }
+        Ok(Duration::new(seconds, nanos))
     }
 }
 
+/// Define a generic sum type for passing anonymous unions via the FFI.
+///
+/// Since Rust doesn't have anonymous enums, a UDL union type like `(string or u32)`
+/// is represented by the generic enum with the matching number of members, such as
+/// `Union2<String, u32>`. The variants are named positionally, so the value for the
+/// first member type is `Union2::A(...)`, the second is `Union2::B(...)` and so on.
+///
+/// Unions are currently always passed by serializing to a buffer.
+/// We write a `u8` tag giving the 1-based position of the variant, followed by its value.
+macro_rules! define_union_type {
+    ($Name:ident { $($Variant:ident($T:ident) = $tag:literal),+ }) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $Name<$($T),+> {
+            $($Variant($T)),+
+        }
+
+        unsafe impl<$($T: ViaFfi),+> ViaFfi for $Name<$($T),+> {
+            type FfiType = RustBuffer;
+
+            fn lower(self) -> Self::FfiType {
+                lower_into_buffer(self)
+            }
+
+            fn try_lift(v: Self::FfiType) -> Result<Self> {
+                try_lift_from_buffer(v)
+            }
+
+            fn write<B: BufMut>(&self, buf: &mut B) {
+                match self {
+                    $($Name::$Variant(v) => {
+                        buf.put_u8($tag);
+                        ViaFfi::write(v, buf);
+                    })+
+                }
+            }
+
+            fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+                check_remaining(buf, 1)?;
+                Ok(match buf.get_u8() {
+                    $($tag => $Name::$Variant(<$T as ViaFfi>::try_read(buf)?),)+
+                    _ => bail!("unexpected tag byte for {}", stringify!($Name)),
+                })
+            }
+        }
+    };
+}
+
+define_union_type!(Union2 { A(T1) = 1, B(T2) = 2 });
+define_union_type!(Union3 { A(T1) = 1, B(T2) = 2, C(T3) = 3 });
+define_union_type!(Union4 { A(T1) = 1, B(T2) = 2, C(T3) = 3, D(T4) = 4 });
+define_union_type!(Union5 { A(T1) = 1, B(T2) = 2, C(T3) = 3, D(T4) = 4, E(T5) = 5 });
+define_union_type!(Union6 { A(T1) = 1, B(T2) = 2, C(T3) = 3, D(T4) = 4, E(T5) = 5, F(T6) = 6 });
+
+/// Support for passing tuples via the FFI.
+///
+/// Tuples are currently always passed by serializing to a buffer.
+/// We write each element in turn, with no other framing.
+macro_rules! impl_via_ffi_for_tuple {
+    ($($T:ident),+) => {
+        unsafe impl<$($T: ViaFfi),+> ViaFfi for ($($T,)+) {
+            type FfiType = RustBuffer;
+
+            fn lower(self) -> Self::FfiType {
+                lower_into_buffer(self)
+            }
+
+            fn try_lift(v: Self::FfiType) -> Result<Self> {
+                try_lift_from_buffer(v)
+            }
+
+            #[allow(non_snake_case)]
+            fn write<B: BufMut>(&self, buf: &mut B) {
+                let ($($T,)+) = self;
+                $(ViaFfi::write($T, buf);)+
+            }
+
+            fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+                Ok(($(<$T as ViaFfi>::try_read(buf)?,)+))
+            }
+        }
+    };
+}
+
+impl_via_ffi_for_tuple!(T1, T2);
+impl_via_ffi_for_tuple!(T1, T2, T3);
+impl_via_ffi_for_tuple!(T1, T2, T3, T4);
+impl_via_ffi_for_tuple!(T1, T2, T3, T4, T5);
+impl_via_ffi_for_tuple!(T1, T2, T3, T4, T5, T6);
+
 /// Support for associative arrays via the FFI.
-/// Note that because of webidl limitations,
-/// the key must always be of the String type.
+/// The key can be any type that can itself be passed via the FFI,
+/// as long as it can be hashed and compared for equality.
 ///
 /// HashMaps are currently always passed by serializing to a buffer.
-/// We write a `i32` entries count followed by each entry (string
-/// key followed by the value) in turn.
+/// We write a `i32` entries count followed by each entry (serialized
+/// key followed by the serialized value) in turn.
 /// (It's a signed type due to limits of the JVM).
-unsafe impl<V: ViaFfi> ViaFfi for HashMap<String, V> {
+unsafe impl<K: ViaFfi + Hash + Eq, V: ViaFfi> ViaFfi for HashMap<K, V> {
     type FfiType = RustBuffer;
 
     fn lower(self) -> Self::FfiType {
@@ -399,7 +659,7 @@ unsafe impl<V: ViaFfi> ViaFfi for HashMap<String, V> {
         let len = usize::try_from(buf.get_i32())?;
         let mut map = HashMap::with_capacity(len);
         for _ in 0..len {
-            let key = String::try_read(buf)?;
+            let key = <K as ViaFfi>::try_read(buf)?;
             let value = <V as ViaFfi>::try_read(buf)?;
             map.insert(key, value);
         }
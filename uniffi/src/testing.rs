@@ -10,7 +10,7 @@
 //! the `uniffi_macros` crate.
 
 use anyhow::{bail, Result};
-use cargo_metadata::Message;
+use cargo_metadata::{Message, MetadataCommand};
 use lazy_static::lazy_static;
 use std::{
     collections::HashMap,
@@ -35,15 +35,41 @@ lazy_static! {
 /// a foreign-language test file that exercises that component's bindings. It ensures that the
 /// component is compiled and available for use and then executes the foreign language script,
 /// returning successfully iff the script exits successfully.
+///
+/// JavaScript test files (those with a `.js` or `.mjs` extension) exercise bindings for the
+/// WebAssembly target, so for those the component is compiled for `wasm32-unknown-unknown`.
+///
+/// If the component crate declares a `leak-tracking` feature, which should enable the feature
+/// of the same name on `uniffi`, the component is built with that feature and the test fails
+/// if the script leaves any of the component's objects or buffers alive when it finishes.
+/// Scripts can also call the `assertNoLeaks()` helper from the generated bindings
+/// (`assert_no_leaks()` in Python) to check for leaks at any point.
 pub fn run_foreign_language_testcase(pkg_dir: &str, udl_file: &str, test_file: &str) -> Result<()> {
-    let cdylib_file = ensure_compiled_cdylib(pkg_dir)?;
+    run_foreign_language_testcase_for_components(pkg_dir, &[udl_file], test_file)
+}
+
+/// Execute the given foreign-language script as part of a rust test suite, for a crate that
+/// bundles several components into its library.
+///
+/// This is like `run_foreign_language_testcase`, but takes the udl files for all of the
+/// components in the library, and the script can use the bindings for any of them.
+pub fn run_foreign_language_testcase_for_components(
+    pkg_dir: &str,
+    udl_files: &[&str],
+    test_file: &str,
+) -> Result<()> {
+    let cdylib_file = match Path::new(test_file).extension().and_then(|e| e.to_str()) {
+        Some("js") | Some("mjs") => ensure_compiled_wasm(pkg_dir)?,
+        _ => ensure_compiled_cdylib(pkg_dir)?,
+    };
     let out_dir = Path::new(cdylib_file.as_str())
         .parent()
         .ok_or_else(|| anyhow::anyhow!("Generated cdylib has no parent directory"))?
         .to_str()
         .unwrap();
+    let check_leaks = has_leak_tracking_feature(pkg_dir)?;
     let _lock = UNIFFI_BINDGEN.lock();
-    run_uniffi_bindgen_test(out_dir, udl_file, test_file)?;
+    run_uniffi_bindgen_test(out_dir, udl_files, test_file, check_leaks)?;
     Ok(())
 }
 
@@ -56,14 +82,39 @@ pub fn run_foreign_language_testcase(pkg_dir: &str, udl_file: &str, test_file: &
 /// Internally, this function does a bit of caching and concurrency management to avoid rebuilding
 /// the component for multiple testcases.
 pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
+    compile_component(pkg_dir, None)
+}
+
+/// Ensure that a uniffi component crate is compiled to WebAssembly and ready for use.
+///
+/// This is like `ensure_compiled_cdylib`, but builds the component for the
+/// `wasm32-unknown-unknown` target and returns the path to the resulting `.wasm` file.
+/// That target must have been installed, e.g. via `rustup target add wasm32-unknown-unknown`.
+pub fn ensure_compiled_wasm(pkg_dir: &str) -> Result<String> {
+    compile_component(pkg_dir, Some("wasm32-unknown-unknown"))
+}
+
+fn compile_component(pkg_dir: &str, target: Option<&str>) -> Result<String> {
+    let cache_key = match target {
+        Some(target) => format!("{}@{}", pkg_dir, target),
+        None => pkg_dir.to_string(),
+    };
     // Have we already compiled this component?
     let mut compiled_components = COMPILED_COMPONENTS.lock().unwrap();
-    if let Some(cdylib_file) = compiled_components.get(pkg_dir) {
+    if let Some(cdylib_file) = compiled_components.get(&cache_key) {
         return Ok(cdylib_file.to_string());
     }
     // Nope, looks like we'll have to compile it afresh.
     let mut cmd = Command::new("cargo");
     cmd.arg("build").arg("--message-format=json").arg("--lib");
+    if let Some(target) = target {
+        cmd.arg("--target").arg(target);
+    }
+    // Leak tracking is only enabled for these builds, so it doesn't leak into other crates
+    // in the same workspace via feature unification.
+    if has_leak_tracking_feature(pkg_dir)? {
+        cmd.arg("--features").arg("leak-tracking");
+    }
     cmd.current_dir(pkg_dir);
     cmd.stdout(Stdio::piped());
     let mut child = cmd.spawn()?;
@@ -107,9 +158,11 @@ pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
     let cdylib_files: Vec<_> = cdylib
         .filenames
         .iter()
-        .filter(|nm| match nm.extension().unwrap_or_default().to_str() {
-            Some("dylib") | Some("so") => true,
-            _ => false,
+        .filter(|nm| {
+            matches!(
+                (target, nm.extension().unwrap_or_default().to_str()),
+                (None, Some("dylib")) | (None, Some("so")) | (Some(_), Some("wasm"))
+            )
         })
         .collect();
     if cdylib_files.len() != 1 {
@@ -117,10 +170,23 @@ pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
     }
     let cdylib_file = cdylib_files[0].to_string_lossy().into_owned();
     // Cache the result for subsequent tests.
-    compiled_components.insert(pkg_dir.to_string(), cdylib_file.clone());
+    compiled_components.insert(cache_key, cdylib_file.clone());
     Ok(cdylib_file)
 }
 
+/// Check whether the crate in `pkg_dir` declares a `leak-tracking` feature.
+fn has_leak_tracking_feature(pkg_dir: &str) -> Result<bool> {
+    let manifest_path = Path::new(pkg_dir).join("Cargo.toml");
+    let metadata = MetadataCommand::new()
+        .manifest_path(&manifest_path)
+        .no_deps()
+        .exec()?;
+    let manifest_path = manifest_path.canonicalize()?;
+    Ok(metadata.packages.iter().any(|package| {
+        package.manifest_path == manifest_path && package.features.contains_key("leak-tracking")
+    }))
+}
+
 /// Execute the `uniffi-bindgen test` command.
 ///
 /// The default behaviour, suitable for most consumers, is to shell out to the `uniffi-bindgen`
@@ -130,10 +196,21 @@ pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
 /// on the `uniffi_bindgen` crate and execute its methods in-process. This is useful for folks
 /// who are working on uniffi itself and want to test out their changes to the bindings generator.
 #[cfg(not(feature = "builtin-bindgen"))]
-fn run_uniffi_bindgen_test(out_dir: &str, udl_file: &str, test_file: &str) -> Result<()> {
-    let status = Command::new("uniffi-bindgen")
-        .args(&["test", out_dir, udl_file, test_file])
-        .status()?;
+fn run_uniffi_bindgen_test(
+    out_dir: &str,
+    udl_files: &[&str],
+    test_file: &str,
+    check_leaks: bool,
+) -> Result<()> {
+    let mut cmd = Command::new("uniffi-bindgen");
+    cmd.args(&["test", out_dir, udl_files[0]]);
+    for udl_file in &udl_files[1..] {
+        cmd.args(&["--component", udl_file]);
+    }
+    if check_leaks {
+        cmd.arg("--check-leaks");
+    }
+    let status = cmd.arg(test_file).status()?;
     if !status.success() {
         bail!("Error while running tests: {}",);
     }
@@ -141,6 +218,11 @@ fn run_uniffi_bindgen_test(out_dir: &str, udl_file: &str, test_file: &str) -> Re
 }
 
 #[cfg(feature = "builtin-bindgen")]
-fn run_uniffi_bindgen_test(out_dir: &str, udl_file: &str, test_file: &str) -> Result<()> {
-    uniffi_bindgen::run_tests(out_dir, udl_file, vec![test_file], None)
+fn run_uniffi_bindgen_test(
+    out_dir: &str,
+    udl_files: &[&str],
+    test_file: &str,
+    check_leaks: bool,
+) -> Result<()> {
+    uniffi_bindgen::run_tests(out_dir, udl_files, vec![test_file], None, check_leaks)
 }
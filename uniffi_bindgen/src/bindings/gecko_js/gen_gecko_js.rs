@@ -112,6 +112,18 @@ impl<'config, 'ci> Context<'config, 'ci> {
         format!("{}_RustError", self.ci.ffi_namespace())
     }
 
+    /// Returns the type name of the C struct used to pass the dictionary with
+    /// the given `name` over the FFI, for dictionaries that only contain
+    /// numbers and Booleans. See the docs for `ffi_rustbuffer_type` about why
+    /// this type name must be unique for each component.
+    pub fn ffi_struct_type(&self, name: &str) -> String {
+        format!(
+            "{}_{}FfiStruct",
+            self.ci.ffi_namespace(),
+            name.to_camel_case()
+        )
+    }
+
     /// Returns the name to use for the `detail` C++ namespace, which contains
     /// the serialization helpers and other internal types. This name must be
     /// unique for each component.
@@ -335,6 +347,7 @@ mod filters {
             FFIType::RustBuffer => context.ffi_rustbuffer_type(),
             FFIType::RustError => context.ffi_rusterror_type(),
             FFIType::ForeignBytes => context.ffi_foreignbytes_type(),
+            FFIType::Struct(name) => context.ffi_struct_type(name),
             FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
         })
     }
@@ -506,6 +519,31 @@ mod filters {
         })
     }
 
+    /// Lowers a borrowed (`[ByRef]`) string or byte array argument into
+    /// `ForeignBytes` that point at its contents.
+    pub fn lower_borrowed_cpp(
+        type_: &WebIDLType,
+        from: &str,
+        context: &Context<'_, '_>,
+    ) -> Result<String, askama::Error> {
+        let (lifted, from) = match type_ {
+            // Rust wants UTF-8, so we have to convert the string first. The temporary
+            // lives until the end of the full expression, which includes the FFI call.
+            WebIDLType::Flat(Type::String) => (
+                "nsACString".into(),
+                format!("NS_ConvertUTF16toUTF8({})", from),
+            ),
+            _ => (in_arg_type_cpp(type_, context)?, from.into()),
+        };
+        Ok(format!(
+            "{}::ViaFfi<{}, {}>::Lower({})",
+            context.detail_name(),
+            lifted,
+            context.ffi_foreignbytes_type(),
+            from
+        ))
+    }
+
     /// Generates an expression for lowering a C++ type into a C type when
     /// calling an FFI function.
     pub fn lower_cpp(
@@ -528,7 +566,7 @@ mod filters {
             "{}::ViaFfi<{}, {}, {}>::Lower({})",
             context.detail_name(),
             lifted,
-            type_ffi(&FFIType::from(type_), context)?,
+            type_ffi(&type_.ffi_type(context.ci), context)?,
             nullable,
             from
         ))
@@ -557,7 +595,7 @@ mod filters {
             "{}::ViaFfi<{}, {}, {}>::Lift({}, {})",
             context.detail_name(),
             lifted,
-            type_ffi(&FFIType::from(type_), context)?,
+            type_ffi(&type_.ffi_type(context.ci), context)?,
             nullable,
             from,
             into,
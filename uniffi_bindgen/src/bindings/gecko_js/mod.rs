@@ -8,7 +8,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 
 pub mod gen_gecko_js;
 mod webidl;
@@ -16,7 +16,7 @@ pub use gen_gecko_js::{
     Config, Interface, InterfaceHeader, Namespace, NamespaceHeader, SharedHeader, WebIDL,
 };
 
-use super::super::interface::ComponentInterface;
+use super::super::interface::{ComponentInterface, Type};
 
 pub struct Binding {
     name: String,
@@ -71,6 +71,17 @@ pub fn write_bindings(
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Vec<Binding>> {
     use askama::Template;
 
+    if !ci.iter_constant_definitions().is_empty() {
+        bail!("Gecko bindings don't support constants yet");
+    }
+    if ci
+        .iter_types()
+        .iter()
+        .any(|t| matches!(t, Type::External { .. }))
+    {
+        bail!("Gecko bindings don't support external types yet");
+    }
+
     let mut bindings = Vec::new();
 
     let context = gen_gecko_js::Context::new(config, ci);
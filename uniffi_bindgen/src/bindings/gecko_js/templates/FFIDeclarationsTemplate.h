@@ -23,6 +23,17 @@ struct {{ context.ffi_rusterror_type() }} {
   char* mMessage;
 };
 
+// Dictionaries that only contain numbers and Booleans are passed by value.
+{%- for rec in ci.iter_record_definitions() %}
+{%- if rec.is_plain_old_data() %}
+struct {{ context.ffi_struct_type(rec.name()) }} {
+  {%- for field in rec.fields() %}
+  {{ field.type_().ffi_type(ci)|type_ffi(context) }} {{ field.name()|field_name_cpp }};
+  {%- endfor %}
+};
+{%- endif %}
+{%- endfor %}
+
 {% for func in ci.iter_ffi_function_definitions() -%}
 {%- match func.return_type() -%}
 {%- when Some with (type_) %}
@@ -69,6 +69,14 @@ already_AddRefed<{{ obj.name()|class_name_cpp(context) }}> {{ obj.name()|class_n
 MOZ_STATIC_ASSERT(false, "Sorry the gecko-js backend does not yet support alternate constructors");
 {%- endfor %}
 
+{%- for meth in obj.static_methods() %}
+MOZ_STATIC_ASSERT(false, "Sorry the gecko-js backend does not yet support static methods");
+{%- endfor %}
+
+{%- for prop in obj.properties() %}
+MOZ_STATIC_ASSERT(false, "Sorry the gecko-js backend does not yet support properties");
+{%- endfor %}
+
 {%- for meth in obj.methods() %}
 {% match meth.cpp_return_type() %}{% when Some with (type_) %}{{ type_|ret_type_cpp(context) }}{% else %}void{% endmatch %} {{ obj.name()|class_name_cpp(context) }}::{{ meth.name()|fn_name_cpp }}(
   {%- for arg in meth.cpp_arguments() %}
@@ -482,6 +482,31 @@ struct ViaFfi<nsAString, {{ context.ffi_rustbuffer_type() }}, false> {
   }
 };
 
+// Borrowed (`[ByRef]`) strings and byte arrays are lowered into `ForeignBytes`
+// that point directly at their contents, without copying. The lowered value must
+// not outlive the lifted one, so these are only used for FFI call arguments.
+
+template <>
+struct ViaFfi<nsACString, {{ context.ffi_foreignbytes_type() }}, false> {
+  [[nodiscard]] static {{ context.ffi_foreignbytes_type() }} Lower(const nsACString& aLifted) {
+    MOZ_RELEASE_ASSERT(
+        aLifted.Length() <=
+        static_cast<size_t>(std::numeric_limits<int32_t>::max()));
+    return {static_cast<int32_t>(aLifted.Length()),
+            reinterpret_cast<const uint8_t*>(aLifted.BeginReading())};
+  }
+};
+
+template <>
+struct ViaFfi<Sequence<uint8_t>, {{ context.ffi_foreignbytes_type() }}, false> {
+  [[nodiscard]] static {{ context.ffi_foreignbytes_type() }} Lower(const Sequence<uint8_t>& aLifted) {
+    MOZ_RELEASE_ASSERT(
+        aLifted.Length() <=
+        static_cast<size_t>(std::numeric_limits<int32_t>::max()));
+    return {static_cast<int32_t>(aLifted.Length()), aLifted.Elements()};
+  }
+};
+
 template <>
 struct Serializable<nsString> {
   [[nodiscard]] static bool ReadFrom(Reader& aReader, nsString& aValue) {
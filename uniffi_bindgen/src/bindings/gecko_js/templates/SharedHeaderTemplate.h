@@ -89,6 +89,28 @@ struct Serializable<{{ rec.name()|class_name_cpp(context) }}> {
     {%- endfor %}
   }
 };
+
+{% if rec.is_plain_old_data() -%}
+template <>
+struct ViaFfi<{{ rec.name()|class_name_cpp(context) }}, {{ context.ffi_struct_type(rec.name()) }}> {
+  [[nodiscard]] static bool Lift(const {{ context.ffi_struct_type(rec.name()) }}& aLowered, {{ rec.name()|class_name_cpp(context) }}& aLifted) {
+    {%- for field in rec.fields() %}
+    if (!{{ field.webidl_type()|lift_cpp("aLowered.{}"|format(field.name()|field_name_cpp), "aLifted.{}"|format(field.name()|field_name_cpp), context) }}) {
+      return false;
+    }
+    {%- endfor %}
+    return true;
+  }
+
+  [[nodiscard]] static {{ context.ffi_struct_type(rec.name()) }} Lower(const {{ rec.name()|class_name_cpp(context) }}& aLifted) {
+    {{ context.ffi_struct_type(rec.name()) }} lowered;
+    {%- for field in rec.fields() %}
+    lowered.{{ field.name()|field_name_cpp }} = {{ field.webidl_type()|lower_cpp("aLifted.{}"|format(field.name()|field_name_cpp), context) }};
+    {%- endfor %}
+    return lowered;
+  }
+};
+{% endif -%}
 {% endfor %}
 
 }  // namespace {{ context.detail_name() }}
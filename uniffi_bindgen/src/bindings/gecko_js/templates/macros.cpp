@@ -12,7 +12,11 @@
     {%- let args = func.arguments() -%}
     {%- if !args.is_empty() %},{% endif -%}
     {%- for arg in args %}
-    {{ arg.webidl_type()|lower_cpp(arg.name(), context) }}{%- if !loop.last %},{% endif -%}
+    {% if arg.is_borrowed_bytes() -%}
+    {{ arg.webidl_type()|lower_borrowed_cpp(arg.name(), context) }}
+    {%- else -%}
+    {{ arg.webidl_type()|lower_cpp(arg.name(), context) }}
+    {%- endif %}{%- if !loop.last %},{% endif -%}
     {%- endfor %}
     , &err
   );
@@ -27,7 +31,11 @@
   {% match func.ffi_func().return_type() %}{% when Some with (type_) %}const {{ type_|type_ffi(context) }} {{ result }} ={% else %}{% endmatch %}{{ func.ffi_func().name() }}(
     {%- let args = func.arguments() -%}
     {%- for arg in args %}
-    {{ arg.webidl_type()|lower_cpp(arg.name(), context) }}{%- if !loop.last %},{% endif -%}
+    {% if arg.is_borrowed_bytes() -%}
+    {{ arg.webidl_type()|lower_borrowed_cpp(arg.name(), context) }}
+    {%- else -%}
+    {{ arg.webidl_type()|lower_cpp(arg.name(), context) }}
+    {%- endif %}{%- if !loop.last %},{% endif -%}
     {%- endfor %}
     {% if !args.is_empty() %}, {% endif %}&{{ error }}
   );
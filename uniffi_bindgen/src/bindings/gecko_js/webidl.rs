@@ -31,7 +31,9 @@
 //! UniFFI interface types. Capturing this logic here lets us keep our templates
 //! and filters clean.
 
-use crate::interface::{Argument, Constructor, FFIType, Field, Function, Literal, Method, Type};
+use crate::interface::{
+    Argument, ComponentInterface, Constructor, FFIType, Field, Function, Literal, Method, Type,
+};
 
 /// WebIDL types correspond to UniFFI interface types, but carry additional
 /// information for compound types.
@@ -78,6 +80,20 @@ impl WebIDLType {
         }
     }
 
+    /// Returns the low-level FFI type used to pass this WebIDL type. Like
+    /// `Type::ffi_type`, this needs the component interface to tell whether
+    /// a dictionary is passed as a C struct or serialized into a buffer.
+    pub fn ffi_type(&self, ci: &ComponentInterface) -> FFIType {
+        match self {
+            WebIDLType::Flat(inner) => inner.ffi_type(ci),
+            WebIDLType::Optional(_)
+            | WebIDLType::OptionalWithDefaultValue(_)
+            | WebIDLType::Nullable(_)
+            | WebIDLType::Sequence(_)
+            | WebIDLType::Map(_) => FFIType::RustBuffer,
+        }
+    }
+
     pub fn is_optional_record(&self) -> bool {
         match self {
             WebIDLType::OptionalWithDefaultValue(inner) => match inner.as_ref() {
@@ -113,6 +129,18 @@ impl From<Type> for WebIDLType {
                 panic!("[TODO: From<Type>({:?})]", type_)
             }
             Type::CallbackInterface(_) => panic!("Callback interfaces unimplemented"),
+            // Byte arrays have the same serialized form as a sequence of bytes,
+            // so we expose them to JS as a `sequence<octet>` for now.
+            Type::Bytes => WebIDLType::Sequence(Box::new(WebIDLType::Flat(Type::UInt8))),
+            Type::Union(..) => panic!("Union types unimplemented"),
+            Type::Tuple { .. } => panic!("Tuple types unimplemented"),
+            Type::Timestamp | Type::Duration => {
+                panic!("Timestamps and durations unimplemented")
+            }
+            // Custom types have the same representation as the builtin type that they wrap,
+            // and Gecko doesn't support converting them into anything richer, yet.
+            Type::Custom { builtin, .. } => (*builtin).into(),
+            Type::External { .. } => panic!("External types unimplemented"),
             Type::Optional(inner) => match *inner {
                 Type::Record(name) => {
                     WebIDLType::OptionalWithDefaultValue(Box::new(Type::Record(name).into()))
@@ -120,20 +148,10 @@ impl From<Type> for WebIDLType {
                 inner => WebIDLType::Nullable(Box::new(inner.into())),
             },
             Type::Sequence(inner) => WebIDLType::Sequence(Box::new((*inner).into())),
-            Type::Map(inner) => WebIDLType::Map(Box::new((*inner).into())),
-        }
-    }
-}
-
-impl From<&WebIDLType> for FFIType {
-    fn from(type_: &WebIDLType) -> FFIType {
-        match type_ {
-            WebIDLType::Flat(inner) => inner.into(),
-            WebIDLType::Optional(_)
-            | WebIDLType::OptionalWithDefaultValue(_)
-            | WebIDLType::Nullable(_)
-            | WebIDLType::Sequence(_)
-            | WebIDLType::Map(_) => FFIType::RustBuffer,
+            Type::Map(key, value) => match *key {
+                Type::String => WebIDLType::Map(Box::new((*value).into())),
+                _ => panic!("WebIDL records only support string keys, not {:?}", key),
+            },
         }
     }
 }
@@ -2,11 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use askama::Template;
 use heck::{CamelCase, MixedCase, ShoutySnakeCase};
 use serde::{Deserialize, Serialize};
 
+use crate::bindings::CustomTypeConfig;
 use crate::interface::*;
 use crate::MergeWith;
 
@@ -16,6 +19,11 @@ use crate::MergeWith;
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Config {
     package_name: Option<String>,
+    cdylib_name: Option<String>,
+    #[serde(default)]
+    custom_types: HashMap<String, CustomTypeConfig>,
+    #[serde(default)]
+    external_packages: HashMap<String, String>,
 }
 
 impl Config {
@@ -30,12 +38,48 @@ impl Config {
             Config::default_package_name()
         }
     }
+
+    pub fn cdylib_name(&self) -> String {
+        match self.cdylib_name.as_ref() {
+            Some(name) => name.clone(),
+            None => "uniffi".into(),
+        }
+    }
+
+    /// How to expose a custom type from the UDL, defaulting to the builtin type that it wraps.
+    pub fn custom_type(&self, name: &str) -> CustomTypeConfig {
+        self.custom_types.get(name).cloned().unwrap_or_default()
+    }
+
+    /// The imports needed by all the custom types, sorted and without duplicates.
+    pub fn custom_type_imports(&self) -> Vec<String> {
+        let mut imports: Vec<String> = self
+            .custom_types
+            .values()
+            .flat_map(|c| c.imports().iter().cloned())
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+
+    /// The package containing the bindings for an external type's crate, which defaults
+    /// to the package that those bindings would use if the crate's namespace matched its name.
+    pub fn external_package(&self, crate_name: &str) -> String {
+        match self.external_packages.get(crate_name) {
+            Some(package_name) => package_name.clone(),
+            None => format!("uniffi.{}", crate_name.replace('-', "_")),
+        }
+    }
 }
 
 impl From<&ComponentInterface> for Config {
     fn from(ci: &ComponentInterface) -> Self {
         Config {
             package_name: Some(format!("uniffi.{}", ci.namespace())),
+            cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            custom_types: HashMap::new(),
+            external_packages: HashMap::new(),
         }
     }
 }
@@ -44,6 +88,9 @@ impl MergeWith for Config {
     fn merge_with(&self, other: &Self) -> Self {
         Config {
             package_name: self.package_name.merge_with(&other.package_name),
+            cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            external_packages: self.external_packages.merge_with(&other.external_packages),
         }
     }
 }
@@ -81,14 +128,23 @@ mod filters {
             // These types need conversion, and special handling for lifting/lowering.
             Type::Boolean => "Boolean".to_string(),
             Type::String => "String".to_string(),
+            Type::Bytes => "ByteArray".to_string(),
+            Type::Timestamp => "java.time.Instant".to_string(),
+            Type::Duration => "java.time.Duration".to_string(),
             Type::Enum(name)
             | Type::Record(name)
             | Type::Object(name)
             | Type::Error(name)
-            | Type::CallbackInterface(name) => class_name_kt(name)?,
+            | Type::CallbackInterface(name)
+            | Type::Custom { name, .. }
+            | Type::External { name, .. } => class_name_kt(name)?,
             Type::Optional(t) => format!("{}?", type_kt(t)?),
             Type::Sequence(t) => format!("List<{}>", type_kt(t)?),
-            Type::Map(t) => format!("Map<String, {}>", type_kt(t)?),
+            Type::Union(..) => class_name_kt(&type_.union_name())?,
+            // Tuples are named after their typedef, which is either a typealias for one of
+            // Kotlin's builtin pairs and triples, or a data class for larger tuples.
+            Type::Tuple { name, .. } => class_name_kt(name)?,
+            Type::Map(k, v) => format!("Map<{}, {}>", type_kt(k)?, type_kt(v)?),
         })
     }
 
@@ -108,10 +164,31 @@ mod filters {
             FFIType::RustBuffer => "RustBuffer.ByValue".to_string(),
             FFIType::RustError => "RustError".to_string(),
             FFIType::ForeignBytes => "ForeignBytes.ByValue".to_string(),
+            FFIType::Struct(name) => format!("{}FfiStruct.ByValue", class_name_kt(name)?),
             FFIType::ForeignCallback => "ForeignCallback".to_string(),
         })
     }
 
+    /// Quote a string as a Kotlin string literal, escaping the characters that Kotlin would
+    /// otherwise interpret, including the `$` that starts a string template.
+    fn string_literal_kt(s: &str) -> String {
+        let mut out = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '$' => out.push_str("\\$"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
     pub fn literal_kt(literal: &Literal) -> Result<String, askama::Error> {
         fn typed_number(type_: &Type, num_str: String) -> Result<String, askama::Error> {
             Ok(match type_ {
@@ -130,7 +207,7 @@ mod filters {
 
         Ok(match literal {
             Literal::Boolean(v) => format!("{}", v),
-            Literal::String(s) => format!("\"{}\"", s),
+            Literal::String(s) => string_literal_kt(s),
             Literal::Null => "null".into(),
             Literal::EmptySequence => "listOf()".into(),
             Literal::EmptyMap => "mapOf".into(),
@@ -170,6 +247,35 @@ mod filters {
         Ok(nm.to_string().to_mixed_case())
     }
 
+    /// Get the idiomatic Kotlin rendering of a constant name.
+    pub fn const_name_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
+    }
+
+    /// Get the name of an element of a tuple, matching the properties of Kotlin's `Pair` and `Triple`.
+    pub fn tuple_element_kt(index: &usize) -> Result<String, askama::Error> {
+        const NAMES: [&str; 6] = ["first", "second", "third", "fourth", "fifth", "sixth"];
+        Ok(NAMES[*index].into())
+    }
+
+    /// Render a doc comment from the UDL as KDoc, followed by a newline and the given
+    /// amount of indentation so that the documented declaration lines up beneath it.
+    pub fn doc_comment_kt(docs: &Option<&str>, spaces: &usize) -> Result<String, askama::Error> {
+        Ok(match docs {
+            None => "".into(),
+            Some(docs) => {
+                let indent = " ".repeat(*spaces);
+                let mut out = "/**\n".to_string();
+                for line in docs.lines() {
+                    out.push_str(format!("{} * {}", indent, line.replace("*/", "* /")).trim_end());
+                    out.push('\n');
+                }
+                out.push_str(&format!("{} */\n{}", indent, indent));
+                out
+            }
+        })
+    }
+
     /// Get the idiomatic Kotlin rendering of an individual enum variant.
     pub fn enum_variant_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
         Ok(nm.to_string().to_shouty_snake_case())
@@ -187,13 +293,39 @@ mod filters {
                 class_name_kt(&type_.canonical_name())?,
                 nm,
             ),
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Custom { .. }
+            | Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => {
                 format!("lower{}({})", class_name_kt(&type_.canonical_name())?, nm,)
             }
             _ => format!("{}.lower()", nm),
         })
     }
 
+    /// Get a Kotlin expression for lowering a borrowed string or byte array into `ForeignBytes`.
+    pub fn lower_borrowed_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        let nm = var_name_kt(nm)?;
+        Ok(match type_ {
+            Type::String => format!("ForeignBytes.fromString({})", nm),
+            Type::Bytes => format!("ForeignBytes.fromBytes({})", nm),
+            Type::Sequence(_) => format!(
+                "ForeignBytes.fromBytes({}.toUByteArray().toByteArray())",
+                nm
+            ),
+            _ => unreachable!(
+                "Only strings and byte arrays can be borrowed, not {:?}",
+                type_
+            ),
+        })
+    }
+
     /// Get a Kotlin expression for writing a value into a byte buffer.
     ///
     /// Where possible, this delegates to a `write()` method on the type itself, but special
@@ -211,7 +343,16 @@ mod filters {
                 nm,
                 target,
             ),
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => format!(
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Custom { .. }
+            | Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => format!(
                 "write{}({}, {})",
                 class_name_kt(&type_.canonical_name())?,
                 nm,
@@ -233,7 +374,16 @@ mod filters {
                 class_name_kt(&type_.canonical_name())?,
                 nm,
             ),
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Custom { .. }
+            | Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => {
                 format!("lift{}({})", class_name_kt(&type_.canonical_name())?, nm,)
             }
             _ => format!("{}.lift({})", type_kt(type_)?, nm),
@@ -252,7 +402,16 @@ mod filters {
                 class_name_kt(&type_.canonical_name())?,
                 nm,
             ),
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Custom { .. }
+            | Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => {
                 format!("read{}({})", class_name_kt(&type_.canonical_name())?, nm,)
             }
             _ => format!("{}.read({})", type_kt(type_)?, nm),
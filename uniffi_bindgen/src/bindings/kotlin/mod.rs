@@ -5,7 +5,7 @@
 use anyhow::{bail, Context, Result};
 use std::{
     env,
-    ffi::OsString,
+    ffi::{OsStr, OsString},
     fs::File,
     io::Write,
     path::{Path, PathBuf},
@@ -15,7 +15,7 @@ use std::{
 pub mod gen_kotlin;
 pub use gen_kotlin::{Config, KotlinWrapper};
 
-use super::super::interface::ComponentInterface;
+use super::super::interface::{ComponentInterface, Type};
 
 pub fn write_bindings(
     config: &Config,
@@ -65,10 +65,39 @@ pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path
     kt_file.push(format!("{}.kt", ci.namespace()));
     let mut jar_file = PathBuf::from(out_dir);
     jar_file.push(format!("{}.jar", ci.namespace()));
-    let status = Command::new("kotlinc")
-        .arg("-Xopt-in=kotlin.ExperimentalUnsignedTypes")
+    let mut cmd = Command::new("kotlinc");
+    cmd.arg("-Xopt-in=kotlin.ExperimentalUnsignedTypes");
+    let mut classpath = env::var_os("CLASSPATH").unwrap_or_else(|| OsString::from(""));
+    // External types are read and written using the `internal` helpers from the bindings of the
+    // component that defines them, so we let the compiler see the jars that have already been
+    // compiled for other components, and treat them as part of the same module.
+    if ci
+        .iter_types()
+        .iter()
+        .any(|t| matches!(t, Type::External { .. }))
+    {
+        let mut friend_paths = OsString::from("-Xfriend-paths=");
+        for entry in PathBuf::from(out_dir)
+            .read_dir()
+            .context("Failed to list target directory when compiling the bindings")?
+        {
+            let path = entry
+                .context("Directory listing failed while compiling the bindings")?
+                .path();
+            if path.extension() == Some(OsStr::new("jar")) && path != jar_file {
+                classpath.push(":");
+                classpath.push(&path);
+                if friend_paths.len() > "-Xfriend-paths=".len() {
+                    friend_paths.push(",");
+                }
+                friend_paths.push(&path);
+            }
+        }
+        cmd.arg(friend_paths);
+    }
+    let status = cmd
         .arg("-classpath")
-        .arg(env::var("CLASSPATH").unwrap_or_else(|_| "".to_string()))
+        .arg(classpath)
         .arg(&kt_file)
         .arg("-d")
         .arg(jar_file)
@@ -82,6 +111,11 @@ pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path
     Ok(())
 }
 
+/// Kotlin code that fails the script if any objects or buffers from the component have leaked.
+pub fn leak_check(config: &Config, _ci: &ComponentInterface) -> String {
+    format!("{}.assertNoLeaks()\n", config.package_name())
+}
+
 /// Execute the specifed kotlin script, with classpath based on the generated
 // artifacts in the given output directory.
 pub fn run_script(out_dir: &Path, script_file: &Path) -> Result<()> {
@@ -1,4 +1,4 @@
-enum class {{ e.name()|class_name_kt }} {
+{{ e.docs()|doc_comment_kt(0) }}enum class {{ e.name()|class_name_kt }} {
     {% for variant in e.variants() %}
     {{ variant|enum_variant_kt }}{% if loop.last %};{% else %},{% endif %}
     {% endfor %}
@@ -9,6 +9,10 @@
 
 interface RustErrorReference : Structure.ByReference {
     fun isFailure(): Boolean
+    fun isPanic(): Boolean
+    fun isWrongThread(): Boolean
+    fun isInvalidArgument(): Boolean
+    fun isReentrantCall(): Boolean
     fun<E: Exception> intoException(): E
     fun ensureConsumed()
     fun getMessage(): String?
@@ -36,6 +40,34 @@ internal open class RustError : Structure() {
         return code != 0
     }
 
+    /**
+     * Does this represent a Rust panic?
+     */
+    fun isPanic(): Boolean {
+        return code == -1
+    }
+
+    /**
+     * Does this represent calling a thread-confined object on the wrong thread?
+     */
+    fun isWrongThread(): Boolean {
+        return code == -2
+    }
+
+    /**
+     * Does this represent an argument that Rust couldn't lift?
+     */
+    fun isInvalidArgument(): Boolean {
+        return code == -3
+    }
+
+    /**
+     * Does this represent calling a thread-confined object that's already in use on this thread?
+     */
+    fun isReentrantCall(): Boolean {
+        return code == -4
+    }
+
     @Synchronized
     fun ensureConsumed() {
         if (this.message != null) {
@@ -95,7 +127,72 @@ internal open class InternalError : RustError() {
     }
 }
 
-class InternalException(message: String) : Exception(message)
+open class InternalException(message: String) : Exception(message)
+
+/**
+ * Thrown when Rust code panics.
+ *
+ * As well as the panic message, this has the location of the panic in the Rust code, the name of
+ * the thread that panicked, and a backtrace if the Rust code was built with the `panic-backtraces`
+ * feature of the `uniffi` crate. Those are null if they're unknown.
+ */
+class RustPanic(
+    override val message: String,
+    val file: String? = null,
+    val line: Int? = null,
+    val column: Int? = null,
+    val threadName: String? = null,
+    val backtrace: String? = null
+) : InternalException(message) {
+    override fun toString(): String =
+        if (file == null) {
+            "RustPanic: $message"
+        } else {
+            "RustPanic: $message at $file:$line:$column"
+        }
+
+    internal companion object {
+        // The panic hook records a structured report of the panic, which we take
+        // so that it doesn't get mixed up with the next one.
+        fun take(message: String): RustPanic =
+            rustCall(InternalError.ByReference()) { err ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_take_panic_report().name() }}(err)
+            }.let { rbuf ->
+                liftFromRustBuffer(rbuf) { buf ->
+                    if (!buf.hasRemaining()) {
+                        RustPanic(message)
+                    } else {
+                        RustPanic(
+                            message = String.read(buf),
+                            file = String.read(buf),
+                            line = Int.read(buf),
+                            column = Int.read(buf),
+                            threadName = String.read(buf).ifEmpty { null },
+                            backtrace = String.read(buf).ifEmpty { null }
+                        )
+                    }
+                }
+            }
+    }
+}
+
+/**
+ * Thrown when calling a method of a thread-confined object on a thread other than the one
+ * that created it.
+ */
+class WrongThreadException(message: String) : IllegalStateException(message)
+
+/**
+ * Thrown when calling a method of a thread-confined object while it's already in use on the
+ * same thread, for example from a callback that it's making.
+ */
+class ReentrantCallException(message: String) : IllegalStateException(message)
+
+/**
+ * Thrown when Rust couldn't lift one of the arguments that it was passed, which means that
+ * these bindings have a bug or are out of date.
+ */
+class InvalidArgumentException(message: String) : IllegalArgumentException(message)
 
 {%- for e in ci.iter_error_definitions() %}
 internal open class {{e.name()}} : RustError() {
@@ -118,7 +215,7 @@ internal open class {{e.name()}} : RustError() {
     }
 }
 
-open class {{e.name()}}Exception(message: String) : Exception(message) {
+{{ e.docs()|doc_comment_kt(0) }}open class {{e.name()}}Exception(message: String) : Exception(message) {
     {% for value in e.values() -%}
     class {{value}}(msg: String) : {{e.name()}}Exception(msg)
     {% endfor %}
@@ -132,6 +229,19 @@ open class {{e.name()}}Exception(message: String) : Exception(message) {
 private inline fun <U, E: RustErrorReference> nullableRustCall(callback: (E) -> U?, err: E): U? {
     try {
         val ret = callback(err)
+        // These are the same for every call, whatever errors it's declared to throw.
+        if (err.isPanic()) {
+            throw RustPanic.take(err.consumeErrorMessage())
+        }
+        if (err.isWrongThread()) {
+            throw WrongThreadException(err.consumeErrorMessage())
+        }
+        if (err.isInvalidArgument()) {
+            throw InvalidArgumentException(err.consumeErrorMessage())
+        }
+        if (err.isReentrantCall()) {
+            throw ReentrantCallException(err.consumeErrorMessage())
+        }
         if (err.isFailure()) {
             throw err.intoException()
         }
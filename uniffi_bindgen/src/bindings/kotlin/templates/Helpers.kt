@@ -2,20 +2,90 @@
 // This would be a good candidate for isolating in its own ffi-support lib.
 
 abstract class FFIObject(
-    private val handle: AtomicLong
-) {
+    handle: Long,
+    freeHandle: (Long) -> Unit
+) : AutoCloseable {
+    // Keeps the Rust object alive until this object is destroyed and any calls in progress
+    // have returned. The cleaner shares this, so it must not refer to this object.
+    private val calls = FFIObjectCalls(handle, freeHandle)
+
+    // Frees the Rust object if this object is garbage-collected without being destroyed first.
+    private val cleanable = FFIObjectCleaner.register(this, calls)
+
+    /**
+     * Disconnect the object from the underlying Rust object.
+     *
+     * It can be called more than once, and from any thread. Once called, interacting with the
+     * object causes an `IllegalStateException`, but any calls that are already in progress on
+     * other threads carry on, and the Rust object is freed when the last of them returns.
+     *
+     * The Rust object is freed when this object is garbage-collected if this method was never
+     * called, but that might not happen for a long time, so clients should call this method
+     * (or use the object in a `use {}` block) once they're done with it.
+     */
     open fun destroy() {
-        this.handle.set(0L)
+        cleanable.clean()
+    }
+
+    override fun close() {
+        destroy()
     }
 
-    internal inline fun <R> callWithHandle(block: (handle: Long) -> R) =
-        this.handle.get().let { handle -> 
-            if (handle != 0L) {
-                block(handle)
-            } else {
-                throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+    // The call keeps the Rust object alive until it returns, even if this object becomes
+    // unreachable and is cleaned in the meantime. That's what `Reference.reachabilityFence(this)`
+    // would do for us, but it isn't available on older versions of Android.
+    internal inline fun <R> callWithHandle(block: (handle: Long) -> R): R {
+        val handle = calls.enter()
+            ?: throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+        try {
+            return block(handle)
+        } finally {
+            calls.exit()
+        }
+    }
+}
+
+// Counts the references to the Rust object behind an `FFIObject`: one for the object itself
+// until it's destroyed, plus one for each call in progress. The last one to go frees it.
+internal class FFIObjectCalls(
+    private val handle: Long,
+    private val freeHandle: (Long) -> Unit
+) {
+    private val destroyed = java.util.concurrent.atomic.AtomicBoolean(false)
+    private val count = AtomicLong(1L)
+
+    // Take a reference for a call, returning the handle to use,
+    // or `null` if the object has been destroyed.
+    fun enter(): Long? {
+        if (destroyed.get()) {
+            return null
+        }
+        // The count can only be zero once the object has been destroyed and freed,
+        // in which case we mustn't bring it back.
+        while (true) {
+            val count = this.count.get()
+            if (count == 0L) {
+                return null
             }
+            if (this.count.compareAndSet(count, count + 1L)) {
+                return handle
+            }
+        }
+    }
+
+    fun exit() {
+        if (count.decrementAndGet() == 0L) {
+            freeHandle(handle)
+        }
+    }
+
+    // Give up the object's own reference. This does so at most once, however many times it's
+    // called, and from whichever thread.
+    fun release() {
+        if (destroyed.compareAndSet(false, true)) {
+            exit()
         }
+    }
 }
 
 inline fun <T : FFIObject, R> T.use(block: (T) -> R) =
@@ -29,6 +99,44 @@ inline fun <T : FFIObject, R> T.use(block: (T) -> R) =
         }
     }
 
+// Frees the Rust objects behind any `FFIObject`s that are garbage-collected without being destroyed.
+// This is a simplified `java.lang.ref.Cleaner`, which isn't available on older versions of Android.
+internal object FFIObjectCleaner {
+    private val queue = java.lang.ref.ReferenceQueue<FFIObject>()
+    // A phantom reference is only enqueued if it's still reachable when its object is collected.
+    private val cleanables = java.util.Collections.newSetFromMap(
+        java.util.concurrent.ConcurrentHashMap<Cleanable, Boolean>()
+    )
+
+    init {
+        kotlin.concurrent.thread(isDaemon = true, name = "uniffi-object-cleaner") {
+            while (true) {
+                try {
+                    (queue.remove() as Cleanable).clean()
+                } catch (e: Throwable) {
+                    // There's nobody to report this error to, so just keep cleaning.
+                }
+            }
+        }
+    }
+
+    // The `calls` must not refer to `obj`, or it would never be collected.
+    fun register(obj: FFIObject, calls: FFIObjectCalls) =
+        Cleanable(obj, calls).also { cleanables.add(it) }
+
+    class Cleanable(
+        obj: FFIObject,
+        private val calls: FFIObjectCalls
+    ) : java.lang.ref.PhantomReference<FFIObject>(obj, queue) {
+        // Give up the object's reference to the Rust object, which frees it unless a call is
+        // still in progress, in which case that call frees it when it returns.
+        fun clean() {
+            cleanables.remove(this)
+            calls.release()
+        }
+    }
+}
+
 internal typealias Handle = Long
 internal class ConcurrentHandleMap<T>(
     private val leftMap: MutableMap<Handle, T> = mutableMapOf(),
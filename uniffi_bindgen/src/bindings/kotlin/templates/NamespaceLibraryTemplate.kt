@@ -4,7 +4,7 @@ fun findLibraryName(componentName: String): String {
     if (libOverride != null) {
         return libOverride
     }
-    return "uniffi_${componentName}"
+    return "{{ config.cdylib_name() }}"
 }
 
 inline fun <reified Lib : Library> loadIndirect(
@@ -20,6 +20,12 @@ internal interface _UniFFILib : Library {
     companion object {
         internal val INSTANCE: _UniFFILib by lazy { 
             loadIndirect<_UniFFILib>(componentName = "{{ ci.namespace() }}")
+            .also { lib: _UniFFILib ->
+                // Record structured reports of Rust panics, for the `RustPanic` exception.
+                rustCall(InternalError.ByReference()) { err ->
+                    lib.{{ ci.ffi_install_panic_hook().name() }}(err)
+                }
+            }
             {% let callback_interfaces = ci.iter_callback_interface_definitions() %}
             {%- if !callback_interfaces.is_empty() -%}
             .also { lib: _UniFFILib ->
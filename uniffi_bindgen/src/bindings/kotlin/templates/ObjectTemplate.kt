@@ -1,6 +1,9 @@
 public interface {{ obj.name()|class_name_kt }}Interface {
+    {% for prop in obj.properties() -%}
+    {{ prop.docs()|doc_comment_kt(4) }}{% if prop.readonly() %}val{% else %}var{% endif %} {{ prop.name()|var_name_kt }}: {{ prop.type_()|type_kt }}
+    {% endfor -%}
     {% for meth in obj.methods() -%}
-    fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %})
+    {{ meth.docs()|doc_comment_kt(4) }}fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %}: {{ return_type|type_kt -}}
     {%- else -%}
@@ -8,9 +11,13 @@ public interface {{ obj.name()|class_name_kt }}Interface {
     {% endfor %}
 }
 
-class {{ obj.name()|class_name_kt }}(
+{{ obj.class_docs().as_deref()|doc_comment_kt(0) }}class {{ obj.name()|class_name_kt }}(
     handle: Long
-) : FFIObject(AtomicLong(handle)), {{ obj.name()|class_name_kt }}Interface {
+) : FFIObject(handle, {
+    rustCall(InternalError.ByReference()) { err ->
+        _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(it, err)
+    }
+}), {{ obj.name()|class_name_kt }}Interface {
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
@@ -19,26 +26,71 @@ class {{ obj.name()|class_name_kt }}(
     {%- when None %}
     {%- endmatch %}
 
-    /**
-     * Disconnect the object from the underlying Rust object.
-     * 
-     * It can be called more than once, but once called, interacting with the object 
-     * causes an `IllegalStateException`.
-     * 
-     * Clients **must** call this method once done with the object, or cause a memory leak.
-     */
-    override fun destroy() {
-        try {
-            callWithHandle {
-                super.destroy() // poison the handle so no-one else can use it before we tell rust.
+    {%- match obj.ffi_object_to_string() %}
+    {%- when Some with (func) %}
+
+    override fun toString(): String =
+        callWithHandle {
+            rustCall(InternalError.ByReference()) { err ->
+                _UniFFILib.INSTANCE.{{ func.name() }}(it, err)
+            }
+        }.let {
+            {{ "it"|lift_kt(Type::String) }}
+        }
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_eq() %}
+    {%- when Some with (func) %}
+
+    override fun equals(other: Any?): Boolean {
+        if (other !is {{ obj.name()|class_name_kt }}) {
+            return false
+        }
+        return callWithHandle { handle ->
+            other.callWithHandle { otherHandle ->
                 rustCall(InternalError.ByReference()) { err ->
-                    _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(it, err)
+                    _UniFFILib.INSTANCE.{{ func.name() }}(handle, otherHandle, err)
                 }
             }
-        } catch (e: IllegalStateException) {
-            // The user called this more than once. Better than less than once.
+        }.let {
+            {{ "it"|lift_kt(Type::Boolean) }}
         }
     }
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_hash() %}
+    {%- when Some with (func) %}
+
+    override fun hashCode(): Int =
+        callWithHandle {
+            rustCall(InternalError.ByReference()) { err ->
+                _UniFFILib.INSTANCE.{{ func.name() }}(it, err)
+            }
+        }.hashCode()
+    {%- when None %}
+    {%- endmatch %}
+
+    {% for prop in obj.properties() -%}
+    override {% if prop.readonly() %}val{% else %}var{% endif %} {{ prop.name()|var_name_kt }}: {{ prop.type_()|type_kt }}
+        get() =
+            callWithHandle {
+                {%- call kt::to_ffi_call_with_prefix("it", prop.getter()) %}
+            }.let {
+                {{ "it"|lift_kt(prop.type_()) }}
+            }
+        {%- match prop.setter() %}
+        {%- when Some with (setter) %}
+        set(value) {
+            callWithHandle {
+                {%- call kt::to_ffi_call_with_prefix("it", setter) %}
+            }
+        }
+        {%- when None %}
+        {%- endmatch %}
+
+    {% endfor %}
 
     {% for meth in obj.methods() -%}
     {%- match meth.return_type() -%}
@@ -59,12 +111,27 @@ class {{ obj.name()|class_name_kt }}(
     {% endmatch %}
     {% endfor %}
 
-    {% if obj.constructors().len() > 1 -%}
+    {% if obj.constructors().len() > 1 || !obj.static_methods().is_empty() -%}
     companion object {
         {% for cons in obj.alternate_constructors() -%}
         fun {{ cons.name()|fn_name_kt }}({% call kt::arg_list_decl(cons) %}): {{ obj.name()|class_name_kt }} =
             {{ obj.name()|class_name_kt }}({% call kt::to_ffi_call(cons) %})
         {% endfor %}
+
+        {% for meth in obj.static_methods() -%}
+        {%- match meth.return_type() -%}
+
+        {%- when Some with (return_type) -%}
+        {{ meth.docs()|doc_comment_kt(8) }}fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}): {{ return_type|type_kt }} {
+            val _retval = {% call kt::to_ffi_call(meth) %}
+            return {{ "_retval"|lift_kt(return_type) }}
+        }
+
+        {%- when None -%}
+        {{ meth.docs()|doc_comment_kt(8) }}fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}) =
+            {% call kt::to_ffi_call(meth) %}
+        {% endmatch %}
+        {% endfor %}
     }
     {%- endif %}
 }
@@ -1,6 +1,7 @@
-data class {{ rec.name()|class_name_kt }} (
+{%- let class_name = rec.name()|class_name_kt %}
+{{ rec.docs()|doc_comment_kt(0) }}data class {{ class_name }} (
     {%- for field in rec.fields() %}
-    val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt -}}
+    {{ field.docs()|doc_comment_kt(4) }}val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt -}}
     {%- match field.default_value() %}
         {%- when Some with(literal) %} = {{ literal|literal_kt }}
         {%- else %}
@@ -9,13 +10,23 @@ data class {{ rec.name()|class_name_kt }} (
     {%- endfor %}
 ) {
     companion object {
+        {%- if rec.is_plain_old_data() %}
+        internal fun lift(v: {{ class_name }}FfiStruct.ByValue): {{ class_name }} {
+            return {{ class_name }}(
+            {%- for field in rec.fields() %}
+            {{ "v.{}"|format(field.name()|var_name_kt)|lift_kt(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+            )
+        }
+        {%- else %}
         // XXX TODO: put this in a superclass maybe?
-        internal fun lift(rbuf: RustBuffer.ByValue): {{ rec.name()|class_name_kt }} {
-            return liftFromRustBuffer(rbuf) { buf -> {{ rec.name()|class_name_kt }}.read(buf) }
+        internal fun lift(rbuf: RustBuffer.ByValue): {{ class_name }} {
+            return liftFromRustBuffer(rbuf) { buf -> {{ class_name }}.read(buf) }
         }
+        {%- endif %}
 
-        internal fun read(buf: ByteBuffer): {{ rec.name()|class_name_kt }} {
-            return {{ rec.name()|class_name_kt }}(
+        internal fun read(buf: ByteBuffer): {{ class_name }} {
+            return {{ class_name }}(
             {%- for field in rec.fields() %}
             {{ "buf"|read_kt(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
             {%- endfor %}
@@ -23,13 +34,40 @@ data class {{ rec.name()|class_name_kt }} (
         }
     }
 
+    {%- if rec.is_plain_old_data() %}
+
+    internal fun lower(): {{ class_name }}FfiStruct.ByValue {
+        val v = {{ class_name }}FfiStruct.ByValue()
+        {%- for field in rec.fields() %}
+        v.{{ field.name()|var_name_kt }} = {{ field.name()|lower_kt(field.type_()) }}
+        {%- endfor %}
+        return v
+    }
+    {%- else %}
+
     internal fun lower(): RustBuffer.ByValue {
         return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
     }
+    {%- endif %}
 
     internal fun write(buf: RustBufferBuilder) {
         {%- for field in rec.fields() %}
             {{ "(this.{})"|format(field.name())|write_kt("buf", field.type_()) }}
         {%- endfor %}
     }
-}
\ No newline at end of file
+}
+{%- if rec.is_plain_old_data() %}
+
+// The C-style struct used to pass {{ class_name }} across the FFI by value.
+// It has the same fields as the record, in their low-level FFI representation.
+
+@Structure.FieldOrder({% for field in rec.fields() %}"{{ field.name()|var_name_kt }}"{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %})
+open class {{ class_name }}FfiStruct : Structure() {
+    {%- for field in rec.fields() %}
+    {%- let ffi_type = field.type_().ffi_type(ci)|type_ffi %}
+    @JvmField var {{ field.name()|var_name_kt }}: {{ ffi_type }} = 0.to{{ ffi_type }}()
+    {%- endfor %}
+
+    class ByValue : {{ class_name }}FfiStruct(), Structure.ByValue
+}
+{%- endif %}
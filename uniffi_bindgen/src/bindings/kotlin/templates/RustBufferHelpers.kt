@@ -282,6 +282,175 @@ internal fun String.write(buf: RustBufferBuilder) {
     buf.put(byteArr)
 }
 
+{% when Type::Bytes -%}
+
+// Helper functions for pasing values of type {{ typ|type_kt }}.
+// There's no `ByteArray.Companion` to hang them off, so they're named like the compound types.
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): ByteArray {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): ByteArray {
+    val len = buf.getInt()
+    val byteArr = ByteArray(len)
+    buf.get(byteArr)
+    return byteArr
+}
+
+internal fun lower{{ canonical_type_name }}(v: ByteArray): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: ByteArray, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    buf.put(v)
+}
+
+{% when Type::Timestamp -%}
+
+// Helper functions for pasing values of type {{ typ|type_kt }}.
+// Timestamps are serialized as whole seconds since the epoch followed by nanoseconds,
+// which happens to be exactly how `Instant` represents them.
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): java.time.Instant {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): java.time.Instant {
+    val seconds = buf.getLong()
+    val nanos = buf.getInt()
+    if (nanos < 0 || nanos >= 1_000_000_000) {
+        throw RuntimeException("Invalid nanoseconds in timestamp")
+    }
+    return java.time.Instant.ofEpochSecond(seconds, nanos.toLong())
+}
+
+internal fun lower{{ canonical_type_name }}(v: java.time.Instant): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: java.time.Instant, buf: RustBufferBuilder) {
+    buf.putLong(v.epochSecond)
+    buf.putInt(v.nano)
+}
+
+{% when Type::Duration -%}
+
+// Helper functions for pasing values of type {{ typ|type_kt }}.
+// Durations are serialized as whole seconds followed by nanoseconds, and can't be negative.
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): java.time.Duration {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): java.time.Duration {
+    // The seconds are unsigned, but a `Duration` can't hold more than `Long.MAX_VALUE` of them anyway.
+    val seconds = buf.getLong()
+    val nanos = buf.getInt()
+    if (seconds < 0 || nanos < 0 || nanos >= 1_000_000_000) {
+        throw RuntimeException("Duration out of range")
+    }
+    return java.time.Duration.ofSeconds(seconds, nanos.toLong())
+}
+
+internal fun lower{{ canonical_type_name }}(v: java.time.Duration): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: java.time.Duration, buf: RustBufferBuilder) {
+    if (v.isNegative) {
+        throw IllegalArgumentException("Duration must not be negative")
+    }
+    buf.putLong(v.seconds)
+    buf.putInt(v.nano)
+}
+
+{% when Type::Custom with { name, builtin } -%}
+{%- let custom = config.custom_type(name) %}
+{%- let ffi_type_name = builtin.ffi_type(ci)|type_ffi %}
+
+// Helper functions for passing values of the custom type {{ name }},
+// which is passed over the FFI as the builtin type {{ builtin|type_kt }}.
+
+{% match custom.type_name() -%}
+{% when Some with (type_name) -%}
+typealias {{ typ|type_kt }} = {{ type_name }}
+{% when None -%}
+typealias {{ typ|type_kt }} = {{ builtin|type_kt }}
+{% endmatch %}
+internal fun lift{{ canonical_type_name }}(v: {{ ffi_type_name }}): {{ typ|type_kt }} {
+    val builtinValue = {{ "v"|lift_kt(builtin) }}
+    return {{ custom.into_custom("builtinValue") }}
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ typ|type_kt }} {
+    val builtinValue = {{ "buf"|read_kt(builtin) }}
+    return {{ custom.into_custom("builtinValue") }}
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ typ|type_kt }}): {{ ffi_type_name }} {
+    val builtinValue = {{ custom.from_custom("v") }}
+    return {{ "builtinValue"|lower_kt(builtin) }}
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ typ|type_kt }}, buf: RustBufferBuilder) {
+    val builtinValue = {{ custom.from_custom("v") }}
+    {{ "builtinValue"|write_kt("buf", builtin) }}
+}
+
+{% when Type::External with { name, crate_name } -%}
+{%- let package = config.external_package(crate_name) %}
+
+// Helper functions for passing values of the record {{ name }}, which is defined by the
+// component in `{{ package }}`. We use that component's helpers to read and write it,
+// so its bindings must be compiled alongside these ones.
+
+typealias {{ typ|type_kt }} = {{ package }}.{{ name|class_name_kt }}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ typ|type_kt }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ typ|type_kt }} {
+    return {{ package }}.{{ name|class_name_kt }}.read(buf)
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ typ|type_kt }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ typ|type_kt }}, buf: RustBufferBuilder) {
+    // The other component has its own `RustBufferBuilder`, so we write the value
+    // using that and then copy the bytes across.
+    val external = {{ package }}.RustBufferBuilder()
+    v.write(external)
+    val rbuf = external.finalize()
+    try {
+        val bytes = ByteArray(rbuf.len)
+        rbuf.asByteBuffer()?.get(bytes)
+        buf.put(bytes)
+    } finally {
+        {{ package }}.RustBuffer.free(rbuf)
+    }
+}
+
 {% when Type::Optional with (inner_type) -%}
 {% let inner_type_name = inner_type|type_kt %}
 
@@ -346,43 +515,140 @@ internal fun write{{ canonical_type_name }}(v: List<{{ inner_type_name }}>, buf:
     }
 }
 
-{% when Type::Map with (inner_type) -%}
-{% let inner_type_name = inner_type|type_kt %}
+{% when Type::Map with (key_type, value_type) -%}
+{% let key_type_name = key_type|type_kt %}
+{% let value_type_name = value_type|type_kt %}
 
 // Helper functions for pasing values of type {{ typ|type_kt }}
 
-internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<String, {{ inner_type_name }}> {
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<{{ key_type_name }}, {{ value_type_name }}> {
     return liftFromRustBuffer(rbuf) { buf ->
         read{{ canonical_type_name }}(buf)
     }
 }
 
-internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<String, {{ inner_type_name }}> {
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<{{ key_type_name }}, {{ value_type_name }}> {
     // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
-    val items : MutableMap<String, {{ inner_type_name }}> = mutableMapOf()
+    val items : MutableMap<{{ key_type_name }}, {{ value_type_name }}> = mutableMapOf()
     val len = buf.getInt()
     repeat(len) {
-        val k = String.read(buf)
-        val v = {{ "buf"|read_kt(inner_type) }}
+        val k = {{ "buf"|read_kt(key_type) }}
+        val v = {{ "buf"|read_kt(value_type) }}
         items[k] = v
     }
     return items
 }
 
-internal fun lower{{ canonical_type_name }}(m: Map<String, {{ inner_type_name }}>): RustBuffer.ByValue {
+internal fun lower{{ canonical_type_name }}(m: Map<{{ key_type_name }}, {{ value_type_name }}>): RustBuffer.ByValue {
     return lowerIntoRustBuffer(m) { m, buf ->
         write{{ canonical_type_name }}(m, buf)
     }
 }
 
-internal fun write{{ canonical_type_name }}(v: Map<String, {{ inner_type_name }}>, buf: RustBufferBuilder) {
+internal fun write{{ canonical_type_name }}(v: Map<{{ key_type_name }}, {{ value_type_name }}>, buf: RustBufferBuilder) {
     buf.putInt(v.size)
     v.forEach { k, v ->
-        k.write(buf)
-        {{ "v"|write_kt("buf", inner_type) }}
+        {{ "k"|write_kt("buf", key_type) }}
+        {{ "v"|write_kt("buf", value_type) }}
+    }
+}
+
+{% when Type::Union with (name, members) -%}
+{% let union_type_name = typ|type_kt %}
+
+// The sum type for values of the union {{ union_type_name }},
+// with one variant for each member type.
+
+sealed class {{ union_type_name }} {
+    {%- for (variant, member_type) in typ.union_variants() %}
+    data class {{ variant }}(val value: {{ member_type|type_kt }}) : {{ union_type_name }}()
+    {%- endfor %}
+}
+
+// Helper functions for pasing values of type {{ typ|type_kt }}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ union_type_name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
     }
 }
 
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ union_type_name }} {
+    return when (buf.get().toInt()) {
+        {%- for (variant, member_type) in typ.union_variants() %}
+        {{ loop.index }} -> {{ union_type_name }}.{{ variant }}({{ "buf"|read_kt(member_type) }})
+        {%- endfor %}
+        else -> throw RuntimeException("invalid tag for union type {{ union_type_name }}")
+    }
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ union_type_name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ union_type_name }}, buf: RustBufferBuilder) {
+    when (v) {
+        {%- for (variant, member_type) in typ.union_variants() %}
+        is {{ union_type_name }}.{{ variant }} -> {
+            val value = v.value
+            buf.putByte({{ loop.index }})
+            {{ "value"|write_kt("buf", member_type) }}
+        }
+        {%- endfor %}
+    }
+}
+
+{% when Type::Tuple with { name, elements } -%}
+{% let tuple_type_name = typ|type_kt %}
+{%- if elements.len() == 2 %}
+
+typealias {{ tuple_type_name }} = Pair<{{ elements[0]|type_kt }}, {{ elements[1]|type_kt }}>
+{%- else if elements.len() == 3 %}
+
+typealias {{ tuple_type_name }} = Triple<{{ elements[0]|type_kt }}, {{ elements[1]|type_kt }}, {{ elements[2]|type_kt }}>
+{%- else %}
+
+// Kotlin only has builtin classes for pairs and triples, so larger tuples get a data class.
+
+data class {{ tuple_type_name }}(
+    {%- for element_type in elements %}
+    val {{ loop.index0|tuple_element_kt }}: {{ element_type|type_kt }}{% if !loop.last %},{% endif %}
+    {%- endfor %}
+)
+{%- endif %}
+
+// Helper functions for pasing values of type {{ typ|type_kt }}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ tuple_type_name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ tuple_type_name }} {
+    return {{ tuple_type_name }}(
+        {%- for element_type in elements %}
+        {{ "buf"|read_kt(element_type) }}{% if !loop.last %},{% endif %}
+        {%- endfor %}
+    )
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ tuple_type_name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ tuple_type_name }}, buf: RustBufferBuilder) {
+    {%- for element_type in elements %}
+    {%- let element_name = loop.index0|tuple_element_kt %}
+    val {{ element_name }} = v.{{ element_name }}
+    {{ element_name|write_kt("buf", element_type) }}
+    {%- endfor %}
+}
+
 {% when Type::Enum with (enum_name) -%}
 {# Helpers for Enum types are defined inline with the Enum class #}
 
@@ -34,10 +34,10 @@ open class RustBuffer : Structure() {
 }
 
 // This is a helper for safely passing byte references into the rust code.
-// It's not actually used at the moment, because there aren't many things that you
-// can take a direct pointer to in the JVM, and if we're going to copy something
-// then we might as well copy it into a `RustBuffer`. But it's here for API
-// completeness.
+// It's used for borrowed (`[ByRef]`) strings and byte arrays. There aren't many things
+// that you can take a direct pointer to in the JVM, so we copy the bytes into native
+// memory that stays alive for as long as the struct does, but that's still cheaper
+// than calling into Rust to allocate a `RustBuffer` for them.
 
 @Structure.FieldOrder("len", "data", "padding", "padding2")
 open class ForeignBytes : Structure() {
@@ -48,6 +48,18 @@ open class ForeignBytes : Structure() {
     @JvmField var padding2: Int = 0
 
     class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        internal fun fromBytes(bytes: ByteArray) = ByValue().apply {
+            len = bytes.size
+            // JNA can't allocate zero bytes of memory, but the rust code treats a null pointer as empty.
+            if (bytes.isNotEmpty()) {
+                data = Memory(bytes.size.toLong()).apply { write(0, bytes, 0, bytes.size) }
+            }
+        }
+
+        internal fun fromString(value: String) = fromBytes(value.toByteArray(Charsets.UTF_8))
+    }
 }
 
 
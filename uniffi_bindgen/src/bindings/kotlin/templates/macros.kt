@@ -34,7 +34,11 @@ rustCall(
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}
+        {{- arg.name()|lower_borrowed_kt(arg.type_()) }}
+        {%- else %}
         {{- arg.name()|lower_kt(arg.type_()) }}
+        {%- endif %}
         {%- if !loop.last %}, {% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -18,6 +18,7 @@ package {{ config.package_name() }};
 // helpers directly inline like we're doing here.
 
 import com.sun.jna.Library
+import com.sun.jna.Memory
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
@@ -27,6 +28,9 @@ import java.util.concurrent.atomic.AtomicLong
 import java.util.concurrent.atomic.AtomicReference
 import java.util.concurrent.locks.ReentrantLock
 import kotlin.concurrent.withLock
+{%- for imp in config.custom_type_imports() %}
+import {{ imp }}
+{%- endfor %}
 
 {% include "RustBufferTemplate.kt" %}
 
@@ -50,6 +54,11 @@ import kotlin.concurrent.withLock
 {% include "RecordTemplate.kt" %}
 {% endfor %}
 
+// Namespace constants
+{%- for c in ci.iter_constant_definitions() %}
+const val {{ c.name()|const_name_kt }}: {{ c.type_()|type_kt }} = {{ c.value()|literal_kt }}
+{%- endfor %}
+
 // Namespace functions
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.kt" %}
@@ -65,4 +74,21 @@ import kotlin.concurrent.withLock
 {% include "CallbackInterfaceTemplate.kt" %}
 {% endfor %}
 
+/**
+ * Check that every object and buffer passed across the FFI has been freed.
+ *
+ * This is intended for use in tests, and only works if the Rust code was built with
+ * the `leak-tracking` feature of the `uniffi` crate.
+ */
+fun assertNoLeaks() {
+    val leaks = rustCall(InternalError.ByReference()) { err ->
+        _UniFFILib.INSTANCE.{{ ci.ffi_leak_report().name() }}(err)
+    }.let {
+        {{ "it"|lift_kt(Type::String) }}
+    }
+    if (leaks.isNotEmpty()) {
+        throw AssertionError("Leaked across the FFI: $leaks")
+    }
+}
+
 {% import "macros.kt" as kt %}
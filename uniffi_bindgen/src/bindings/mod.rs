@@ -7,10 +7,10 @@
 //! This module contains all the code for generating foreign language bindings,
 //! along with some helpers for executing foreign language scripts or tests.
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::interface::ComponentInterface;
 use crate::MergeWith;
@@ -19,6 +19,7 @@ pub mod gecko_js;
 pub mod kotlin;
 pub mod python;
 pub mod swift;
+pub mod wasm;
 
 /// Enumeration of all foreign language targets currently supported by this crate.
 ///
@@ -32,6 +33,7 @@ pub enum TargetLanguage {
     Swift,
     Python,
     GeckoJs,
+    Wasm,
 }
 
 impl TryFrom<&str> for TargetLanguage {
@@ -42,6 +44,7 @@ impl TryFrom<&str> for TargetLanguage {
             "swift" => TargetLanguage::Swift,
             "python" | "py" => TargetLanguage::Python,
             "gecko_js" => TargetLanguage::GeckoJs,
+            "wasm" | "js" | "mjs" => TargetLanguage::Wasm,
             _ => bail!("Unknown or unsupported target language: \"{}\"", value),
         })
     }
@@ -74,6 +77,8 @@ pub struct Config {
     python: python::Config,
     #[serde(default)]
     gecko_js: gecko_js::Config,
+    #[serde(default)]
+    wasm: wasm::Config,
 }
 
 impl From<&ComponentInterface> for Config {
@@ -83,6 +88,7 @@ impl From<&ComponentInterface> for Config {
             swift: ci.into(),
             python: ci.into(),
             gecko_js: ci.into(),
+            wasm: ci.into(),
         }
     }
 }
@@ -94,6 +100,59 @@ impl MergeWith for Config {
             swift: self.swift.merge_with(&other.swift),
             python: self.python.merge_with(&other.python),
             gecko_js: self.gecko_js.merge_with(&other.gecko_js),
+            wasm: self.wasm.merge_with(&other.wasm),
+        }
+    }
+}
+
+/// Config options for how a `[Custom]` type from the UDL appears in foreign-language bindings.
+///
+/// These live in a `custom_types` table for each language, keyed by the name of the custom type.
+/// By default a custom type is exposed to foreign-language code as the builtin type that it wraps,
+/// but the bindings can instead name their own type to expose it as, along with expressions for
+/// converting to and from the builtin type in which `{}` stands for the value being converted.
+/// For example, the Kotlin bindings might declare:
+///
+/// ```toml
+/// [bindings.kotlin.custom_types.Url]
+/// type_name = "URL"
+/// imports = [ "java.net.URL" ]
+/// into_custom = "URL({})"
+/// from_custom = "{}.toString()"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomTypeConfig {
+    type_name: Option<String>,
+    #[serde(default)]
+    imports: Vec<String>,
+    into_custom: Option<String>,
+    from_custom: Option<String>,
+}
+
+impl CustomTypeConfig {
+    /// The name of the foreign-language type to use for the custom type, if not the builtin.
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// Any imports needed for the foreign-language type or the conversion expressions.
+    pub fn imports(&self) -> &[String] {
+        &self.imports
+    }
+
+    /// Get an expression for converting `nm` from the builtin type into the custom type.
+    pub fn into_custom(&self, nm: &str) -> String {
+        match &self.into_custom {
+            Some(expr) => expr.replace("{}", nm),
+            None => nm.to_string(),
+        }
+    }
+
+    /// Get an expression for converting `nm` from the custom type into the builtin type.
+    pub fn from_custom(&self, nm: &str) -> String {
+        match &self.from_custom {
+            Some(expr) => expr.replace("{}", nm),
+            None => nm.to_string(),
         }
     }
 }
@@ -124,6 +183,9 @@ where
         TargetLanguage::GeckoJs => {
             gecko_js::write_bindings(&config.gecko_js, &ci, out_dir, try_format_code, is_testing)?
         }
+        TargetLanguage::Wasm => {
+            wasm::write_bindings(&config.wasm, &ci, out_dir, try_format_code, is_testing)?
+        }
     }
     Ok(())
 }
@@ -144,10 +206,54 @@ where
         TargetLanguage::Swift => swift::compile_bindings(&config.swift, &ci, out_dir)?,
         TargetLanguage::Python => (),
         TargetLanguage::GeckoJs => (),
+        TargetLanguage::Wasm => (),
     }
     Ok(())
 }
 
+/// Write a copy of the given script into `out_dir`, with code appended that fails the script
+/// if any objects or buffers from the component are still live once it has finished.
+///
+/// This returns the path of the copy, to run in place of the original script.
+pub fn write_leak_checking_script<P1, P2>(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: P1,
+    script_file: P2,
+    language: TargetLanguage,
+) -> Result<PathBuf>
+where
+    P1: AsRef<Path>,
+    P2: AsRef<Path>,
+{
+    let script_file = script_file.as_ref();
+    let leak_check = match language {
+        TargetLanguage::Kotlin => kotlin::leak_check(&config.kotlin, ci),
+        TargetLanguage::Swift => swift::leak_check(&config.swift, ci),
+        TargetLanguage::Python => python::leak_check(&config.python, ci),
+        TargetLanguage::GeckoJs => bail!("Can't run Gecko code standalone"),
+        TargetLanguage::Wasm => wasm::leak_check(&config.wasm, ci),
+    };
+    let mut script = std::fs::read_to_string(script_file)
+        .with_context(|| format!("Failed to read script {:?}", script_file))?;
+    script.push('\n');
+    script.push_str(&leak_check);
+    // Kotlin derives a class name from the script's file name, so keep it a valid identifier.
+    let stem = script_file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("Script file has no name"))?;
+    let extension = script_file
+        .extension()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("File has no extension!"))?;
+    let mut script_copy = PathBuf::from(out_dir.as_ref());
+    script_copy.push(format!("{}_checking_leaks.{}", stem, extension));
+    std::fs::write(&script_copy, script)
+        .with_context(|| format!("Failed to write script {:?}", script_copy))?;
+    Ok(script_copy)
+}
+
 /// Execute the given script via foreign language interpreter/shell.
 pub fn run_script<P1, P2>(out_dir: P1, script_file: P2, language: TargetLanguage) -> Result<()>
 where
@@ -161,6 +267,7 @@ where
         TargetLanguage::Swift => swift::run_script(out_dir, script_file)?,
         TargetLanguage::Python => python::run_script(out_dir, script_file)?,
         TargetLanguage::GeckoJs => bail!("Can't run Gecko code standalone"),
+        TargetLanguage::Wasm => wasm::run_script(out_dir, script_file)?,
     }
     Ok(())
 }
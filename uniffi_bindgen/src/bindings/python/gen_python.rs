@@ -2,11 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use askama::Template;
 use heck::{CamelCase, ShoutySnakeCase, SnakeCase};
 use serde::{Deserialize, Serialize};
 
+use crate::bindings::CustomTypeConfig;
 use crate::interface::*;
 use crate::MergeWith;
 
@@ -15,30 +18,92 @@ use crate::MergeWith;
 // sine the details of the underlying component are entirely determined by the `ComponentInterface`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
-    // No config options yet.
+    cdylib_name: Option<String>,
+    #[serde(default)]
+    custom_types: HashMap<String, CustomTypeConfig>,
+    #[serde(default)]
+    external_modules: HashMap<String, String>,
+}
+
+impl Config {
+    pub fn cdylib_name(&self) -> String {
+        match self.cdylib_name.as_ref() {
+            Some(name) => name.clone(),
+            None => "uniffi".into(),
+        }
+    }
+
+    /// How to expose a custom type from the UDL, defaulting to the builtin type that it wraps.
+    pub fn custom_type(&self, name: &str) -> CustomTypeConfig {
+        self.custom_types.get(name).cloned().unwrap_or_default()
+    }
+
+    /// The imports needed by all the custom types, sorted and without duplicates.
+    pub fn custom_type_imports(&self) -> Vec<String> {
+        let mut imports: Vec<String> = self
+            .custom_types
+            .values()
+            .flat_map(|c| c.imports().iter().cloned())
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+
+    /// The module containing the bindings for an external type's crate, which defaults
+    /// to the module that those bindings would use if the crate's namespace matched its name.
+    pub fn external_module(&self, crate_name: &str) -> String {
+        match self.external_modules.get(crate_name) {
+            Some(module_name) => module_name.clone(),
+            None => crate_name.replace('-', "_"),
+        }
+    }
+
+    /// The modules needed by all the external types, sorted and without duplicates.
+    pub fn external_module_imports(&self, ci: &ComponentInterface) -> Vec<String> {
+        let mut imports: Vec<String> = ci
+            .iter_types()
+            .into_iter()
+            .filter_map(|t| match t {
+                Type::External { crate_name, .. } => Some(self.external_module(&crate_name)),
+                _ => None,
+            })
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
 }
 
 impl From<&ComponentInterface> for Config {
-    fn from(_ci: &ComponentInterface) -> Self {
-        Config {}
+    fn from(ci: &ComponentInterface) -> Self {
+        Config {
+            cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            custom_types: HashMap::new(),
+            external_modules: HashMap::new(),
+        }
     }
 }
 
 impl MergeWith for Config {
-    fn merge_with(&self, _other: &Self) -> Self {
-        self.clone()
+    fn merge_with(&self, other: &Self) -> Self {
+        Config {
+            cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            external_modules: self.external_modules.merge_with(&other.external_modules),
+        }
     }
 }
 
 #[derive(Template)]
 #[template(syntax = "py", escape = "none", path = "wrapper.py")]
 pub struct PythonWrapper<'a> {
-    _config: Config,
+    config: Config,
     ci: &'a ComponentInterface,
 }
 impl<'a> PythonWrapper<'a> {
-    pub fn new(_config: Config, ci: &'a ComponentInterface) -> Self {
-        Self { _config, ci }
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
     }
 }
 
@@ -62,10 +127,29 @@ mod filters {
             FFIType::RustBuffer => "RustBuffer".to_string(),
             FFIType::RustError => "ctypes.POINTER(RustError)".to_string(),
             FFIType::ForeignBytes => "ForeignBytes".to_string(),
+            FFIType::Struct(name) => format!("{}FfiStruct", class_name_py(name)?),
             FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
         })
     }
 
+    /// Quote a string as a Python string literal, escaping backslashes, quotes and control characters.
+    fn string_literal_py(s: &str) -> String {
+        let mut out = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
     pub fn literal_py(literal: &Literal) -> Result<String, askama::Error> {
         Ok(match literal {
             Literal::Boolean(v) => {
@@ -76,7 +160,7 @@ mod filters {
                 }
             }
             // use the double-quote form to match with the other languages, and quote escapes.
-            Literal::String(s) => format!("\"{}\"", s),
+            Literal::String(s) => string_literal_py(s),
             Literal::Null => "None".into(),
             Literal::EmptySequence => "[]".into(),
             Literal::EmptyMap => "{}".into(),
@@ -115,6 +199,38 @@ mod filters {
         Ok(nm.to_string().to_shouty_snake_case())
     }
 
+    pub fn const_name_py(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
+    }
+
+    /// Render a doc comment from the UDL as a Python docstring, indenting any subsequent lines
+    /// by the given amount so that they line up with the body of the documented declaration.
+    pub fn docstring_py(docs: &Option<&str>, spaces: &usize) -> Result<String, askama::Error> {
+        Ok(match docs {
+            None => "".into(),
+            Some(docs) => {
+                let indent = " ".repeat(*spaces);
+                let docs = docs.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+                let mut out = "\"\"\"".to_string();
+                if docs.contains('\n') {
+                    for line in docs.lines() {
+                        out.push('\n');
+                        if !line.is_empty() {
+                            out.push_str(&indent);
+                            out.push_str(line);
+                        }
+                    }
+                    out.push('\n');
+                    out.push_str(&indent);
+                } else {
+                    out.push_str(&docs);
+                }
+                out.push_str("\"\"\"");
+                out
+            }
+        })
+    }
+
     pub fn coerce_py(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
@@ -128,20 +244,29 @@ mod filters {
             Type::Float32 | Type::Float64 => format!("float({})", nm),
             Type::Boolean => format!("bool({})", nm),
             Type::String | Type::Object(_) | Type::Error(_) | Type::Record(_) => nm.to_string(),
+            Type::Bytes => format!("bytes({})", nm),
+            Type::Timestamp | Type::Duration | Type::Custom { .. } | Type::External { .. } => {
+                nm.to_string()
+            }
             Type::CallbackInterface(_) => panic!("No support for coercing callback interfaces yet"),
             Type::Enum(name) => format!("{}({})", class_name_py(name)?, nm),
             Type::Optional(t) => format!("(None if {} is None else {})", nm, coerce_py(nm, t)?),
             Type::Sequence(t) => format!("list({} for x in {})", coerce_py(&"x", t)?, nm),
-            Type::Map(t) => format!(
+            Type::Union(..) | Type::Tuple { .. } => nm.to_string(),
+            Type::Map(k, v) => format!(
                 "dict(({},{}) for (k, v) in {}.items())",
-                coerce_py(&"k", &Type::String)?,
-                coerce_py(&"v", t)?,
+                coerce_py(&"k", k)?,
+                coerce_py(&"v", v)?,
                 nm
             ),
         })
     }
 
-    pub fn lower_py(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+    pub fn lower_py(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
             | Type::UInt8
@@ -159,7 +284,24 @@ mod filters {
             Type::Object(_) => format!("({}._handle)", nm),
             Type::CallbackInterface(_) => panic!("No support for lowering callback interfaces yet"),
             Type::Error(_) => panic!("No support for lowering errors, yet"),
-            Type::Record(_) | Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => format!(
+            Type::Record(name) if is_plain_old_data(name, ci) => {
+                format!("{}FfiStruct.fromRecord({})", class_name_py(name)?, nm)
+            }
+            Type::Custom { .. } => format!(
+                "_UniFFI{}.lower({})",
+                class_name_py(&type_.canonical_name())?,
+                nm
+            ),
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Record(_)
+            | Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => format!(
                 "RustBuffer.allocFrom{}({})",
                 class_name_py(&type_.canonical_name())?,
                 nm
@@ -167,7 +309,22 @@ mod filters {
         })
     }
 
-    pub fn lift_py(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+    pub fn lower_borrowed_py(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::String => format!("ForeignBytes.fromString({})", nm),
+            Type::Bytes | Type::Sequence(_) => format!("ForeignBytes.fromBytes(bytes({}))", nm),
+            _ => unreachable!(
+                "Only strings and byte arrays can be borrowed, not {:?}",
+                type_
+            ),
+        })
+    }
+
+    pub fn lift_py(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
             | Type::UInt8
@@ -184,11 +341,31 @@ mod filters {
             Type::Object(_) => panic!("No support for lifting objects, yet"),
             Type::CallbackInterface(_) => panic!("No support for lifting callback interfaces, yet"),
             Type::Error(_) => panic!("No support for lifting errors, yet"),
-            Type::Record(_) | Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => format!(
+            Type::Record(name) if is_plain_old_data(name, ci) => format!("{}.intoRecord()", nm),
+            Type::Custom { .. } => format!(
+                "_UniFFI{}.lift({})",
+                class_name_py(&type_.canonical_name())?,
+                nm
+            ),
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Record(_)
+            | Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => format!(
                 "{}.consumeInto{}()",
                 nm,
                 class_name_py(&type_.canonical_name())?
             ),
         })
     }
+
+    // Records of plain-old-data are passed as a ctypes Structure rather than a RustBuffer.
+    fn is_plain_old_data(name: &str, ci: &ComponentInterface) -> bool {
+        matches!(ci.get_record_definition(name), Some(rec) if rec.is_plain_old_data())
+    }
 }
@@ -54,6 +54,14 @@ pub fn generate_python_bindings(config: &Config, ci: &ComponentInterface) -> Res
         .map_err(|_| anyhow::anyhow!("failed to render python bindings"))
 }
 
+/// Python code that fails the script if any objects or buffers from the component have leaked.
+pub fn leak_check(_config: &Config, ci: &ComponentInterface) -> String {
+    format!(
+        "import {0} as _uniffi_leak_check\n_uniffi_leak_check.assert_no_leaks()\n",
+        ci.namespace()
+    )
+}
+
 /// Execute the specifed python script, with environment based on the generated
 /// artifacts in the given output directory.
 pub fn run_script(out_dir: &Path, script_file: &Path) -> Result<()> {
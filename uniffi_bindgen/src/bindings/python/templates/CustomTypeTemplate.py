@@ -0,0 +1,29 @@
+# Helpers for the custom types declared in the UDL.
+# Each custom type is passed over the FFI as the builtin type that it wraps, and
+# these convert it to and from that builtin type using the configured expressions.
+
+{%- for typ in ci.iter_types() %}
+{%- match typ %}
+{%- when Type::Custom with { name, builtin } %}
+{%- let custom = config.custom_type(name) %}
+
+class _UniFFI{{ typ.canonical_name()|class_name_py }}:
+    @staticmethod
+    def intoCustom(builtinValue):
+        return {{ custom.into_custom("builtinValue") }}
+
+    @staticmethod
+    def fromCustom(v):
+        return {{ custom.from_custom("v") }}
+
+    @staticmethod
+    def lift(v):
+        return _UniFFI{{ typ.canonical_name()|class_name_py }}.intoCustom({{ "v"|lift_py(builtin, ci) }})
+
+    @staticmethod
+    def lower(v):
+        builtinValue = _UniFFI{{ typ.canonical_name()|class_name_py }}.fromCustom(v)
+        return {{ "builtinValue"|lower_py(builtin, ci) }}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
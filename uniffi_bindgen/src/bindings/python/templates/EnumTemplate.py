@@ -1,4 +1,5 @@
 class {{ e.name()|class_name_py }}(enum.Enum):
+    {{ e.docs()|docstring_py(4) }}
     {% for variant in e.variants() -%}
     {{ variant|enum_name_py }} = {{ loop.index }}
     {% endfor %}
@@ -18,8 +18,70 @@ class InternalError(Exception):
     def raise_err(code, message):
         raise InternalError(message)
 
+class WrongThreadError(InternalError):
+    """
+    Raised when calling a method of a thread-confined object on a thread
+    other than the one that created it.
+    """
+    pass
+
+class ReentrantCallError(InternalError):
+    """
+    Raised when calling a method of a thread-confined object while it's already
+    in use on the same thread, for example from a callback that it's making.
+    """
+    pass
+
+class RustPanic(InternalError):
+    """
+    Raised when Rust code panics.
+
+    As well as the panic message, this has the location of the panic in the Rust code, the
+    name of the thread that panicked, and a backtrace if the Rust code was built with the
+    `panic-backtraces` feature of the `uniffi` crate. Those are `None` if they're unknown.
+    """
+
+    def __init__(self, message, file=None, line=None, column=None, thread_name=None, backtrace=None):
+        super().__init__(message)
+        self.message = message
+        self.file = file
+        self.line = line
+        self.column = column
+        self.thread_name = thread_name
+        self.backtrace = backtrace
+
+    def __str__(self):
+        if self.file is None:
+            return self.message
+        return "{} at {}:{}:{}".format(self.message, self.file, self.line, self.column)
+
+    @staticmethod
+    def _take(message):
+        # The panic hook records a structured report of the panic, which we take
+        # so that it doesn't get mixed up with the next one.
+        rbuf = rust_call_with_error(InternalError, _UniFFILib.{{ ci.ffi_take_panic_report().name() }})
+        with rbuf.consumeWithStream() as stream:
+            if stream.remaining() == 0:
+                return RustPanic(message)
+            return RustPanic(
+                message=stream.readString(),
+                file=stream.readString(),
+                line=stream.readI32(),
+                column=stream.readI32(),
+                thread_name=stream.readString() or None,
+                backtrace=stream.readString() or None,
+            )
+
+class InvalidArgumentError(InternalError):
+    """
+    Raised when Rust couldn't lift one of the arguments that it was passed,
+    which means that these bindings have a bug or are out of date.
+    """
+    pass
+
 {% for e in ci.iter_error_definitions() %}
 class {{ e.name()|class_name_py }}:
+    {{ e.docs()|docstring_py(4) }}
     {%- for value in e.values() %}
     class {{ value|class_name_py }}(Exception):
         pass
@@ -44,6 +106,15 @@ def rust_call_with_error(error_class, fn, *args):
         message = str(error)
         error.free()
 
+        # These are the same for every call, whatever errors it's declared to throw.
+        if error.code == -1:
+            raise RustPanic._take(message)
+        if error.code == -2:
+            raise WrongThreadError(message)
+        if error.code == -3:
+            raise InvalidArgumentError(message)
+        if error.code == -4:
+            raise ReentrantCallError(message)
         error_class.raise_err(error.code, message)
     
     return result
@@ -1,26 +1,29 @@
 # This is how we find and load the dynamic library provided by the component.
 # For now we just look it up by name.
 #
-# XXX TODO: This will probably grow some magic for resolving megazording in future.
-# E.g. we might start by looking for the named component in `libuniffi.so` and if
-# that fails, fall back to loading it separately from `lib${componentName}.so`.
+# Several components may be bundled into the same library, in which case the OS only
+# loads it once. We still take our own handle on it rather than sharing `ctypes.cdll`'s,
+# so that the argtypes we declare below can't clash with those of another component.
 
-def loadIndirect(componentName):
+def loadIndirect(cdylibName):
     if sys.platform == "linux":
-        libname = "libuniffi_{}.so"
+        libname = "lib{}.so"
     elif sys.platform == "darwin":
-        libname = "libuniffi_{}.dylib"
+        libname = "lib{}.dylib"
     elif sys.platform.startswith("win"):
-        libname = "libuniffi_{}.dll"
-    return getattr(ctypes.cdll, libname.format(componentName))
+        libname = "lib{}.dll"
+    return ctypes.cdll.LoadLibrary(libname.format(cdylibName))
 
 # A ctypes library to expose the extern-C FFI definitions.
 # This is an implementation detail which will be called internally by the public API.
 
-_UniFFILib = loadIndirect(componentName="{{ ci.namespace() }}")
+_UniFFILib = loadIndirect(cdylibName="{{ config.cdylib_name() }}")
 {%- for func in ci.iter_ffi_function_definitions() %}
 _UniFFILib.{{ func.name() }}.argtypes = (
     {%- call py::arg_list_ffi_decl(func) -%}
 )
 _UniFFILib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|type_ffi }}{% when None %}None{% endmatch %}
-{%- endfor %}
\ No newline at end of file
+{%- endfor %}
+
+# Record structured reports of Rust panics, for the `RustPanic` exception.
+rust_call_with_error(InternalError, _UniFFILib.{{ ci.ffi_install_panic_hook().name() }})
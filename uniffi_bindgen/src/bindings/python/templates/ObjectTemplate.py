@@ -1,4 +1,5 @@
 class {{ obj.name()|class_name_py }}(object):
+    {{ obj.class_docs().as_deref()|docstring_py(4) }}
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
@@ -7,13 +8,118 @@ class {{ obj.name()|class_name_py }}(object):
     {%- when None %}
     {%- endmatch %}
 
+    # The number of calls in progress, which hold off freeing the Rust object until they return.
+    _uniffi_calls = 0
+    _uniffi_destroyed = False
+
     def __del__(self):
+        self.destroy()
+
+    def destroy(self):
+        """
+        Free the underlying Rust object.
+
+        The object will also be freed when it is garbage-collected, but Python makes no promises
+        about when (or whether) that will happen, so it's a good idea to call this explicitly,
+        or to use the object in a `with` block. It's fine to call this more than once, and from
+        any thread. Calling any method on the object after destroying it will raise a `ValueError`,
+        but calls that are already in progress on other threads carry on, and the Rust object is
+        freed when the last of them returns.
+        """
+        # The handle won't have been set if the constructor failed.
+        if getattr(self, "_handle", None) is None:
+            return
+        with _UniFFIObjectLock:
+            if self._uniffi_destroyed:
+                return
+            # Poison the object first, so that no new calls can use it.
+            self._uniffi_destroyed = True
+            if self._uniffi_calls > 0:
+                # The last call to return will free it.
+                return
+        self._uniffi_free()
+
+    def close(self):
+        """An alias for `destroy()`, for use with `contextlib.closing()` and the like."""
+        self.destroy()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.destroy()
+
+    def _uniffi_free(self):
         rust_call_with_error(
             InternalError,
             _UniFFILib.{{ obj.ffi_object_free().name() }},
             self._handle
         )
 
+    @contextlib.contextmanager
+    def _uniffi_calling(self):
+        # Counts a call as in progress for as long as it's using the handle, since ctypes
+        # lets other threads run while the call is in Rust, and one of them might destroy us.
+        with _UniFFIObjectLock:
+            if self._uniffi_destroyed:
+                raise ValueError("{{ obj.name()|class_name_py }} object has already been destroyed")
+            self._uniffi_calls += 1
+        try:
+            yield self._handle
+        finally:
+            with _UniFFIObjectLock:
+                self._uniffi_calls -= 1
+                free = self._uniffi_destroyed and self._uniffi_calls == 0
+            if free:
+                self._uniffi_free()
+
+    {%- match obj.ffi_object_debug() %}
+    {%- when Some with (func) %}
+
+    def __repr__(self):
+        with self._uniffi_calling() as _handle:
+            _retval = rust_call_with_error(InternalError, _UniFFILib.{{ func.name() }}, _handle)
+        return {{ "_retval"|lift_py(Type::String, ci) }}
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_display() %}
+    {%- when Some with (func) %}
+
+    def __str__(self):
+        with self._uniffi_calling() as _handle:
+            _retval = rust_call_with_error(InternalError, _UniFFILib.{{ func.name() }}, _handle)
+        return {{ "_retval"|lift_py(Type::String, ci) }}
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_eq() %}
+    {%- when Some with (func) %}
+
+    def __eq__(self, other):
+        if not isinstance(other, {{ obj.name()|class_name_py }}):
+            return NotImplemented
+        with self._uniffi_calling() as _handle, other._uniffi_calling() as _other_handle:
+            _retval = rust_call_with_error(
+                InternalError,
+                _UniFFILib.{{ func.name() }},
+                _handle,
+                _other_handle
+            )
+        return {{ "_retval"|lift_py(Type::Boolean, ci) }}
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_hash() %}
+    {%- when Some with (func) %}
+
+    def __hash__(self):
+        with self._uniffi_calling() as _handle:
+            _retval = rust_call_with_error(InternalError, _UniFFILib.{{ func.name() }}, _handle)
+        return {{ "_retval"|lift_py(Type::UInt64, ci) }}
+    {%- when None %}
+    {%- endmatch %}
+
     {% for cons in obj.alternate_constructors() -%}
     @classmethod
     def {{ cons.name()|fn_name_py }}(cls, {% call py::arg_list_decl(cons) %}):
@@ -27,18 +133,62 @@ class {{ obj.name()|class_name_py }}(object):
         return inst
     {% endfor %}
 
+    {% for meth in obj.static_methods() -%}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) -%}
+    @staticmethod
+    def {{ meth.name()|fn_name_py }}({% call py::arg_list_decl(meth) %}):
+        {{ meth.docs()|docstring_py(8) }}
+        {%- call py::coerce_args_extra_indent(meth) %}
+        _retval = {% call py::to_ffi_call(meth) %}
+        return {{ "_retval"|lift_py(return_type, ci) }}
+
+    {%- when None -%}
+    @staticmethod
+    def {{ meth.name()|fn_name_py }}({% call py::arg_list_decl(meth) %}):
+        {{ meth.docs()|docstring_py(8) }}
+        {%- call py::coerce_args_extra_indent(meth) %}
+        {% call py::to_ffi_call(meth) %}
+    {% endmatch %}
+    {% endfor %}
+
+    {% for prop in obj.properties() -%}
+    @property
+    def {{ prop.name()|var_name_py }}(self):
+        {{ prop.docs()|docstring_py(8) }}
+        with self._uniffi_calling() as _handle:
+            _retval = {% call py::to_ffi_call_with_prefix("_handle", prop.getter()) %}
+        return {{ "_retval"|lift_py(prop.type_(), ci) }}
+    {%- match prop.setter() %}
+    {%- when Some with (setter) %}
+
+    @{{ prop.name()|var_name_py }}.setter
+    def {{ prop.name()|var_name_py }}(self, value):
+        {%- call py::coerce_args_extra_indent(setter) %}
+        with self._uniffi_calling() as _handle:
+            {% call py::to_ffi_call_with_prefix("_handle", setter) %}
+    {%- when None %}
+    {%- endmatch %}
+
+    {% endfor %}
+
     {% for meth in obj.methods() -%}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
     def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        {{ meth.docs()|docstring_py(8) }}
         {%- call py::coerce_args_extra_indent(meth) %}
-        _retval = {% call py::to_ffi_call_with_prefix("self._handle", meth) %}
-        return {{ "_retval"|lift_py(return_type) }}
+        with self._uniffi_calling() as _handle:
+            _retval = {% call py::to_ffi_call_with_prefix("_handle", meth) %}
+        return {{ "_retval"|lift_py(return_type, ci) }}
 
     {%- when None -%}
     def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        {{ meth.docs()|docstring_py(8) }}
         {%- call py::coerce_args_extra_indent(meth) %}
-        {% call py::to_ffi_call_with_prefix("self._handle", meth) %}
+        with self._uniffi_calling() as _handle:
+            {% call py::to_ffi_call_with_prefix("_handle", meth) %}
     {% endmatch %}
     {% endfor %}
@@ -0,0 +1,27 @@
+{%- if rec.is_plain_old_data() %}
+{%- let struct_name = rec.name()|class_name_py %}
+# Records containing only numbers and booleans are passed across the FFI by value,
+# as a C-style struct with the same fields, rather than being serialized into a RustBuffer.
+
+class {{ struct_name }}FfiStruct(ctypes.Structure):
+    _fields_ = [
+        {%- for field in rec.fields() %}
+        ("{{ field.name() }}", {{ field.type_().ffi_type(ci)|type_ffi }}),
+        {%- endfor %}
+    ]
+
+    @staticmethod
+    def fromRecord(v):
+        return {{ struct_name }}FfiStruct(
+            {%- for field in rec.fields() %}
+            {{ "v.{}"|format(field.name()|var_name_py)|lower_py(field.type_(), ci) }},
+            {%- endfor %}
+        )
+
+    def intoRecord(self):
+        return {{ struct_name }}(
+            {%- for field in rec.fields() %}
+            {{ "self.{}"|format(field.name())|lift_py(field.type_(), ci) }},
+            {%- endfor %}
+        )
+{%- endif %}
@@ -1,4 +1,5 @@
 class {{ rec.name()|class_name_py }}(object):
+    {{ rec.docs()|docstring_py(4) }}
     def __init__(self,{% for field in rec.fields() %}{{ field.name()|var_name_py }}{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
         {%- for field in rec.fields() %}
         self.{{ field.name()|var_name_py }} = {{ field.name()|var_name_py }}
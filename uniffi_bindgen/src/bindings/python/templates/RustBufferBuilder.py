@@ -31,8 +31,9 @@ class RustBufferBuilder(object):
 
     def write(self, value):
         with self._reserve(len(value)):
-            for i, byte in enumerate(value):
-                self.rbuf.data[self.rbuf.len + i] = byte
+            # Copy the bytes in bulk, which is much faster than assigning them one at a time.
+            dest = ctypes.addressof(self.rbuf.data.contents) + self.rbuf.len
+            ctypes.memmove(dest, bytes(value), len(value))
 
     # For every type used in the interface, we provide helper methods for conveniently
     # writing values of that type in a buffer. Putting them on this internal helper object
@@ -62,7 +63,7 @@ class RustBufferBuilder(object):
     {% when Type::UInt16 -%}
 
     def writeU16(self, v):
-        self._pack_into(1, ">H", v)
+        self._pack_into(2, ">H", v)
 
     {% when Type::Int32 -%}
 
@@ -106,6 +107,32 @@ class RustBufferBuilder(object):
         self._pack_into(4, ">i", len(utf8Bytes))
         self.write(utf8Bytes)
 
+    {% when Type::Bytes -%}
+
+    def writeBytes(self, v):
+        self._pack_into(4, ">i", len(v))
+        self.write(v)
+
+    {% when Type::Timestamp -%}
+
+    def writeTimestamp(self, v):
+        # Naive datetimes are assumed to be in UTC.
+        if v.tzinfo is None:
+            v = v.replace(tzinfo=datetime.timezone.utc)
+        # A timedelta normalizes its days to be negative before the epoch,
+        # and its seconds and microseconds to be positive, which is what we want.
+        delta = v - datetime.datetime.fromtimestamp(0, tz=datetime.timezone.utc)
+        self._pack_into(8, ">q", delta.days * 86400 + delta.seconds)
+        self._pack_into(4, ">I", delta.microseconds * 1000)
+
+    {% when Type::Duration -%}
+
+    def writeDuration(self, v):
+        if v < datetime.timedelta(0):
+            raise ValueError("Duration must not be negative")
+        self._pack_into(8, ">Q", v.days * 86400 + v.seconds)
+        self._pack_into(4, ">I", v.microseconds * 1000)
+
     {% when Type::Object with (object_name) -%}
     # The Object type {{ object_name }}.
     # Objects cannot currently be serialized, but we can produce a helpful error.
@@ -142,6 +169,26 @@ class RustBufferBuilder(object):
         self.write{{ field.type_().canonical_name()|class_name_py }}(v.{{ field.name() }})
         {%- endfor %}
 
+    {% when Type::Custom with { name, builtin } -%}
+    # The custom type {{ name }}, which is serialized as the builtin type {{ builtin.canonical_name() }}.
+
+    def write{{ canonical_type_name }}(self, v):
+        self.write{{ builtin.canonical_name()|class_name_py }}(_UniFFI{{ canonical_type_name }}.fromCustom(v))
+
+    {% when Type::External with { name, crate_name } -%}
+    {%- let module = config.external_module(crate_name) -%}
+    # The record {{ name }}, which is defined by the component in the `{{ module }}` module.
+    # That component has its own `RustBufferBuilder`, so we write the value using that
+    # and then copy the bytes across.
+
+    def write{{ canonical_type_name }}(self, v):
+        builder = {{ module }}.RustBufferBuilder()
+        try:
+            builder.write{{ "Record{}"|format(name)|class_name_py }}(v)
+            self.write(builder.rbuf.data[0:builder.rbuf.len])
+        finally:
+            builder.discard()
+
     {% when Type::Optional with (inner_type) -%}
     # The Optional<T> type for {{ inner_type.canonical_name() }}.
 
@@ -160,14 +207,37 @@ class RustBufferBuilder(object):
         for item in items:
             self.write{{ inner_type.canonical_name()|class_name_py }}(item)
 
-    {% when Type::Map with (inner_type) -%}
-    # The Map<T> type for {{ inner_type.canonical_name() }}.
+    {% when Type::Union with (name, members) -%}
+    {%- let union_name = typ.union_name()|class_name_py %}
+    # The union type {{ union_name }}.
+
+    def write{{ canonical_type_name }}(self, v):
+        {%- for (variant, member_type) in typ.union_variants() %}
+        if isinstance(v, {{ union_name }}.{{ variant }}):
+            self._pack_into(1, ">B", {{ loop.index }})
+            self.write{{ member_type.canonical_name()|class_name_py }}(v.value)
+            return
+        {%- endfor %}
+        raise TypeError("Expected a variant of {{ union_name }}, not {}".format(type(v)))
+
+    {% when Type::Tuple with { name, elements } -%}
+    # The tuple type {{ name }}.
+
+    def write{{ canonical_type_name }}(self, v):
+        if len(v) != {{ elements.len() }}:
+            raise ValueError("Expected a tuple of length {{ elements.len() }}, not {}".format(len(v)))
+        {%- for element_type in elements %}
+        self.write{{ element_type.canonical_name()|class_name_py }}(v[{{ loop.index0 }}])
+        {%- endfor %}
+
+    {% when Type::Map with (key_type, value_type) -%}
+    # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
     def write{{ canonical_type_name }}(self, items):
         self._pack_into(4, ">i", len(items))
         for (k, v) in items.items():
-            self.writeString(k)
-            self.write{{ inner_type.canonical_name()|class_name_py }}(v)
+            self.write{{ key_type.canonical_name()|class_name_py }}(k)
+            self.write{{ value_type.canonical_name()|class_name_py }}(v)
 
     {%- endmatch -%}
     {%- endfor %}
\ No newline at end of file
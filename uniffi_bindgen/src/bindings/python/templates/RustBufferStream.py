@@ -51,7 +51,7 @@ class RustBufferStream(object):
     {% when Type::UInt16 -%}
 
     def readU16(self):
-        return self._unpack_from(1, ">H")
+        return self._unpack_from(2, ">H")
 
     {% when Type::Int32 -%}
 
@@ -103,6 +103,34 @@ class RustBufferStream(object):
         utf8Bytes = self.read(size)
         return utf8Bytes.decode("utf-8")
 
+    {% when Type::Bytes -%}
+
+    def readBytes(self):
+        size = self._unpack_from(4, ">i")
+        if size < 0:
+            raise InternalError("Unexpected negative byte string length")
+        return self.read(size)
+
+    {% when Type::Timestamp -%}
+
+    def readTimestamp(self):
+        seconds = self._unpack_from(8, ">q")
+        nanos = self._unpack_from(4, ">I")
+        if nanos >= 1000000000:
+            raise InternalError("Unexpected nanoseconds in timestamp")
+        # Python's datetime only has microsecond precision.
+        return datetime.datetime.fromtimestamp(0, tz=datetime.timezone.utc) + datetime.timedelta(seconds=seconds, microseconds=nanos // 1000)
+
+    {% when Type::Duration -%}
+
+    def readDuration(self):
+        seconds = self._unpack_from(8, ">Q")
+        nanos = self._unpack_from(4, ">I")
+        if nanos >= 1000000000:
+            raise InternalError("Unexpected nanoseconds in duration")
+        # Python's timedelta only has microsecond precision.
+        return datetime.timedelta(seconds=seconds, microseconds=nanos // 1000)
+
     {% when Type::Object with (object_name) -%}
     # The Object type {{ object_name }}.
     # Objects cannot currently be serialized, but we can produce a helpful error.
@@ -143,6 +171,24 @@ class RustBufferStream(object):
             {%- endfor %}
         )
 
+    {% when Type::Custom with { name, builtin } -%}
+    # The custom type {{ name }}, which is serialized as the builtin type {{ builtin.canonical_name() }}.
+
+    def read{{ canonical_type_name }}(self):
+        return _UniFFI{{ canonical_type_name }}.intoCustom(self.read{{ builtin.canonical_name()|class_name_py }}())
+
+    {% when Type::External with { name, crate_name } -%}
+    {%- let module = config.external_module(crate_name) -%}
+    # The record {{ name }}, which is defined by the component in the `{{ module }}` module.
+    # We let that component's own `RustBufferStream` read the value from our buffer.
+
+    def read{{ canonical_type_name }}(self):
+        stream = {{ module }}.RustBufferStream(self.rbuf)
+        stream.offset = self.offset
+        value = stream.read{{ "Record{}"|format(name)|class_name_py }}()
+        self.offset = stream.offset
+        return value
+
     {% when Type::Optional with (inner_type) -%}
     # The Optional<T> type for {{ inner_type.canonical_name() }}.
 
@@ -168,8 +214,30 @@ class RustBufferStream(object):
             count -= 1
         return items
 
-    {% when Type::Map with (inner_type) -%}
-    # The Map<T> type for {{ inner_type.canonical_name() }}.
+    {% when Type::Union with (name, members) -%}
+    {%- let union_name = typ.union_name()|class_name_py %}
+    # The union type {{ union_name }}.
+
+    def read{{ canonical_type_name }}(self):
+        tag = self._unpack_from(1, ">B")
+        {%- for (variant, member_type) in typ.union_variants() %}
+        if tag == {{ loop.index }}:
+            return {{ union_name }}.{{ variant }}(self.read{{ member_type.canonical_name()|class_name_py }}())
+        {%- endfor %}
+        raise InternalError("Unexpected tag byte for {{ union_name }}")
+
+    {% when Type::Tuple with { name, elements } -%}
+    # The tuple type {{ name }}.
+
+    def read{{ canonical_type_name }}(self):
+        return (
+            {%- for element_type in elements %}
+            self.read{{ element_type.canonical_name()|class_name_py }}(),
+            {%- endfor %}
+        )
+
+    {% when Type::Map with (key_type, value_type) -%}
+    # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
     def read{{ canonical_type_name }}(self):
         count = self._unpack_from(4, ">i")
@@ -177,8 +245,8 @@ class RustBufferStream(object):
             raise InternalError("Unexpected negative map size")
         items = {}
         while count > 0:
-            key = self.readString()
-            items[key] = self.read{{ inner_type.canonical_name()|class_name_py }}()
+            key = self.read{{ key_type.canonical_name()|class_name_py }}()
+            items[key] = self.read{{ value_type.canonical_name()|class_name_py }}()
             count -= 1
         return items
 
@@ -78,10 +78,62 @@ class RustBuffer(ctypes.Structure):
         with self.consumeWithStream() as stream:
             return stream.read(stream.remaining()).decode("utf-8")
 
+    {% when Type::Bytes -%}
+    # The primitive Bytes type.
+
+    @staticmethod
+    def allocFromBytes(value):
+        with RustBuffer.allocWithBuilder() as builder:
+            builder.writeBytes(value)
+            return builder.finalize()
+
+    def consumeIntoBytes(self):
+        with self.consumeWithStream() as stream:
+            return stream.readBytes()
+
+    {% when Type::Timestamp -%}
+    # The primitive Timestamp type.
+
+    @staticmethod
+    def allocFromTimestamp(value):
+        with RustBuffer.allocWithBuilder() as builder:
+            builder.writeTimestamp(value)
+            return builder.finalize()
+
+    def consumeIntoTimestamp(self):
+        with self.consumeWithStream() as stream:
+            return stream.readTimestamp()
+
+    {% when Type::Duration -%}
+    # The primitive Duration type.
+
+    @staticmethod
+    def allocFromDuration(value):
+        with RustBuffer.allocWithBuilder() as builder:
+            builder.writeDuration(value)
+            return builder.finalize()
+
+    def consumeIntoDuration(self):
+        with self.consumeWithStream() as stream:
+            return stream.readDuration()
+
     {% when Type::Record with (record_name) -%}
     {%- let rec = ci.get_record_definition(record_name).unwrap() -%}
     # The Record type {{ record_name }}.
 
+    @staticmethod
+    def allocFrom{{ canonical_type_name }}(v):
+        with RustBuffer.allocWithBuilder() as builder:
+            builder.write{{ canonical_type_name }}(v)
+            return builder.finalize()
+
+    def consumeInto{{ canonical_type_name }}(self):
+        with self.consumeWithStream() as stream:
+            return stream.read{{ canonical_type_name }}()
+
+    {% when Type::External with { name, crate_name } -%}
+    # The external record {{ name }}.
+
     @staticmethod
     def allocFrom{{ canonical_type_name }}(v):
         with RustBuffer.allocWithBuilder() as builder:
@@ -118,8 +170,35 @@ class RustBuffer(ctypes.Structure):
         with self.consumeWithStream() as stream:
             return stream.read{{ canonical_type_name }}()
 
-    {% when Type::Map with (inner_type) -%}
-    # The Map<T> type for {{ inner_type.canonical_name() }}.
+    {% when Type::Union with (name, members) -%}
+    {%- let union_name = typ.union_name()|class_name_py %}
+    # The union type {{ union_name }}.
+
+    @staticmethod
+    def allocFrom{{ canonical_type_name }}(v):
+        with RustBuffer.allocWithBuilder() as builder:
+            builder.write{{ canonical_type_name }}(v)
+            return builder.finalize()
+
+    def consumeInto{{ canonical_type_name }}(self):
+        with self.consumeWithStream() as stream:
+            return stream.read{{ canonical_type_name }}()
+
+    {% when Type::Tuple with { name, elements } -%}
+    # The tuple type {{ name }}.
+
+    @staticmethod
+    def allocFrom{{ canonical_type_name }}(v):
+        with RustBuffer.allocWithBuilder() as builder:
+            builder.write{{ canonical_type_name }}(v)
+            return builder.finalize()
+
+    def consumeInto{{ canonical_type_name }}(self):
+        with self.consumeWithStream() as stream:
+            return stream.read{{ canonical_type_name }}()
+
+    {% when Type::Map with (key_type, value_type) -%}
+    # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
     @staticmethod
     def allocFrom{{ canonical_type_name }}(v):
@@ -148,3 +227,15 @@ class ForeignBytes(ctypes.Structure):
 
     def __str__(self):
         return "ForeignBytes(len={}, data={})".format(self.len, self.data[0:self.len])
+
+    # Borrowed strings and byte arrays are passed to Rust by pointing directly at the bytes,
+    # which must not be freed until the call returns. Going via `c_char_p` ensures that the
+    # struct holds a reference to the `bytes` object, keeping it alive for as long as the struct.
+
+    @staticmethod
+    def fromBytes(data):
+        return ForeignBytes(len(data), ctypes.cast(ctypes.c_char_p(data), ctypes.POINTER(ctypes.c_char)))
+
+    @staticmethod
+    def fromString(value):
+        return ForeignBytes.fromBytes(value.encode("utf-8"))
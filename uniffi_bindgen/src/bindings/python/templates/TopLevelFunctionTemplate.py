@@ -2,13 +2,15 @@
 {%- when Some with (return_type) %}
 
 def {{ func.name()|fn_name_py }}({%- call py::arg_list_decl(func) -%}):
+    {{ func.docs()|docstring_py(4) }}
     {%- call py::coerce_args(func) %}
     _retval = {% call py::to_ffi_call(func) %}
-    return {{ "_retval"|lift_py(return_type) }}
+    return {{ "_retval"|lift_py(return_type, ci) }}
 
 {% when None -%}
 
 def {{ func.name()|fn_name_py }}({%- call py::arg_list_decl(func) -%}):
+    {{ func.docs()|docstring_py(4) }}
     {%- call py::coerce_args(func) %}
     {% call py::to_ffi_call(func) %}
 {% endmatch %}
\ No newline at end of file
@@ -0,0 +1,24 @@
+# Sum types for the unions used in the UDL.
+# Each member type of a union gets a variant class holding a value of that type,
+# named positionally since union members don't have names of their own.
+
+{%- for typ in ci.iter_types() %}
+{%- match typ %}
+{%- when Type::Union with (name, members) %}
+{%- let union_name = typ.union_name()|class_name_py %}
+
+class {{ union_name }}:
+    {%- for (variant, member_type) in typ.union_variants() %}
+    class {{ variant }}(object):
+        def __init__(self, value):
+            self.value = value
+
+        def __str__(self):
+            return "{{ union_name }}.{{ variant }}({})".format(self.value)
+
+        def __eq__(self, other):
+            return isinstance(other, {{ union_name }}.{{ variant }}) and self.value == other.value
+    {% endfor %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
@@ -33,7 +33,11 @@ rust_call_with_error(
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg.name()|lower_py(arg.type_()) }}
+        {%- if arg.is_borrowed_bytes() %}
+        {{- arg.name()|lower_borrowed_py(arg.type_()) }}
+        {%- else %}
+        {{- arg.name()|lower_py(arg.type_(), ci) }}
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -18,14 +18,27 @@ import ctypes
 import enum
 import struct
 import contextlib
+import datetime
+import threading
+{%- for imp in config.custom_type_imports() %}
+import {{ imp }}
+{%- endfor %}
+{%- for module in config.external_module_imports(ci) %}
+import {{ module }}
+{%- endfor %}
 
 {% include "RustBufferTemplate.py" %}
 {% include "RustBufferStream.py" %}
 {% include "RustBufferBuilder.py" %}
+{% include "CustomTypeTemplate.py" %}
 
 # Error definitions
 {% include "ErrorTemplate.py" %}
 
+{%- for rec in ci.iter_record_definitions() %}
+{% include "RecordFfiStructTemplate.py" %}
+{%- endfor %}
+
 {% include "NamespaceLibraryTemplate.py" %}
 
 # Public interface members begin here.
@@ -38,22 +51,62 @@ import contextlib
 {% include "RecordTemplate.py" %}
 {% endfor %}
 
+{% include "UnionTemplate.py" %}
+
+{%- for c in ci.iter_constant_definitions() %}
+{{ c.name()|const_name_py }} = {{ c.value()|literal_py }}
+{%- endfor %}
+
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.py" %}
 {% endfor %}
 
+{%- if !ci.iter_object_definitions().is_empty() %}
+# Guards the count of calls in progress on each object, so that an object that's destroyed
+# during a call is only freed once the call returns. It's reentrant in case an object is
+# garbage-collected, and hence destroyed, on a thread that's holding it.
+_UniFFIObjectLock = threading.RLock()
+{% endif %}
+
 {% for obj in ci.iter_object_definitions() %}
 {% include "ObjectTemplate.py" %}
 {% endfor %}
 
+def assert_no_leaks():
+    """
+    Check that every object and buffer passed across the FFI has been freed.
+
+    This is intended for use in tests, and only works if the Rust code was built with
+    the `leak-tracking` feature of the `uniffi` crate.
+    """
+    _retval = rust_call_with_error(InternalError, _UniFFILib.{{ ci.ffi_leak_report().name() }})
+    leaks = {{ "_retval"|lift_py(Type::String, ci) }}
+    if leaks:
+        raise AssertionError("Leaked across the FFI: {}".format(leaks))
+
 __all__ = [
     "InternalError",
+    "WrongThreadError",
+    "ReentrantCallError",
+    "InvalidArgumentError",
+    "RustPanic",
+    "assert_no_leaks",
     {%- for e in ci.iter_enum_definitions() %}
     "{{ e.name()|class_name_py }}",
     {%- endfor %}
     {%- for record in ci.iter_record_definitions() %}
     "{{ record.name()|class_name_py }}",
     {%- endfor %}
+    {%- for typ in ci.iter_types() %}
+    {%- match typ %}
+    {%- when Type::Union with (name, members) %}
+    "{{ typ.union_name()|class_name_py }}",
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+    {%- for c in ci.iter_constant_definitions() %}
+    "{{ c.name()|const_name_py }}",
+    {%- endfor %}
     {%- for func in ci.iter_function_definitions() %}
     "{{ func.name()|fn_name_py }}",
     {%- endfor %}
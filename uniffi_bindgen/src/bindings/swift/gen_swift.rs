@@ -2,6 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
 use std::path::Path;
 
 use anyhow::Result;
@@ -9,6 +10,7 @@ use askama::Template;
 use heck::{CamelCase, MixedCase};
 use serde::{Deserialize, Serialize};
 
+use crate::bindings::CustomTypeConfig;
 use crate::interface::*;
 use crate::MergeWith;
 
@@ -19,6 +21,9 @@ use crate::MergeWith;
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     module_name: Option<String>,
+    cdylib_name: Option<String>,
+    #[serde(default)]
+    custom_types: HashMap<String, CustomTypeConfig>,
 }
 impl Config {
     pub fn module_name(&self) -> String {
@@ -27,18 +32,41 @@ impl Config {
             None => "uniffi".into(),
         }
     }
+    pub fn cdylib_name(&self) -> String {
+        match self.cdylib_name.as_ref() {
+            Some(name) => name.clone(),
+            None => "uniffi".into(),
+        }
+    }
     pub fn modulemap_filename(&self) -> String {
         format!("{}.modulemap", self.module_name())
     }
     pub fn header_filename(&self) -> String {
         format!("{}-Bridging-Header.h", self.module_name())
     }
+    /// How to expose a custom type from the UDL, defaulting to the builtin type that it wraps.
+    pub fn custom_type(&self, name: &str) -> CustomTypeConfig {
+        self.custom_types.get(name).cloned().unwrap_or_default()
+    }
+    /// The imports needed by all the custom types, sorted and without duplicates.
+    pub fn custom_type_imports(&self) -> Vec<String> {
+        let mut imports: Vec<String> = self
+            .custom_types
+            .values()
+            .flat_map(|c| c.imports().iter().cloned())
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
 }
 
 impl From<&ComponentInterface> for Config {
     fn from(ci: &ComponentInterface) -> Self {
         Config {
             module_name: Some(format!("uniffi_{}", ci.namespace())),
+            cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            custom_types: HashMap::new(),
         }
     }
 }
@@ -47,6 +75,8 @@ impl MergeWith for Config {
     fn merge_with(&self, other: &Self) -> Self {
         Config {
             module_name: self.module_name.merge_with(&other.module_name),
+            cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
         }
     }
 }
@@ -105,6 +135,41 @@ impl<'config, 'ci> SwiftWrapper<'config, 'ci> {
             is_testing,
         }
     }
+
+    /// The types that are lifted, lowered and serialized using helper functions, sorted so that
+    /// the generated code doesn't change from one run to the next.
+    pub fn helper_types(&self) -> Vec<Type> {
+        let mut types: Vec<Type> = self
+            .ci
+            .iter_types()
+            .into_iter()
+            .filter(uses_helpers)
+            .collect();
+        types.sort_by_key(|t| t.canonical_name());
+        types
+    }
+
+    pub fn uses_helpers(&self, type_: &Type) -> bool {
+        uses_helpers(type_)
+    }
+}
+
+/// Whether a type is lifted, lowered and serialized using helper functions, rather than by
+/// methods from its conformance to `ViaFfi`.
+///
+/// Swift tuples are structural types that can't conform to protocols, and durations are exposed
+/// as `TimeInterval`, which is just another name for `Double` and so already conforms as an `f64`.
+/// Optionals, sequences and maps that contain them need helpers too, since the generic
+/// conformances of `Optional`, `Array` and `Dictionary` would use the wrong ones for their
+/// contents, as do custom types that wrap a duration.
+fn uses_helpers(type_: &Type) -> bool {
+    match type_ {
+        Type::Duration | Type::Tuple { .. } => true,
+        Type::Optional(inner) | Type::Sequence(inner) => uses_helpers(inner),
+        Type::Map(key, value) => uses_helpers(key) || uses_helpers(value),
+        Type::Custom { builtin, .. } => uses_helpers(builtin),
+        _ => false,
+    }
 }
 
 /// Filters for our Askama templates above. These output C (for the bridging
@@ -128,14 +193,22 @@ mod filters {
             Type::Float64 => "Double".into(),
             Type::Boolean => "Bool".into(),
             Type::String => "String".into(),
+            Type::Bytes => "Data".into(),
+            Type::Timestamp => "Date".into(),
+            Type::Duration => "TimeInterval".into(),
             Type::Enum(name)
             | Type::Record(name)
             | Type::Object(name)
             | Type::Error(name)
-            | Type::CallbackInterface(name) => class_name_swift(name)?,
+            | Type::CallbackInterface(name)
+            | Type::Custom { name, .. }
+            | Type::External { name, .. } => class_name_swift(name)?,
             Type::Optional(type_) => format!("{}?", type_swift(type_)?),
             Type::Sequence(type_) => format!("[{}]", type_swift(type_)?),
-            Type::Map(type_) => format!("[String:{}]", type_swift(type_)?),
+            Type::Union(..) => class_name_swift(&type_.union_name())?,
+            // Tuples are declared as a typealias named after their typedef.
+            Type::Tuple { name, .. } => class_name_swift(name)?,
+            Type::Map(k, v) => format!("[{}:{}]", type_swift(k)?, type_swift(v)?),
         })
     }
 
@@ -157,10 +230,30 @@ mod filters {
             FFIType::RustBuffer => "RustBuffer".into(),
             FFIType::RustError => "NativeRustError".into(),
             FFIType::ForeignBytes => "ForeignBytes".into(),
+            FFIType::Struct(name) => format!("{}FfiStruct", class_name_swift(name)?),
             FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
         })
     }
 
+    /// Quote a string as a Swift string literal, escaping the characters that Swift would
+    /// otherwise interpret, including the backslash that starts an interpolation like `\(x)`.
+    fn string_literal_swift(s: &str) -> String {
+        let mut out = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
     pub fn literal_swift(literal: &Literal) -> Result<String, askama::Error> {
         fn typed_number(type_: &Type, num_str: String) -> Result<String, askama::Error> {
             Ok(match type_ {
@@ -182,7 +275,7 @@ mod filters {
 
         Ok(match literal {
             Literal::Boolean(v) => format!("{}", v),
-            Literal::String(s) => format!("\"{}\"", s),
+            Literal::String(s) => string_literal_swift(s),
             Literal::Null => "nil".into(),
             Literal::EmptySequence => "[]".into(),
             Literal::EmptyMap => "[:]".into(),
@@ -210,15 +303,30 @@ mod filters {
     /// Lower a Swift type into an FFI type.
     ///
     /// This is used to pass arguments over the FFI, from Swift to Rust.
-    pub fn lower_swift(name: &dyn fmt::Display, _type_: &Type) -> Result<String, askama::Error> {
-        Ok(format!("{}.lower()", var_name_swift(name)?))
+    pub fn lower_swift(name: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            // Some types can't use methods from `ViaFfi`, so they have helper functions instead.
+            _ if uses_helpers(type_) => format!(
+                "lower{}({})",
+                class_name_swift(&type_.canonical_name())?,
+                var_name_swift(name)?
+            ),
+            _ => format!("{}.lower()", var_name_swift(name)?),
+        })
     }
 
     /// Lift a Swift type from an FFI type.
     ///
     /// This is used to receive values over the FFI, from Rust to Swift.
     pub fn lift_swift(name: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
-        Ok(format!("{}.lift({})", type_swift(type_)?, name))
+        Ok(match type_ {
+            _ if uses_helpers(type_) => format!(
+                "lift{}({})",
+                class_name_swift(&type_.canonical_name())?,
+                name
+            ),
+            _ => format!("{}.lift({})", type_swift(type_)?, name),
+        })
     }
 
     /// Read a Swift type from a byte buffer.
@@ -226,7 +334,52 @@ mod filters {
     /// This is used to receive values over the FFI, when they're part of a complex type
     /// that is passed by serializing into bytes.
     pub fn read_swift(name: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
-        Ok(format!("{}.read(from: {})", type_swift(type_)?, name))
+        Ok(match type_ {
+            _ if uses_helpers(type_) => format!(
+                "read{}(from: {})",
+                class_name_swift(&type_.canonical_name())?,
+                name
+            ),
+            _ => format!("{}.read(from: {})", type_swift(type_)?, name),
+        })
+    }
+
+    /// Write a Swift type into a byte buffer.
+    ///
+    /// This is used to send values over the FFI, when they're part of a complex type
+    /// that is passed by serializing into bytes.
+    pub fn write_swift(
+        name: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+        type_: &Type,
+    ) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            _ if uses_helpers(type_) => format!(
+                "write{}({}, into: {})",
+                class_name_swift(&type_.canonical_name())?,
+                name,
+                target
+            ),
+            _ => format!("{}.write(into: {})", name, target),
+        })
+    }
+
+    /// Render a doc comment from the UDL as Swift `///` comments, followed by a newline and
+    /// the given amount of indentation so that the documented declaration lines up beneath it.
+    pub fn doc_comment_swift(docs: &Option<&str>, spaces: &usize) -> Result<String, askama::Error> {
+        Ok(match docs {
+            None => "".into(),
+            Some(docs) => {
+                let indent = " ".repeat(*spaces);
+                let mut out = String::new();
+                for line in docs.lines() {
+                    out.push_str(format!("/// {}", line).trim_end());
+                    out.push('\n');
+                    out.push_str(&indent);
+                }
+                out
+            }
+        })
     }
 
     pub fn enum_variant_swift(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
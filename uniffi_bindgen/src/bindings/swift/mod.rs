@@ -14,7 +14,7 @@ use std::{
 pub mod gen_swift;
 pub use gen_swift::{BridgingHeader, Config, ModuleMap, SwiftWrapper};
 
-use super::super::interface::ComponentInterface;
+use super::super::interface::{ComponentInterface, Type};
 
 pub struct Bindings {
     header: String,
@@ -86,6 +86,14 @@ pub fn generate_bindings(
     is_testing: bool,
 ) -> Result<Bindings> {
     use askama::Template;
+    check_tuple_usage(ci)?;
+    if ci
+        .iter_types()
+        .iter()
+        .any(|t| matches!(t, Type::External { .. }))
+    {
+        bail!("Swift bindings don't support external types yet");
+    }
     let header = BridgingHeader::new(config, &ci)
         .render()
         .map_err(|_| anyhow!("failed to render Swift bridging header"))?;
@@ -95,6 +103,49 @@ pub fn generate_bindings(
     Ok(Bindings { header, library })
 }
 
+/// Check that tuples are only used where the Swift bindings can support them.
+///
+/// Swift tuples are structural types that can't conform to protocols, so they can't be
+/// `Hashable`. That rules them out (even when nested inside other types) as dictionary keys,
+/// union members and record fields, all of which need to be hashed. Elsewhere, including
+/// inside optionals, sequences, dictionary values and other tuples, they're serialized using
+/// helper functions rather than protocols.
+fn check_tuple_usage(ci: &ComponentInterface) -> Result<()> {
+    for type_ in ci.iter_types() {
+        let contains_tuple = match &type_ {
+            Type::Map(key, _) => contains_tuple(key),
+            Type::Union(_, members) => members.iter().any(contains_tuple),
+            _ => false,
+        };
+        if contains_tuple {
+            bail!(
+                "Swift bindings don't support tuples in dictionary keys or unions ({})",
+                type_.canonical_name()
+            );
+        }
+    }
+    for rec in ci.iter_record_definitions() {
+        if rec.fields().iter().any(|f| contains_tuple(&f.type_())) {
+            bail!(
+                "Swift bindings don't support tuples in record fields ({})",
+                rec.name()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn contains_tuple(type_: &Type) -> bool {
+    match type_ {
+        Type::Tuple { .. } => true,
+        Type::Optional(inner) | Type::Sequence(inner) => contains_tuple(inner),
+        Type::Map(key, value) => contains_tuple(key) || contains_tuple(value),
+        Type::Union(_, members) => members.iter().any(contains_tuple),
+        Type::Custom { builtin, .. } => contains_tuple(builtin),
+        _ => false,
+    }
+}
+
 fn generate_module_map(
     config: &Config,
     ci: &ComponentInterface,
@@ -140,7 +191,7 @@ pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path
         .arg("-parse-as-library")
         .arg("-L")
         .arg(&out_path)
-        .arg(format!("-luniffi_{}", ci.namespace()))
+        .arg(format!("-l{}", config.cdylib_name()))
         .arg("-Xcc")
         .arg(module_map_file_option)
         .arg(source_file)
@@ -154,6 +205,13 @@ pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path
     Ok(())
 }
 
+/// Swift code that fails the script if any objects or buffers from the component have leaked.
+///
+/// The call is qualified with the module name, so the script must import the component's module.
+pub fn leak_check(_config: &Config, ci: &ComponentInterface) -> String {
+    format!("{}.assertNoLeaks()\n", ci.namespace())
+}
+
 pub fn run_script(out_dir: &Path, script_file: &Path) -> Result<()> {
     let mut cmd = Command::new("swift");
 
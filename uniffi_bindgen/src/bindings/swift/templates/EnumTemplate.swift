@@ -1,4 +1,4 @@
-public enum {{ e.name()|class_name_swift }}: ViaFfi {
+{{ e.docs()|doc_comment_swift(0) }}public enum {{ e.name()|class_name_swift }}: ViaFfi {
     {% for variant in e.variants() %}
     case {{ variant|enum_variant_swift }}
     {% endfor %}
@@ -16,6 +16,8 @@ enum InternalError: RustError {
     case incompleteData
     case unexpectedOptionalTag
     case unexpectedEnumCase
+    case unexpectedUnionTag
+    case unexpectedNanoseconds
     case emptyResult
     case unknown(message: String = "")
 
@@ -25,6 +27,8 @@ enum InternalError: RustError {
         case .incompleteData: return "The buffer still has data after lifting its containing value"
         case .unexpectedOptionalTag: return "Unexpected optional tag; should be 0 or 1"
         case .unexpectedEnumCase: return "Raw enum value doesn't match any cases"
+        case .unexpectedUnionTag: return "Unexpected union tag; doesn't match any member types"
+        case .unexpectedNanoseconds: return "Nanoseconds should be less than one second"
         case .emptyResult: return "Unexpected nil returned from FFI function"
         case let .unknown(message): return "FFI function returned unknown error: \(message)"
         }
@@ -46,8 +50,96 @@ enum InternalError: RustError {
     }
 }
 
+/// Thrown when calling a method of a thread-confined object on a thread other than the one
+/// that created it.
+public struct WrongThreadError: LocalizedError {
+    public let message: String
+
+    public var errorDescription: String? {
+        return message
+    }
+}
+
+/// Thrown when calling a method of a thread-confined object while it's already in use on the
+/// same thread, for example from a callback that it's making.
+public struct ReentrantCallError: LocalizedError {
+    public let message: String
+
+    public var errorDescription: String? {
+        return message
+    }
+}
+
+/// Thrown when Rust code panics.
+///
+/// As well as the panic message, this has the location of the panic in the Rust code, the name of
+/// the thread that panicked, and a backtrace if the Rust code was built with the `panic-backtraces`
+/// feature of the `uniffi` crate. Those are `nil` if they're unknown.
+public struct RustPanic: LocalizedError {
+    public let message: String
+    public let file: String?
+    public let line: Int32?
+    public let column: Int32?
+    public let threadName: String?
+    public let backtrace: String?
+
+    public var errorDescription: String? {
+        guard let file = file, let line = line, let column = column else {
+            return message
+        }
+        return "\(message) at \(file):\(line):\(column)"
+    }
+
+    // The panic hook records a structured report of the panic, which we take
+    // so that it doesn't get mixed up with the next one.
+    fileprivate static func take(message: String) throws -> RustPanic {
+        let rbuf = try rustCall(InternalError.unknown()) { err in
+            {{ ci.ffi_take_panic_report().name() }}(err)
+        }
+        defer {
+            rbuf.deallocate()
+        }
+        let reader = Reader(data: Data(rustBuffer: rbuf))
+        if !reader.hasRemaining() {
+            return RustPanic(message: message, file: nil, line: nil, column: nil, threadName: nil, backtrace: nil)
+        }
+        let message = try String.read(from: reader)
+        let file = try String.read(from: reader)
+        let line = try Int32.read(from: reader)
+        let column = try Int32.read(from: reader)
+        let threadName = try String.read(from: reader)
+        let backtrace = try String.read(from: reader)
+        return RustPanic(
+            message: message,
+            file: file,
+            line: line,
+            column: column,
+            threadName: threadName.isEmpty ? nil : threadName,
+            backtrace: backtrace.isEmpty ? nil : backtrace
+        )
+    }
+}
+
+// Record structured reports of Rust panics, for the `RustPanic` error. Swift initializes globals
+// lazily, so this happens before the first call into Rust.
+private let panicHookInstalled: Bool = {
+    var err = NativeRustError(code: 0, message: nil)
+    {{ ci.ffi_install_panic_hook().name() }}(&err)
+    return true
+}()
+
+/// Thrown when Rust couldn't lift one of the arguments that it was passed, which means that
+/// these bindings have a bug or are out of date.
+public struct InvalidArgumentError: LocalizedError {
+    public let message: String
+
+    public var errorDescription: String? {
+        return message
+    }
+}
+
 {% for e in ci.iter_error_definitions() %}
-public enum {{e.name()}}: RustError {
+{{ e.docs()|doc_comment_swift(0) }}public enum {{e.name()}}: RustError {
     case NoError
     {% for value in e.values() %}
     case {{value}}(message: String)
@@ -114,8 +206,28 @@ func nullableRustCall<T, E: RustError>(_ err: E, _ cb: (UnsafeMutablePointer<Nat
 
 @discardableResult
 func tryUnwrap<T, E: RustError>(_ err: E, _ callback: (UnsafeMutablePointer<NativeRustError>) throws -> T?) throws -> T? {
+    _ = panicHookInstalled
     var native_err = NativeRustError(code: 0, message: nil)
     let returnedVal = try callback(&native_err)
+    // These are the same for every call, whatever errors it's declared to throw.
+    if (-4 ... -1).contains(native_err.code) {
+        let message = native_err.message!
+        defer {
+            try! rustCall(InternalError.unknown()) { err in
+                {{ ci.ffi_string_free().name() }}(message, err)
+            }
+        }
+        switch native_err.code {
+        case -1:
+            throw try RustPanic.take(message: String(cString: message))
+        case -2:
+            throw WrongThreadError(message: String(cString: message))
+        case -4:
+            throw ReentrantCallError(message: String(cString: message))
+        default:
+            throw InvalidArgumentError(message: String(cString: message))
+        }
+    }
     if let retErr = try E.fromConsuming(native_err) {
         throw retErr
     }
@@ -1,7 +1,10 @@
 
 public protocol {{ obj.name() }}Protocol {
+    {% for prop in obj.properties() -%}
+    {{ prop.docs()|doc_comment_swift(4) }}var {{ prop.name()|var_name_swift }}: {{ prop.type_()|type_swift }} { get{% if !prop.readonly() %} set{% endif %} }
+    {% endfor -%}
     {% for meth in obj.methods() -%}
-    func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
+    {{ meth.docs()|doc_comment_swift(4) }}func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %} -> {{ return_type|type_swift -}}
     {%- else -%}
@@ -9,7 +12,7 @@ public protocol {{ obj.name() }}Protocol {
     {% endfor %}
 }
 
-public class {{ obj.name()|class_name_swift }}: {{ obj.name() }}Protocol {
+{{ obj.class_docs().as_deref()|doc_comment_swift(0) }}public class {{ obj.name()|class_name_swift }}: {{ obj.name() }}Protocol {
     private let handle: UInt64
 
     private init(fromRawHandle handle: UInt64) {
@@ -36,20 +39,108 @@ public class {{ obj.name()|class_name_swift }}: {{ obj.name() }}Protocol {
     }
     {% endfor %}
 
+    {% for meth in obj.static_methods() -%}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) -%}
+    {{ meth.docs()|doc_comment_swift(4) }}public static func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
+        let _retval = {% call swift::to_ffi_call(meth) %}
+        return {% call swift::try(meth) %} {{ "_retval"|lift_swift(return_type) }}
+    }
+
+    {%- when None -%}
+    {{ meth.docs()|doc_comment_swift(4) }}public static func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+        {% call swift::to_ffi_call(meth) %}
+    }
+    {%- endmatch %}
+    {% endfor %}
+
+    {% for prop in obj.properties() -%}
+    {{ prop.docs()|doc_comment_swift(4) }}public var {{ prop.name()|var_name_swift }}: {{ prop.type_()|type_swift }} {
+        get {
+            let _retval = {% call swift::to_ffi_call_with_prefix("self.handle", prop.getter()) %}
+            return {% call swift::try(prop.getter()) %} {{ "_retval"|lift_swift(prop.type_()) }}
+        }
+        {%- match prop.setter() %}
+        {%- when Some with (setter) %}
+        set(value) {
+            {% call swift::to_ffi_call_with_prefix("self.handle", setter) %}
+        }
+        {%- when None %}
+        {%- endmatch %}
+    }
+    {% endfor %}
+
     {# // TODO: Maybe merge the two templates (i.e the one with a return type and the one without) #}
     {% for meth in obj.methods() -%}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
-    public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
+    {{ meth.docs()|doc_comment_swift(4) }}public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
         let _retval = {% call swift::to_ffi_call_with_prefix("self.handle", meth) %}
         return {% call swift::try(meth) %} {{ "_retval"|lift_swift(return_type) }}
     }
 
     {%- when None -%}
-    public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+    {{ meth.docs()|doc_comment_swift(4) }}public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
         {% call swift::to_ffi_call_with_prefix("self.handle", meth) %}
     }
     {%- endmatch %}
     {% endfor %}
-}
\ No newline at end of file
+}
+
+{%- match obj.ffi_object_debug() %}
+{%- when Some with (func) %}
+
+extension {{ obj.name()|class_name_swift }}: CustomDebugStringConvertible {
+    public var debugDescription: String {
+        let _retval = try! rustCall(InternalError.unknown()) { err in
+            {{ func.name() }}(self.handle, err)
+        }
+        return try! {{ "_retval"|lift_swift(Type::String) }}
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.ffi_object_display() %}
+{%- when Some with (func) %}
+
+extension {{ obj.name()|class_name_swift }}: CustomStringConvertible {
+    public var description: String {
+        let _retval = try! rustCall(InternalError.unknown()) { err in
+            {{ func.name() }}(self.handle, err)
+        }
+        return try! {{ "_retval"|lift_swift(Type::String) }}
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.ffi_object_eq() %}
+{%- when Some with (func) %}
+
+extension {{ obj.name()|class_name_swift }}: Equatable {
+    public static func == (lhs: {{ obj.name()|class_name_swift }}, rhs: {{ obj.name()|class_name_swift }}) -> Bool {
+        let _retval = try! rustCall(InternalError.unknown()) { err in
+            {{ func.name() }}(lhs.handle, rhs.handle, err)
+        }
+        return try! {{ "_retval"|lift_swift(Type::Boolean) }}
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.ffi_object_hash() %}
+{%- when Some with (func) %}
+
+extension {{ obj.name()|class_name_swift }}: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        let _retval = try! rustCall(InternalError.unknown()) { err in
+            {{ func.name() }}(self.handle, err)
+        }
+        hasher.combine(_retval)
+    }
+}
+{%- when None %}
+{%- endmatch %}
@@ -1,6 +1,6 @@
-public struct {{ rec.name()|class_name_swift }}:  ViaFfiUsingByteBuffer, ViaFfi, Equatable, Hashable {
+{{ rec.docs()|doc_comment_swift(0) }}public struct {{ rec.name()|class_name_swift }}: {% if !rec.is_plain_old_data() %} ViaFfiUsingByteBuffer,{% endif %} ViaFfi, Equatable, Hashable {
     {%- for field in rec.fields() %}
-    let {{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}
+    {{ field.docs()|doc_comment_swift(4) }}let {{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}
     {%- endfor %}
 
     // Default memberwise initializers are never public by default, so we
@@ -29,6 +29,28 @@ public struct {{ rec.name()|class_name_swift }}:  ViaFfiUsingByteBuffer, ViaFfi,
         return true
     }
 
+    {%- if rec.is_plain_old_data() %}
+    // Records containing only numbers and booleans are passed over the FFI by value,
+    // as a C struct declared in the bridging header.
+    typealias FfiType = {{ rec.name()|class_name_swift }}FfiStruct
+
+    static func lift(_ v: FfiType) throws -> {{ rec.name()|class_name_swift }} {
+        return try {{ rec.name()|class_name_swift }}(
+            {%- for field in rec.fields() %}
+            {{ field.name()|var_name_swift }}: {{ "v.{}"|format(field.name())|lift_swift(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        )
+    }
+
+    func lower() -> FfiType {
+        return FfiType(
+            {%- for field in rec.fields() %}
+            {{ field.name() }}: {{ field.name()|lower_swift(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        )
+    }
+
+    {% endif -%}
     static func read(from buf: Reader) throws -> {{ rec.name()|class_name_swift }} {
         return try {{ rec.name()|class_name_swift }}(
             {%- for field in rec.fields() %}
@@ -39,7 +61,7 @@ public struct {{ rec.name()|class_name_swift }}:  ViaFfiUsingByteBuffer, ViaFfi,
 
     func write(into buf: Writer) {
         {%- for field in rec.fields() %}
-        {{ field.name()|var_name_swift }}.write(into: buf)
+        {{ field.name()|var_name_swift|write_swift("buf", field.type_()) }}
         {%- endfor %}
     }
 
@@ -204,6 +204,234 @@ extension String: ViaFfi {
     }
 }
 
+// Byte arrays are serialized like any other sequence, but copy their contents in bulk.
+extension Data: ViaFfiUsingByteBuffer, ViaFfi, Serializable {
+    static func read(from buf: Reader) throws -> Self {
+        let len: Int32 = try buf.readInt()
+        return Data(try buf.readBytes(count: Int(len)))
+    }
+
+    func write(into buf: Writer) {
+        buf.writeInt(Int32(self.count))
+        buf.writeBytes(self)
+    }
+}
+
+// Timestamps are serialized as whole seconds followed by nanoseconds.
+// We only emit the extension for them if they're used.
+// Custom types are emitted here too, since they need to know how their builtin type is passed,
+// as are the sum types for anonymous unions.
+{%- for typ in ci.iter_types() %}
+{%- match typ %}
+{%- when Type::Timestamp %}
+
+extension Date: ViaFfiUsingByteBuffer, ViaFfi, Serializable {
+    static func read(from buf: Reader) throws -> Self {
+        let seconds: Int64 = try buf.readInt()
+        let nanos: UInt32 = try buf.readInt()
+        guard nanos < 1_000_000_000 else {
+            throw InternalError.unexpectedNanoseconds
+        }
+        return Date(timeIntervalSince1970: Double(seconds) + Double(nanos) / 1.0e9)
+    }
+
+    func write(into buf: Writer) {
+        let interval = self.timeIntervalSince1970
+        var seconds = interval.rounded(.down)
+        var nanos = ((interval - seconds) * 1.0e9).rounded()
+        if nanos >= 1.0e9 {
+            seconds += 1
+            nanos = 0
+        }
+        buf.writeInt(Int64(seconds))
+        buf.writeInt(UInt32(nanos))
+    }
+}
+{%- when Type::Custom with { name, builtin } %}
+{%- let custom = config.custom_type(name) %}
+{%- match custom.type_name() %}
+{%- when Some with (type_name) %}
+
+// The custom type {{ name }} is passed over the FFI as the builtin type {{ builtin|type_swift }}.
+public typealias {{ typ|type_swift }} = {{ type_name }}
+{%- if !self.uses_helpers(builtin) %}
+
+extension {{ type_name }}: ViaFfi {
+    typealias FfiType = {{ builtin|type_swift }}.FfiType
+
+    static func read(from buf: Reader) throws -> {{ type_name }} {
+        let builtinValue = try {{ "buf"|read_swift(builtin) }}
+        return {{ custom.into_custom("builtinValue") }}
+    }
+
+    func write(into buf: Writer) {
+        let builtinValue = {{ custom.from_custom("self") }}
+        builtinValue.write(into: buf)
+    }
+
+    static func lift(_ v: FfiType) throws -> {{ type_name }} {
+        let builtinValue = try {{ "v"|lift_swift(builtin) }}
+        return {{ custom.into_custom("builtinValue") }}
+    }
+
+    func lower() -> FfiType {
+        let builtinValue = {{ custom.from_custom("self") }}
+        return {{ "builtinValue"|lower_swift(builtin) }}
+    }
+}
+{%- endif %}
+{%- when None %}
+
+// The custom type {{ name }} is exposed as the builtin type {{ builtin|type_swift }} that it wraps.
+public typealias {{ typ|type_swift }} = {{ builtin|type_swift }}
+{%- endmatch %}
+{%- when Type::Tuple with { name, elements } %}
+
+// The tuple type {{ name }}, which is passed over the FFI using the helper functions below.
+public typealias {{ typ|type_swift }} = (
+    {%- for element_type in elements %}
+    {{ element_type|type_swift }}{% if !loop.last %},{% endif %}
+    {%- endfor %}
+)
+{%- when Type::Union with (name, members) %}
+
+// The union {{ typ|type_swift }}, with a case for each member type.
+public enum {{ typ|type_swift }}: ViaFfiUsingByteBuffer, ViaFfi, Equatable, Hashable {
+    {%- for (variant, member_type) in typ.union_variants() %}
+    case {{ variant|enum_variant_swift }}({{ member_type|type_swift }})
+    {%- endfor %}
+
+    static func read(from buf: Reader) throws -> {{ typ|type_swift }} {
+        let tag: UInt8 = try buf.readInt()
+        switch tag {
+        {%- for (variant, member_type) in typ.union_variants() %}
+        case {{ loop.index }}: return .{{ variant|enum_variant_swift }}(try {{ "buf"|read_swift(member_type) }})
+        {%- endfor %}
+        default: throw InternalError.unexpectedUnionTag
+        }
+    }
+
+    func write(into buf: Writer) {
+        switch self {
+        {%- for (variant, member_type) in typ.union_variants() %}
+        case let .{{ variant|enum_variant_swift }}(value):
+            buf.writeInt(UInt8({{ loop.index }}))
+            {{ "value"|write_swift("buf", member_type) }}
+        {%- endfor %}
+        }
+    }
+}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+// Durations are exposed as `TimeInterval`, which is the same type as `Double`, and Swift tuples
+// can't conform to protocols, so those types are lifted, lowered and serialized using helper
+// functions instead, as are any optionals, sequences, maps and custom types that contain them.
+{%- for typ in self.helper_types() %}
+{%- let helper_name = typ.canonical_name()|class_name_swift %}
+
+fileprivate func read{{ helper_name }}(from buf: Reader) throws -> {{ typ|type_swift }} {
+    {%- match typ %}
+    {%- when Type::Duration %}
+    let seconds: UInt64 = try buf.readInt()
+    let nanos: UInt32 = try buf.readInt()
+    guard nanos < 1_000_000_000 else {
+        throw InternalError.unexpectedNanoseconds
+    }
+    return Double(seconds) + Double(nanos) / 1.0e9
+    {%- when Type::Optional with (inner) %}
+    switch try buf.readInt() as Int8 {
+    case 0: return nil
+    case 1: return try {{ "buf"|read_swift(inner) }}
+    default: throw InternalError.unexpectedOptionalTag
+    }
+    {%- when Type::Sequence with (inner) %}
+    let len: Int32 = try buf.readInt()
+    var seq = {{ typ|type_swift }}()
+    seq.reserveCapacity(Int(len))
+    for _ in 0..<len {
+        seq.append(try {{ "buf"|read_swift(inner) }})
+    }
+    return seq
+    {%- when Type::Map with (key, value) %}
+    let len: Int32 = try buf.readInt()
+    var dict = {{ typ|type_swift }}()
+    dict.reserveCapacity(Int(len))
+    for _ in 0..<len {
+        dict[try {{ "buf"|read_swift(key) }}] = try {{ "buf"|read_swift(value) }}
+    }
+    return dict
+    {%- when Type::Custom with { name, builtin } %}
+    let builtinValue = try {{ "buf"|read_swift(builtin) }}
+    return {{ config.custom_type(name).into_custom("builtinValue") }}
+    {%- when Type::Tuple with { name, elements } %}
+    return try (
+        {%- for element_type in elements %}
+        {{ "buf"|read_swift(element_type) }}{% if !loop.last %},{% endif %}
+        {%- endfor %}
+    )
+    {%- else %}
+    {%- endmatch %}
+}
+
+fileprivate func write{{ helper_name }}(_ v: {{ typ|type_swift }}, into buf: Writer) {
+    {%- match typ %}
+    {%- when Type::Duration %}
+    precondition(v >= 0, "Duration must not be negative")
+    var seconds = v.rounded(.down)
+    var nanos = ((v - seconds) * 1.0e9).rounded()
+    if nanos >= 1.0e9 {
+        seconds += 1
+        nanos = 0
+    }
+    buf.writeInt(UInt64(seconds))
+    buf.writeInt(UInt32(nanos))
+    {%- when Type::Optional with (inner) %}
+    guard let value = v else {
+        buf.writeInt(Int8(0))
+        return
+    }
+    buf.writeInt(Int8(1))
+    {{ "value"|write_swift("buf", inner) }}
+    {%- when Type::Sequence with (inner) %}
+    buf.writeInt(Int32(v.count))
+    for item in v {
+        {{ "item"|write_swift("buf", inner) }}
+    }
+    {%- when Type::Map with (key, value) %}
+    buf.writeInt(Int32(v.count))
+    for (key, value) in v {
+        {{ "key"|write_swift("buf", key) }}
+        {{ "value"|write_swift("buf", value) }}
+    }
+    {%- when Type::Custom with { name, builtin } %}
+    let builtinValue = {{ config.custom_type(name).from_custom("v") }}
+    {{ "builtinValue"|write_swift("buf", builtin) }}
+    {%- when Type::Tuple with { name, elements } %}
+    {%- for element_type in elements %}
+    {{ "v.{}"|format(loop.index0)|write_swift("buf", element_type) }}
+    {%- endfor %}
+    {%- else %}
+    {%- endmatch %}
+}
+
+fileprivate func lift{{ helper_name }}(_ buf: RustBuffer) throws -> {{ typ|type_swift }} {
+    let reader = Reader(data: Data(rustBuffer: buf))
+    let value = try read{{ helper_name }}(from: reader)
+    if reader.hasRemaining() {
+        throw InternalError.incompleteData
+    }
+    buf.deallocate()
+    return value
+}
+
+fileprivate func lower{{ helper_name }}(_ v: {{ typ|type_swift }}) -> RustBuffer {
+    let writer = Writer()
+    write{{ helper_name }}(v, into: writer)
+    return RustBuffer(bytes: writer.bytes)
+}
+{%- endfor %}
 
 extension Bool: ViaFfi {
     typealias FfiType = Int8
@@ -364,13 +592,13 @@ extension Array: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Element: Seri
     }
 }
 
-extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key == String, Value: Serializable {
+extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key: Serializable, Value: Serializable {
     static func read(from buf: Reader) throws -> Self {
         let len: Int32 = try buf.readInt()
-        var dict = [String: Value]()
+        var dict = [Key: Value]()
         dict.reserveCapacity(Int(len))
         for _ in 0..<len {
-            dict[try String.read(from: buf)] = try Value.read(from: buf)
+            dict[try Key.read(from: buf)] = try Value.read(from: buf)
         }
         return dict
     }
@@ -25,3 +25,25 @@ extension ForeignBytes {
         self.init(len: Int32(bufferPointer.count), data: bufferPointer.baseAddress, padding: 0, padding2: 0)
     }
 }
+
+// Helpers for passing borrowed strings and byte arrays to rust without copying them.
+// The `ForeignBytes` is only valid for the duration of the closure.
+
+extension String {
+    func withForeignBytes<T>(_ body: (ForeignBytes) throws -> T) rethrows -> T {
+        var value = self
+        return try value.withUTF8 { try body(ForeignBytes(bufferPointer: $0)) }
+    }
+}
+
+extension Array where Element == UInt8 {
+    func withForeignBytes<T>(_ body: (ForeignBytes) throws -> T) rethrows -> T {
+        return try withUnsafeBufferPointer { try body(ForeignBytes(bufferPointer: $0)) }
+    }
+}
+
+extension Data {
+    func withForeignBytes<T>(_ body: (ForeignBytes) throws -> T) rethrows -> T {
+        return try withUnsafeBytes { try body(ForeignBytes(bufferPointer: $0.bindMemory(to: UInt8.self))) }
+    }
+}
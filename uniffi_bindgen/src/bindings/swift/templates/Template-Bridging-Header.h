@@ -31,6 +31,17 @@ typedef struct NativeRustError {
     char *_Nullable message;
 } NativeRustError;
 
+// Records containing only numbers and booleans are passed by value as a C struct.
+{%- for rec in ci.iter_record_definitions() %}
+{%- if rec.is_plain_old_data() %}
+typedef struct {{ rec.name()|class_name_swift }}FfiStruct {
+    {%- for field in rec.fields() %}
+    {{ field.type_().ffi_type(ci)|type_ffi }} {{ field.name() }};
+    {%- endfor %}
+} {{ rec.name()|class_name_swift }}FfiStruct;
+{%- endif %}
+{%- endfor %}
+
   
 {% for func in ci.iter_ffi_function_definitions() -%}
     {%- match func.return_type() -%}{%- when Some with (type_) %}{{ type_|type_ffi }}{% when None %}void{% endmatch %} {{ func.name() }}(
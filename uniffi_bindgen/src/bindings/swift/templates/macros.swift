@@ -5,7 +5,7 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
-{% call try(func) %} rustCall(
+{% call try(func) %} {% call _borrow_args_open(func) %}rustCall(
     {% match func.throws() %}
     {% when Some with (e) %}
     {{e}}.NoError
@@ -14,11 +14,11 @@
     {% endmatch %}
 ) { err in
     {{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}err)
-}
+}{% call _borrow_args_close(func) %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
-{% call try(func) %} rustCall(
+{% call try(func) %} {% call _borrow_args_open(func) %}rustCall(
     {%- match func.throws() %}
     {%- when Some with (e) %}
     {{e}}.NoError
@@ -29,12 +29,36 @@
     {{ func.ffi_func().name() }}(
         {{- prefix }}, {% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}err
     )
-}
+}{% call _borrow_args_close(func) %}
 {%- endmacro %}
 
+{#-
+// Borrowed (`[ByRef]`) strings and byte arrays are passed to rust as `ForeignBytes`
+// pointing directly at their contents, which are only valid inside a closure.
+// So we wrap the call in one nested closure per borrowed argument, shadowing its name.
+-#}
+
+{%- macro _borrow_args_open(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() -%}
+        {{ arg.name()|var_name_swift }}.withForeignBytes { {{ arg.name()|var_name_swift }} in try {# space #}
+        {%- endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro _borrow_args_close(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %} }{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}
+        {{- arg.name()|var_name_swift }}
+        {%- else %}
         {{- arg.name()|lower_swift(arg.type_()) }}
+        {%- endif %}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro -%}
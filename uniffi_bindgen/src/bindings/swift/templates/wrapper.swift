@@ -2,6 +2,9 @@
 // Trust me, you don't want to mess with it!
 
 import Foundation
+{%- for imp in config.custom_type_imports() %}
+import {{ imp }}
+{%- endfor %}
 {% if is_testing -%}
 import {{ config.module_name() }}
 {% endif -%}
@@ -21,6 +24,10 @@ import {{ config.module_name() }}
 {% include "RecordTemplate.swift" %}
 {% endfor %}
 
+{%- for c in ci.iter_constant_definitions() %}
+public let {{ c.name()|var_name_swift }}: {{ c.type_()|type_swift }} = {{ c.value()|literal_swift }}
+{%- endfor %}
+
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.swift" %}
 {% endfor %}
@@ -29,4 +36,18 @@ import {{ config.module_name() }}
 {% include "ObjectTemplate.swift" %}
 {% endfor %}
 
+/// Check that every object and buffer passed across the FFI has been freed.
+///
+/// This is intended for use in tests, and only works if the Rust code was built with
+/// the `leak-tracking` feature of the `uniffi` crate.
+public func assertNoLeaks() {
+    let _retval = try! rustCall(InternalError.unknown()) { err in
+        {{ ci.ffi_leak_report().name() }}(err)
+    }
+    let leaks = try! {{ "_retval"|lift_swift(Type::String) }}
+    if !leaks.isEmpty {
+        fatalError("Leaked across the FFI: \(leaks)")
+    }
+}
+
 {% import "macros.swift" as swift %}
@@ -0,0 +1,474 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use askama::Template;
+use heck::{CamelCase, MixedCase, ShoutySnakeCase};
+use serde::{Deserialize, Serialize};
+
+use crate::bindings::CustomTypeConfig;
+use crate::interface::*;
+use crate::MergeWith;
+
+// Some config options for the caller to customize the generated JS.
+// Note that this can only be used to control details of the JS *that do not affect the underlying component*,
+// since the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    cdylib_name: Option<String>,
+    #[serde(default)]
+    custom_types: HashMap<String, CustomTypeConfig>,
+}
+
+impl Config {
+    /// The name of the compiled library, without the `.wasm` extension.
+    pub fn cdylib_name(&self) -> String {
+        match self.cdylib_name.as_ref() {
+            Some(name) => name.clone(),
+            None => "uniffi".into(),
+        }
+    }
+
+    /// The filename of the `.wasm` module that the generated JS will load.
+    /// It is expected to live in the same directory as the generated JS.
+    pub fn wasm_filename(&self) -> String {
+        format!("{}.wasm", self.cdylib_name())
+    }
+
+    /// How to expose a custom type from the UDL, defaulting to the builtin type that it wraps.
+    pub fn custom_type(&self, name: &str) -> CustomTypeConfig {
+        self.custom_types.get(name).cloned().unwrap_or_default()
+    }
+
+    /// The imports needed by all the custom types, sorted and without duplicates.
+    pub fn custom_type_imports(&self) -> Vec<String> {
+        let mut imports: Vec<String> = self
+            .custom_types
+            .values()
+            .flat_map(|c| c.imports().iter().cloned())
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+}
+
+impl From<&ComponentInterface> for Config {
+    fn from(ci: &ComponentInterface) -> Self {
+        Config {
+            cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            custom_types: HashMap::new(),
+        }
+    }
+}
+
+impl MergeWith for Config {
+    fn merge_with(&self, other: &Self) -> Self {
+        Config {
+            cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "js", escape = "none", path = "wrapper.js")]
+pub struct JSWrapper<'config, 'ci> {
+    config: &'config Config,
+    ci: &'ci ComponentInterface,
+}
+
+impl<'config, 'ci> JSWrapper<'config, 'ci> {
+    pub fn new(config: &'config Config, ci: &'ci ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "ts", escape = "none", path = "wrapper.d.ts")]
+pub struct TSDeclarations<'config, 'ci> {
+    config: &'config Config,
+    ci: &'ci ComponentInterface,
+}
+
+impl<'config, 'ci> TSDeclarations<'config, 'ci> {
+    pub fn new(config: &'config Config, ci: &'ci ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+}
+
+/// A field of the C-style struct that's used to pass a plain-old-data record over the FFI,
+/// along with where to find it in linear memory according to the `wasm32` C ABI.
+pub struct FfiStructField {
+    name: String,
+    type_: Type,
+    ffi_type: FFIType,
+    offset: usize,
+}
+
+impl FfiStructField {
+    /// The name of the `DataView` methods for accessing this field, e.g. `Float64` for
+    /// `getFloat64` and `setFloat64`.
+    fn accessor(&self) -> &'static str {
+        match self.ffi_type {
+            FFIType::Int8 => "Int8",
+            FFIType::UInt8 => "Uint8",
+            FFIType::Int16 => "Int16",
+            FFIType::UInt16 => "Uint16",
+            FFIType::Int32 => "Int32",
+            FFIType::UInt32 => "Uint32",
+            FFIType::Int64 => "BigInt64",
+            FFIType::UInt64 => "BigUint64",
+            FFIType::Float32 => "Float32",
+            FFIType::Float64 => "Float64",
+            _ => unreachable!("Plain-old-data records only contain numeric fields"),
+        }
+    }
+}
+
+/// Lay out the fields of a plain-old-data record as a C struct, returning the fields
+/// and the total size of the struct.
+///
+/// Each of the numeric types that can appear in such a struct is aligned to its own size.
+fn ffi_struct_layout(rec: &Record, ci: &ComponentInterface) -> (Vec<FfiStructField>, usize) {
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    let mut max_align = 1;
+    for field in rec.fields() {
+        let ffi_type = field.type_().ffi_type(ci);
+        let size = match ffi_type {
+            FFIType::Int8 | FFIType::UInt8 => 1,
+            FFIType::Int16 | FFIType::UInt16 => 2,
+            FFIType::Int32 | FFIType::UInt32 | FFIType::Float32 => 4,
+            FFIType::Int64 | FFIType::UInt64 | FFIType::Float64 => 8,
+            _ => unreachable!("Plain-old-data records only contain numeric fields"),
+        };
+        // Pad the field out to its natural alignment.
+        offset += (size - offset % size) % size;
+        max_align = max_align.max(size);
+        fields.push(FfiStructField {
+            name: field.name().to_string(),
+            type_: field.type_(),
+            ffi_type,
+            offset,
+        });
+        offset += size;
+    }
+    // Pad the end of the struct to the alignment of its largest field.
+    let size = offset + (max_align - offset % max_align) % max_align;
+    (fields, size)
+}
+
+/// Filters for our Askama templates above. These output JS (for the library
+/// itself) and TypeScript (for its type declarations).
+mod filters {
+    use super::*;
+    use std::fmt;
+
+    /// Names the kind of value that the JS glue must pass to the raw WebAssembly
+    /// export for a given FFI type.
+    ///
+    /// This is used to adapt the C ABI calling conventions for `wasm32`: anything that's
+    /// a struct in C is passed as a pointer into linear memory, and 64-bit integers are
+    /// represented as a `BigInt` in JS.
+    pub fn type_ffi(type_: &FFIType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FFIType::Int8 | FFIType::Int16 | FFIType::Int32 => "\"i32\"".into(),
+            FFIType::UInt8 => "\"u8\"".into(),
+            FFIType::UInt16 => "\"u16\"".into(),
+            FFIType::UInt32 => "\"u32\"".into(),
+            FFIType::Int64 => "\"i64\"".into(),
+            FFIType::UInt64 => "\"u64\"".into(),
+            FFIType::Float32 | FFIType::Float64 => "\"f64\"".into(),
+            FFIType::RustCString => "\"ptr\"".into(),
+            FFIType::RustBuffer => "\"RustBuffer\"".into(),
+            FFIType::ForeignBytes => "\"ForeignBytes\"".into(),
+            // Structs are named by the class that knows how to pass them.
+            FFIType::Struct(name) => format!("{}FfiStruct", class_name_js(name)?),
+            FFIType::RustError => "\"ptr\"".into(),
+            // `generate_bindings` rejects components with callback interfaces before rendering.
+            FFIType::ForeignCallback => return Err(unsupported()),
+        })
+    }
+
+    /// The fields of the C-style struct used to pass a plain-old-data record.
+    pub fn ffi_struct_fields(
+        rec: &Record,
+        ci: &ComponentInterface,
+    ) -> Result<Vec<FfiStructField>, askama::Error> {
+        Ok(ffi_struct_layout(rec, ci).0)
+    }
+
+    /// The size in bytes of the C-style struct used to pass a plain-old-data record.
+    pub fn ffi_struct_size(rec: &Record, ci: &ComponentInterface) -> Result<usize, askama::Error> {
+        Ok(ffi_struct_layout(rec, ci).1)
+    }
+
+    /// Declares a TypeScript type in the public interface for the library.
+    pub fn type_ts(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Int8
+            | Type::UInt8
+            | Type::Int16
+            | Type::UInt16
+            | Type::Int32
+            | Type::UInt32
+            | Type::Float32
+            | Type::Float64 => "number".into(),
+            Type::Int64 | Type::UInt64 => "bigint".into(),
+            Type::Boolean => "boolean".into(),
+            Type::String => "string".into(),
+            Type::Bytes => "Uint8Array".into(),
+            Type::Timestamp => "Date".into(),
+            // JS doesn't have a duration type, so we use milliseconds like `Date` does.
+            Type::Duration => "number".into(),
+            Type::Enum(name)
+            | Type::Record(name)
+            | Type::Object(name)
+            | Type::Error(name)
+            | Type::CallbackInterface(name)
+            | Type::Custom { name, .. }
+            | Type::External { name, .. } => class_name_js(name)?,
+            Type::Optional(type_) => format!("{} | null", type_ts(type_)?),
+            Type::Sequence(type_) => format!("Array<{}>", type_ts(type_)?),
+            Type::Union(..) => class_name_js(&type_.union_name())?,
+            Type::Tuple { elements, .. } => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .map(type_ts)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::Map(k, v) => format!("Map<{}, {}>", type_ts(k)?, type_ts(v)?),
+        })
+    }
+
+    /// Quote a string as a JavaScript string literal, escaping backslashes, quotes and
+    /// control characters (including the line and paragraph separators).
+    fn string_literal_js(s: &str) -> String {
+        let mut out = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                    out.push_str(&format!("\\u{:04x}", c as u32))
+                }
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    pub fn literal_js(literal: &Literal) -> Result<String, askama::Error> {
+        fn typed_number(type_: &Type, num_str: String) -> String {
+            match type_ {
+                // 64-bit integers are represented as a `BigInt`.
+                Type::Int64 | Type::UInt64 => format!("{}n", num_str),
+                _ => num_str,
+            }
+        }
+
+        Ok(match literal {
+            Literal::Boolean(v) => format!("{}", v),
+            // use the double-quote form to match with the other languages, and quote escapes.
+            Literal::String(s) => string_literal_js(s),
+            Literal::Null => "null".into(),
+            Literal::EmptySequence => "[]".into(),
+            Literal::EmptyMap => "new Map()".into(),
+            Literal::Enum(v, type_) => match type_ {
+                Type::Enum(name) => format!("{}.{}", class_name_js(name)?, enum_variant_js(v)?),
+                _ => panic!("Unexpected type in enum literal: {:?}", type_),
+            },
+            Literal::Int(i, radix, type_) => typed_number(
+                type_,
+                match radix {
+                    Radix::Octal => format!("0o{:o}", i),
+                    Radix::Decimal => format!("{}", i),
+                    Radix::Hexadecimal => format!("{:#x}", i),
+                },
+            ),
+            Literal::UInt(i, radix, type_) => typed_number(
+                type_,
+                match radix {
+                    Radix::Octal => format!("0o{:o}", i),
+                    Radix::Decimal => format!("{}", i),
+                    Radix::Hexadecimal => format!("{:#x}", i),
+                },
+            ),
+            Literal::Float(string, _type_) => string.clone(),
+        })
+    }
+
+    pub fn class_name_js(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_camel_case())
+    }
+
+    pub fn fn_name_js(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_mixed_case())
+    }
+
+    pub fn var_name_js(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_mixed_case())
+    }
+
+    /// Render a doc comment from the UDL as JSDoc, followed by a newline and the given
+    /// amount of indentation so that the documented declaration lines up beneath it.
+    pub fn doc_comment_ts(docs: &Option<&str>, spaces: &usize) -> Result<String, askama::Error> {
+        Ok(match docs {
+            None => "".into(),
+            Some(docs) => {
+                let indent = " ".repeat(*spaces);
+                let mut out = "/**\n".to_string();
+                for line in docs.lines() {
+                    out.push_str(format!("{} * {}", indent, line.replace("*/", "* /")).trim_end());
+                    out.push('\n');
+                }
+                out.push_str(&format!("{} */\n{}", indent, indent));
+                out
+            }
+        })
+    }
+
+    pub fn enum_variant_js(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
+    }
+
+    pub fn const_name_js(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
+    }
+
+    /// Lower a JS value into an FFI value.
+    ///
+    /// This is used to pass arguments over the FFI, from JS to Rust.
+    pub fn lower_js(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        let nm = var_name_js(nm)?;
+        Ok(match type_ {
+            Type::Int8
+            | Type::UInt8
+            | Type::Int16
+            | Type::UInt16
+            | Type::Int32
+            | Type::UInt32
+            | Type::Float32
+            | Type::Float64
+            | Type::Enum(_) => nm,
+            // Accept plain numbers for convenience, but the FFI needs a `BigInt`.
+            Type::Int64 | Type::UInt64 => format!("BigInt({})", nm),
+            Type::Boolean => format!("({} ? 1 : 0)", nm),
+            Type::String => format!("RustBuffer.allocFromString({})", nm),
+            Type::Object(_) => format!("{}._uniffiHandle()", nm),
+            // `generate_bindings` rejects components that pass these before rendering.
+            Type::CallbackInterface(_) | Type::Error(_) | Type::External { .. } => {
+                return Err(unsupported())
+            }
+            Type::Record(name) if is_plain_old_data(name, ci) => {
+                format!("{}FfiStruct.fromRecord({})", class_name_js(name)?, nm)
+            }
+            Type::Custom { .. } => format!(
+                "_UniFFI{}.lower({})",
+                class_name_js(&type_.canonical_name())?,
+                nm
+            ),
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Record(_)
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => format!(
+                "RustBuffer.allocFrom{}({})",
+                class_name_js(&type_.canonical_name())?,
+                nm
+            ),
+        })
+    }
+
+    /// Lower a borrowed JS string or byte array into a `Uint8Array`,
+    /// which the FFI function will pass to Rust as `ForeignBytes`.
+    pub fn lower_borrowed_js(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        let nm = var_name_js(nm)?;
+        Ok(match type_ {
+            Type::String => format!("new TextEncoder().encode({})", nm),
+            Type::Bytes | Type::Sequence(_) => format!("Uint8Array.from({})", nm),
+            _ => unreachable!(
+                "Only strings and byte arrays can be borrowed, not {:?}",
+                type_
+            ),
+        })
+    }
+
+    /// Lift a JS value from an FFI value.
+    ///
+    /// This is used to receive values over the FFI, from Rust to JS.
+    pub fn lift_js(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Int8
+            | Type::UInt8
+            | Type::Int16
+            | Type::UInt16
+            | Type::Int32
+            | Type::UInt32
+            | Type::Int64
+            | Type::UInt64
+            | Type::Float32
+            | Type::Float64
+            | Type::Enum(_) => nm.to_string(),
+            Type::Boolean => format!("({} !== 0)", nm),
+            Type::String => format!("{}.consumeIntoString()", nm),
+            // `generate_bindings` rejects components that return these before rendering.
+            Type::Object(_)
+            | Type::CallbackInterface(_)
+            | Type::Error(_)
+            | Type::External { .. } => return Err(unsupported()),
+            Type::Record(name) if is_plain_old_data(name, ci) => format!("{}.intoRecord()", nm),
+            Type::Custom { .. } => format!(
+                "_UniFFI{}.lift({})",
+                class_name_js(&type_.canonical_name())?,
+                nm
+            ),
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Record(_)
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => format!(
+                "{}.consumeInto{}()",
+                nm,
+                class_name_js(&type_.canonical_name())?
+            ),
+        })
+    }
+
+    // askama's errors can't carry a message, so this is only a fallback for the checks in
+    // `generate_bindings`, which say what isn't supported.
+    fn unsupported() -> askama::Error {
+        askama::Error::Fmt(fmt::Error)
+    }
+
+    // Records of plain-old-data are passed as a C-style struct rather than a RustBuffer.
+    fn is_plain_old_data(name: &str, ci: &ComponentInterface) -> bool {
+        matches!(ci.get_record_definition(name), Some(rec) if rec.is_plain_old_data())
+    }
+}
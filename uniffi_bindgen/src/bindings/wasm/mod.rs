@@ -0,0 +1,169 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+pub mod gen_wasm;
+pub use gen_wasm::{Config, JSWrapper, TSDeclarations};
+
+use super::super::interface::{Argument, ComponentInterface, Type};
+
+pub struct Bindings {
+    library: String,
+    declarations: String,
+}
+
+/// Generate uniffi component bindings for WebAssembly.
+///
+/// These bindings consist of an ES module `.mjs` file that loads the component compiled for
+/// `wasm32-unknown-unknown` and marshals values in and out of its linear memory, plus a
+/// `.d.mts` file with TypeScript declarations for the public API of that module.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    _try_format_code: bool,
+    _is_testing: bool,
+) -> Result<()> {
+    let out_path = PathBuf::from(out_dir);
+    let Bindings {
+        library,
+        declarations,
+    } = generate_bindings(config, ci)?;
+
+    let mut js_file = out_path.clone();
+    js_file.push(format!("{}.mjs", ci.namespace()));
+    let mut f = File::create(&js_file).context("Failed to create .mjs file for bindings")?;
+    write!(f, "{}", library)?;
+
+    let mut ts_file = out_path;
+    ts_file.push(format!("{}.d.mts", ci.namespace()));
+    let mut f = File::create(&ts_file).context("Failed to create .d.mts file for bindings")?;
+    write!(f, "{}", declarations)?;
+
+    Ok(())
+}
+
+/// Generate JS bindings for the given ComponentInterface, as strings.
+pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bindings> {
+    use askama::Template;
+    if ci
+        .iter_types()
+        .iter()
+        .any(|t| matches!(t, Type::External { .. }))
+    {
+        bail!("JS bindings don't support external types yet");
+    }
+    check_supported(ci)?;
+    let library = JSWrapper::new(config, ci)
+        .render()
+        .map_err(|_| anyhow!("failed to render JS bindings"))?;
+    let declarations = TSDeclarations::new(config, ci)
+        .render()
+        .map_err(|_| anyhow!("failed to render TypeScript declarations"))?;
+    Ok(Bindings {
+        library,
+        declarations,
+    })
+}
+
+// Reject the parts of a component that the JS bindings can't pass over the FFI yet, naming them,
+// rather than failing part way through rendering the templates.
+fn check_supported(ci: &ComponentInterface) -> Result<()> {
+    if let Some(cbi) = ci.iter_callback_interface_definitions().first() {
+        bail!(
+            "callback interface `{}` is not supported by the wasm backend",
+            cbi.name()
+        );
+    }
+    let check = |what: String, args: Vec<&Argument>, return_type: Option<&Type>| -> Result<()> {
+        for arg in args {
+            if let Type::Error(name) = arg.type_() {
+                bail!(
+                    "`{}` takes the error `{}` as an argument, which is not supported by the wasm backend",
+                    what,
+                    name
+                );
+            }
+        }
+        if let Some(Type::Error(name)) = return_type {
+            bail!(
+                "`{}` returns the error `{}`, which is not supported by the wasm backend",
+                what,
+                name
+            );
+        }
+        Ok(())
+    };
+    for func in ci.iter_function_definitions() {
+        check(
+            func.name().to_string(),
+            func.arguments(),
+            func.return_type(),
+        )?;
+    }
+    for obj in ci.iter_object_definitions() {
+        for cons in obj.constructors() {
+            let what = format!("{}.{}", obj.name(), cons.name());
+            check(what, cons.arguments(), None)?;
+        }
+        let methods = obj
+            .methods()
+            .into_iter()
+            .chain(obj.properties().into_iter().flat_map(|p| p.methods()));
+        for meth in methods {
+            let what = format!("{}.{}", obj.name(), meth.name());
+            check(what, meth.arguments(), meth.return_type())?;
+        }
+        for meth in obj.static_methods() {
+            let what = format!("{}.{}", obj.name(), meth.name());
+            check(what, meth.arguments(), meth.return_type())?;
+        }
+    }
+    Ok(())
+}
+
+/// JavaScript code that fails the script if any objects or buffers from the component have leaked.
+pub fn leak_check(_config: &Config, ci: &ComponentInterface) -> String {
+    format!(
+        "import {{ assertNoLeaks as uniffiAssertNoLeaks }} from \"./{}.mjs\";\nuniffiAssertNoLeaks();\n",
+        ci.namespace()
+    )
+}
+
+/// Execute the specified JS script using `node`.
+///
+/// The generated bindings are an ES module which `node` resolves relative to the importing
+/// script, so we copy the script into the output directory alongside the generated
+/// bindings and the compiled `.wasm` file, and run it from there.
+pub fn run_script(out_dir: &Path, script_file: &Path) -> Result<()> {
+    let mut script_copy = PathBuf::from(out_dir);
+    script_copy.push(
+        script_file
+            .file_name()
+            .ok_or_else(|| anyhow!("Script file has no name"))?,
+    );
+    // Scripts that were written into the output directory, e.g. to check for leaks, are already there.
+    if script_file != script_copy {
+        std::fs::copy(script_file, &script_copy)
+            .context("Failed to copy script into the output directory")?;
+    }
+    let status = Command::new("node")
+        .arg(&script_copy)
+        .spawn()
+        .context("Failed to spawn `node` when running script")?
+        .wait()
+        .context("Failed to wait for `node` when running script")?;
+    if !status.success() {
+        bail!("running `node` failed")
+    }
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// Helpers for the custom types declared in the UDL.
+// Each custom type is passed over the FFI as the builtin type that it wraps, and
+// these convert it to and from that builtin type using the configured expressions.
+
+{%- for typ in ci.iter_types() %}
+{%- match typ %}
+{%- when Type::Custom with { name, builtin } %}
+{%- let custom = config.custom_type(name) %}
+
+class _UniFFI{{ typ.canonical_name()|class_name_js }} {
+    static intoCustom(builtinValue) {
+        return {{ custom.into_custom("builtinValue") }};
+    }
+
+    static fromCustom(v) {
+        return {{ custom.from_custom("v") }};
+    }
+
+    static lift(v) {
+        return _UniFFI{{ typ.canonical_name()|class_name_js }}.intoCustom({{ "v"|lift_js(builtin, ci) }});
+    }
+
+    static lower(v) {
+        const builtinValue = _UniFFI{{ typ.canonical_name()|class_name_js }}.fromCustom(v);
+        return {{ "builtinValue"|lower_js(builtin, ci) }};
+    }
+}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
@@ -0,0 +1,5 @@
+export const {{ e.name()|class_name_js }} = Object.freeze({
+    {%- for variant in e.variants() %}
+    {{ variant|enum_variant_js }}: {{ loop.index }},
+    {%- endfor %}
+});
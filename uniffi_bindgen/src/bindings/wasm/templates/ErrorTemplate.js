@@ -0,0 +1,83 @@
+class RustError {
+    constructor(code, message) {
+        this.code = code;
+        // A pointer to a C string in the module's linear memory.
+        this.message = message;
+    }
+
+    static readFrom(ptr) {
+        const view = memoryView();
+        return new RustError(view.getInt32(ptr, true), view.getUint32(ptr + 4, true));
+    }
+
+    // Read the error message, and free the Rust-owned string that it came in.
+    consumeMessage() {
+        if (this.message === 0) {
+            return "";
+        }
+        try {
+            const bytes = new Uint8Array(_UniFFIWasm.memory.buffer);
+            const end = bytes.indexOf(0, this.message);
+            return new TextDecoder().decode(bytes.subarray(this.message, end));
+        } finally {
+            rustCallWithError(InternalError, _UniFFILib.{{ ci.ffi_string_free().name() }}, this.message);
+            this.message = 0;
+        }
+    }
+}
+
+export class InternalError extends Error {
+    constructor(message) {
+        super(message);
+        this.name = "InternalError";
+    }
+
+    static raiseErr(code, message) {
+        throw new InternalError(message);
+    }
+}
+
+// Thrown when Rust couldn't lift one of the arguments that it was passed, which means that
+// these bindings have a bug or are out of date.
+export class InvalidArgumentError extends InternalError {
+    constructor(message) {
+        super(message);
+        this.name = "InvalidArgumentError";
+    }
+}
+
+{% for e in ci.iter_error_definitions() %}
+export class {{ e.name()|class_name_js }} extends Error {
+    constructor(message) {
+        super(message);
+        this.name = this.constructor.name;
+    }
+
+    static raiseErr(code, message) {
+        {%- for value in e.values() %}
+        if (code === {{ loop.index }}) {
+            throw new {{ e.name()|class_name_js }}.{{ value|class_name_js }}(message);
+        }
+        {%- endfor %}
+        throw new InternalError(`Unknown error code ${code}: ${message}`);
+    }
+}
+{%- for value in e.values() %}
+{{ e.name()|class_name_js }}.{{ value|class_name_js }} = class {{ value|class_name_js }} extends {{ e.name()|class_name_js }} {};
+{%- endfor %}
+{% endfor %}
+
+function rustCallWithError(errorClass, fn, ...args) {
+    return withScratch(RUST_ERROR_SIZE, (errPtr) => {
+        const result = fn(...args, errPtr);
+        const error = RustError.readFrom(errPtr);
+        // This is the same for every call, whatever errors it's declared to throw.
+        if (error.code === -3) {
+            throw new InvalidArgumentError(error.consumeMessage());
+        }
+        if (error.code !== 0) {
+            errorClass.raiseErr(error.code, error.consumeMessage());
+        }
+        return result;
+    });
+}
@@ -0,0 +1,168 @@
+// This is how we find and load the WebAssembly module provided by the component.
+// For now we just look it up by name, in the same directory as this module.
+//
+// Several components may be bundled into the same module, in which case they must all
+// share a single instance of it, and hence the same linear memory and allocator.
+// We keep track of the instances in a global so that each module is only loaded once.
+
+function loadIndirect(wasmFilename) {
+    const url = new URL(wasmFilename, import.meta.url);
+    const instances = globalThis.__uniffiWasmInstances || (globalThis.__uniffiWasmInstances = new Map());
+    if (!instances.has(url.href)) {
+        instances.set(url.href, instantiate(url));
+    }
+    return instances.get(url.href);
+}
+
+async function instantiate(url) {
+    let bytes;
+    if (url.protocol === "file:") {
+        // We're running in node, which can't `fetch()` from the local filesystem.
+        const fs = await import("fs");
+        bytes = fs.readFileSync(url);
+    } else {
+        const response = await fetch(url);
+        if (!response.ok) {
+            throw new InternalError(`Failed to fetch ${url}: ${response.status}`);
+        }
+        bytes = await response.arrayBuffer();
+    }
+    const { instance } = await WebAssembly.instantiate(bytes, {});
+    return instance.exports;
+}
+
+const _UniFFIWasm = await loadIndirect("{{ config.wasm_filename() }}");
+
+// The size in bytes of the structs that we pass over the FFI, in the `wasm32` C ABI.
+const RUST_BUFFER_SIZE = 24;
+const FOREIGN_BYTES_SIZE = 24;
+const RUST_ERROR_SIZE = 8;
+
+// The module's linear memory may grow during any call into Rust, which detaches
+// any views of the old memory. Always get a fresh view rather than holding on to one.
+function memoryView() {
+    return new DataView(_UniFFIWasm.memory.buffer);
+}
+
+function memoryBytes(ptr, len) {
+    return new Uint8Array(_UniFFIWasm.memory.buffer, ptr, len);
+}
+
+// Borrow `size` bytes of scratch memory from the Rust allocator for the duration of `callback`.
+function withScratch(size, callback) {
+    const ptr = _UniFFIWasm.{{ ci.ffi_wasm_scratch_alloc().name() }}(size);
+    try {
+        return callback(ptr);
+    } finally {
+        _UniFFIWasm.{{ ci.ffi_wasm_scratch_free().name() }}(ptr, size);
+    }
+}
+
+// The size in bytes of an FFI type that's passed via a pointer into linear memory,
+// or zero if it's passed directly as a WebAssembly value.
+//
+// Structs for plain-old-data records are named by the class that knows how to pass them.
+// Structs containing a single field are passed directly, as if they were just that field.
+function indirectSize(type) {
+    switch (type) {
+        case "RustBuffer": return RUST_BUFFER_SIZE;
+        case "ForeignBytes": return FOREIGN_BYTES_SIZE;
+        default: return (typeof type === "function" && type.PASSED_INDIRECTLY) ? type.SIZE : 0;
+    }
+}
+
+// Adapt a raw WebAssembly export to the way that the generated code expects to call it.
+//
+// In the `wasm32` C ABI, structs are passed as a pointer to a copy of the struct in linear
+// memory, and returned by writing them through a pointer passed as a hidden first argument.
+// We copy struct arguments and return values in and out of scratch memory accordingly,
+// keeping each one 8-byte aligned.
+//
+// Borrowed strings and byte arrays arrive here as a `Uint8Array`. Rust can't point into JS
+// memory, so we copy their contents into scratch memory after the structs and pass a
+// `ForeignBytes` pointing at that copy, which saves a round-trip through the Rust allocator.
+function ffiFunction(name, argTypes, returnType) {
+    const rawFunc = _UniFFIWasm[name];
+    if (rawFunc === undefined) {
+        throw new InternalError(`Missing FFI function ${name}; is the .wasm file out of date?`);
+    }
+    const alignedSize = (type) => (indirectSize(type) + 7) & ~7;
+    const returnsStruct = (indirectSize(returnType) > 0);
+    const structsSize = argTypes.reduce((total, t) => total + alignedSize(t), alignedSize(returnType));
+    const borrowedSize = (args) => args.reduce((total, arg, i) => total + (argTypes[i] === "ForeignBytes" ? arg.length : 0), 0);
+    const callRaw = (rawArgs) => {
+        try {
+            return rawFunc(...rawArgs);
+        } catch (e) {
+            // Panics are compiled to traps on `wasm32-unknown-unknown`, since it doesn't support unwinding.
+            if (e instanceof WebAssembly.RuntimeError) {
+                throw new InternalError(`Rust panicked while calling ${name}: ${e.message}`);
+            }
+            throw e;
+        }
+    };
+    const lowerArgs = (args, rawArgs, nextStruct, nextBytes) => {
+        args.forEach((arg, i) => {
+            if (argTypes[i] === "ForeignBytes") {
+                memoryBytes(nextBytes, arg.length).set(arg);
+                new ForeignBytes(arg.length, nextBytes).writeTo(nextStruct);
+                rawArgs.push(nextStruct);
+                nextStruct += alignedSize(argTypes[i]);
+                nextBytes += arg.length;
+            } else if (indirectSize(argTypes[i]) > 0) {
+                arg.writeTo(nextStruct);
+                rawArgs.push(nextStruct);
+                nextStruct += alignedSize(argTypes[i]);
+            } else if (typeof argTypes[i] === "function") {
+                rawArgs.push(arg.toRaw());
+            } else {
+                rawArgs.push(arg);
+            }
+        });
+        return rawArgs;
+    };
+    return function(...args) {
+        if (structsSize === 0) {
+            return liftRaw(returnType, callRaw(lowerArgs(args, [], 0, 0)));
+        }
+        return withScratch(structsSize + borrowedSize(args), (scratch) => {
+            const rawArgs = returnsStruct ? [scratch] : [];
+            const result = callRaw(lowerArgs(args, rawArgs, scratch + alignedSize(returnType), scratch + structsSize));
+            if (returnsStruct) {
+                return (returnType === "RustBuffer" ? RustBuffer : returnType).readFrom(scratch);
+            }
+            return liftRaw(returnType, result);
+        });
+    };
+}
+
+// WebAssembly only has signed integers, so we need to fix up unsigned return values.
+// Structs that were returned directly as a single value also need to be rebuilt.
+function liftRaw(returnType, value) {
+    switch (returnType) {
+        case "u8": return value & 0xFF;
+        case "u16": return value & 0xFFFF;
+        case "u32": return value >>> 0;
+        case "u64": return BigInt.asUintN(64, value);
+        default: return (typeof returnType === "function") ? returnType.fromRaw(value) : value;
+    }
+}
+
+// An object to expose the extern-C FFI definitions.
+// This is an implementation detail which will be called internally by the public API.
+
+const _UniFFILib = {
+    {%- for func in ci.iter_ffi_function_definitions() %}
+    {{ func.name() }}: ffiFunction("{{ func.name() }}", [{% call js::arg_list_ffi_decl(func) %}], {% match func.return_type() %}{% when Some with (type_) %}{{ type_|type_ffi }}{% when None %}null{% endmatch %}),
+    {%- endfor %}
+};
+
+// Objects are freed when they are garbage-collected, unless they were explicitly destroyed first.
+// Each registration holds the FFI function that frees the object, and its handle.
+const _UniFFIFinalizers = new FinalizationRegistry(([freeFunc, handle]) => {
+    try {
+        rustCallWithError(InternalError, freeFunc, handle);
+    } catch (e) {
+        // There's nobody to report this error to, and we don't want to crash the GC.
+    }
+});
@@ -0,0 +1,103 @@
+export class {{ obj.name()|class_name_js }} {
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    constructor({% call js::arg_list_decl(cons) -%}) {
+        this._handle = {% call js::to_ffi_call(cons) %};
+        _UniFFIFinalizers.register(this, [_UniFFILib.{{ obj.ffi_object_free().name() }}, this._handle], this);
+    }
+    {%- when None %}
+    {%- endmatch %}
+
+    // Lightly yucky way to bypass the usual constructor logic
+    // and just create a new instance with the required handle.
+    static _fromHandle(handle) {
+        const inst = Object.create({{ obj.name()|class_name_js }}.prototype);
+        inst._handle = handle;
+        _UniFFIFinalizers.register(inst, [_UniFFILib.{{ obj.ffi_object_free().name() }}, handle], inst);
+        return inst;
+    }
+
+    _uniffiHandle() {
+        if (this._handle === null) {
+            throw new InternalError("{{ obj.name()|class_name_js }} object has already been destroyed");
+        }
+        return this._handle;
+    }
+
+    // Free the underlying Rust object.
+    //
+    // The object will also be freed when it is garbage-collected, but JS makes no promises
+    // about when (or whether) that will happen, so it's a good idea to call this explicitly.
+    // Calling any method on the object after destroying it will throw an `InternalError`.
+    destroy() {
+        if (this._handle !== null) {
+            _UniFFIFinalizers.unregister(this);
+            rustCallWithError(InternalError, _UniFFILib.{{ obj.ffi_object_free().name() }}, this._handle);
+            this._handle = null;
+        }
+    }
+
+    {%- match obj.ffi_object_to_string() %}
+    {%- when Some with (func) %}
+
+    toString() {
+        const _retval = rustCallWithError(InternalError, _UniFFILib.{{ func.name() }}, this._uniffiHandle());
+        return {{ "_retval"|lift_js(Type::String, ci) }};
+    }
+    {%- when None %}
+    {%- endmatch %}
+
+    {% for cons in obj.alternate_constructors() -%}
+    static {{ cons.name()|fn_name_js }}({% call js::arg_list_decl(cons) %}) {
+        // Call the (fallible) function before creating any half-baked object instances.
+        const handle = {% call js::to_ffi_call(cons) %};
+        return {{ obj.name()|class_name_js }}._fromHandle(handle);
+    }
+    {% endfor %}
+
+    {% for meth in obj.static_methods() -%}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) -%}
+    static {{ meth.name()|fn_name_js }}({% call js::arg_list_decl(meth) %}) {
+        const _retval = {% call js::to_ffi_call(meth) %};
+        return {{ "_retval"|lift_js(return_type, ci) }};
+    }
+
+    {%- when None -%}
+    static {{ meth.name()|fn_name_js }}({% call js::arg_list_decl(meth) %}) {
+        {% call js::to_ffi_call(meth) %};
+    }
+    {% endmatch %}
+    {% endfor %}
+
+    {% for prop in obj.properties() -%}
+    get {{ prop.name()|var_name_js }}() {
+        const _retval = {% call js::to_ffi_call_with_prefix("this._uniffiHandle()", prop.getter()) %};
+        return {{ "_retval"|lift_js(prop.type_(), ci) }};
+    }
+    {% match prop.setter() -%}
+    {%- when Some with (setter) %}
+    set {{ prop.name()|var_name_js }}(value) {
+        {% call js::to_ffi_call_with_prefix("this._uniffiHandle()", setter) %};
+    }
+    {% when None -%}
+    {%- endmatch %}
+    {% endfor %}
+
+    {% for meth in obj.methods() -%}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) -%}
+    {{ meth.name()|fn_name_js }}({% call js::arg_list_decl(meth) %}) {
+        const _retval = {% call js::to_ffi_call_with_prefix("this._uniffiHandle()", meth) %};
+        return {{ "_retval"|lift_js(return_type, ci) }};
+    }
+
+    {%- when None -%}
+    {{ meth.name()|fn_name_js }}({% call js::arg_list_decl(meth) %}) {
+        {% call js::to_ffi_call_with_prefix("this._uniffiHandle()", meth) %};
+    }
+    {% endmatch %}
+    {% endfor %}
+}
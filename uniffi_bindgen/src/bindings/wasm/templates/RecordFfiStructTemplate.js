@@ -0,0 +1,64 @@
+{%- if rec.is_plain_old_data() %}
+{%- let struct_name = rec.name()|class_name_js %}
+{%- let fields = rec|ffi_struct_fields(ci) %}
+// Records containing only numbers and booleans are passed across the FFI by value,
+// as a C-style struct with the same fields, rather than being serialized into a RustBuffer.
+
+class {{ struct_name }}FfiStruct {
+    constructor({% for field in fields %}{{ field.name|var_name_js }}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %}) {
+        {%- for field in fields %}
+        this.{{ field.name|var_name_js }} = {{ field.name|var_name_js }};
+        {%- endfor %}
+    }
+
+    static fromRecord(rec) {
+        const { {% for field in fields %}{{ field.name|var_name_js }}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %} } = rec;
+        return new {{ struct_name }}FfiStruct(
+            {%- for field in fields %}
+            {{ field.name|lower_js(field.type_, ci) }},
+            {%- endfor %}
+        );
+    }
+
+    intoRecord() {
+        return new {{ struct_name }}(
+            {%- for field in fields %}
+            {{ "this.{}"|format(field.name|var_name_js)|lift_js(field.type_, ci) }},
+            {%- endfor %}
+        );
+    }
+    {%- if fields.len() == 1 %}
+    {%- for field in fields %}
+
+    // A struct with a single field is passed as if it were just that field.
+    static fromRaw(v) {
+        return new {{ struct_name }}FfiStruct(liftRaw({{ field.ffi_type|type_ffi }}, v));
+    }
+
+    toRaw() {
+        return this.{{ field.name|var_name_js }};
+    }
+    {%- endfor %}
+    {%- else %}
+
+    static readFrom(ptr) {
+        const view = memoryView();
+        return new {{ struct_name }}FfiStruct(
+            {%- for field in fields %}
+            view.get{{ field.accessor() }}(ptr + {{ field.offset }}, true),
+            {%- endfor %}
+        );
+    }
+
+    writeTo(ptr) {
+        const view = memoryView();
+        {%- for field in fields %}
+        view.set{{ field.accessor() }}(ptr + {{ field.offset }}, this.{{ field.name|var_name_js }}, true);
+        {%- endfor %}
+    }
+    {%- endif %}
+}
+
+{{ struct_name }}FfiStruct.SIZE = {{ rec|ffi_struct_size(ci) }};
+{{ struct_name }}FfiStruct.PASSED_INDIRECTLY = {% if fields.len() == 1 %}false{% else %}true{% endif %};
+{%- endif %}
@@ -0,0 +1,11 @@
+export class {{ rec.name()|class_name_js }} {
+    constructor({% for field in rec.fields() %}{{ field.name()|var_name_js }}{%- match field.default_value() %}{%- when Some with(literal) %} = {{ literal|literal_js }}{%- else %}{%- endmatch %}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %}) {
+        {%- for field in rec.fields() %}
+        this.{{ field.name()|var_name_js }} = {{ field.name()|var_name_js }};
+        {%- endfor %}
+    }
+
+    toString() {
+        return `{{ rec.name()|class_name_js }}({% for field in rec.fields() %}{{ field.name()|var_name_js }}=${this.{{ field.name()|var_name_js }}}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %})`;
+    }
+}
@@ -0,0 +1,290 @@
+
+// Helper for structured writing of values into a RustBuffer.
+//
+// Values are accumulated in JS memory and copied into a Rust-owned buffer in one go when
+// finalized, which saves us from calling into Rust every time the buffer needs to grow.
+class RustBufferBuilder {
+    constructor() {
+        this.bytes = new Uint8Array(16);
+        this.view = new DataView(this.bytes.buffer);
+        this.len = 0;
+    }
+
+    finalize() {
+        const rbuf = RustBuffer.alloc(this.len);
+        memoryBytes(rbuf.data, this.len).set(this.bytes.subarray(0, this.len));
+        rbuf.len = this.len;
+        this.discard();
+        return rbuf;
+    }
+
+    discard() {
+        this.bytes = null;
+        this.view = null;
+    }
+
+    // Make room for `numBytes` more bytes, returning the offset at which to write them.
+    // This may replace `this.bytes` and `this.view`, so call it before accessing either.
+    _reserve(numBytes) {
+        if (this.len + numBytes > this.bytes.length) {
+            const newBytes = new Uint8Array(Math.max(this.bytes.length * 2, this.len + numBytes));
+            newBytes.set(this.bytes);
+            this.bytes = newBytes;
+            this.view = new DataView(newBytes.buffer);
+        }
+        const offset = this.len;
+        this.len += numBytes;
+        return offset;
+    }
+
+    write(value) {
+        const offset = this._reserve(value.length);
+        this.bytes.set(value, offset);
+    }
+
+    // For every type used in the interface, we provide helper methods for conveniently
+    // writing values of that type in a buffer. Putting them on this internal helper object
+    // (rather than, say, as methods on the public classes) makes it easier for us to hide
+    // these implementation details from consumers.
+
+    {%- for typ in ci.iter_types() -%}
+    {%- let canonical_type_name = typ.canonical_name()|class_name_js -%}
+    {%- match typ -%}
+
+    {% when Type::Int8 -%}
+
+    writeI8(v) {
+        const offset = this._reserve(1);
+        this.view.setInt8(offset, v);
+    }
+
+    {% when Type::UInt8 -%}
+
+    writeU8(v) {
+        const offset = this._reserve(1);
+        this.view.setUint8(offset, v);
+    }
+
+    {% when Type::Int16 -%}
+
+    writeI16(v) {
+        const offset = this._reserve(2);
+        this.view.setInt16(offset, v);
+    }
+
+    {% when Type::UInt16 -%}
+
+    writeU16(v) {
+        const offset = this._reserve(2);
+        this.view.setUint16(offset, v);
+    }
+
+    {% when Type::Int32 -%}
+
+    writeI32(v) {
+        const offset = this._reserve(4);
+        this.view.setInt32(offset, v);
+    }
+
+    {% when Type::UInt32 -%}
+
+    writeU32(v) {
+        const offset = this._reserve(4);
+        this.view.setUint32(offset, v);
+    }
+
+    {% when Type::Int64 -%}
+
+    writeI64(v) {
+        const offset = this._reserve(8);
+        this.view.setBigInt64(offset, BigInt(v));
+    }
+
+    {% when Type::UInt64 -%}
+
+    writeU64(v) {
+        const offset = this._reserve(8);
+        this.view.setBigUint64(offset, BigInt(v));
+    }
+
+    {% when Type::Float32 -%}
+
+    writeF32(v) {
+        const offset = this._reserve(4);
+        this.view.setFloat32(offset, v);
+    }
+
+    {% when Type::Float64 -%}
+
+    writeF64(v) {
+        const offset = this._reserve(8);
+        this.view.setFloat64(offset, v);
+    }
+
+    {% when Type::Boolean -%}
+
+    writeBool(v) {
+        const offset = this._reserve(1);
+        this.view.setInt8(offset, v ? 1 : 0);
+    }
+
+    {% when Type::String -%}
+
+    writeString(v) {
+        const utf8Bytes = new TextEncoder().encode(v);
+        const offset = this._reserve(4);
+        this.view.setInt32(offset, utf8Bytes.length);
+        this.write(utf8Bytes);
+    }
+
+    {% when Type::Bytes -%}
+
+    writeBytes(v) {
+        const offset = this._reserve(4);
+        this.view.setInt32(offset, v.length);
+        this.write(v);
+    }
+
+    {% when Type::Timestamp -%}
+
+    writeTimestamp(v) {
+        const millis = v.getTime();
+        const seconds = Math.floor(millis / 1000);
+        const offset = this._reserve(12);
+        this.view.setBigInt64(offset, BigInt(seconds));
+        this.view.setUint32(offset + 8, (millis - seconds * 1000) * 1000000);
+    }
+
+    {% when Type::Duration -%}
+
+    writeDuration(v) {
+        if (!(v >= 0)) {
+            throw new RangeError("Duration must be a non-negative number of milliseconds");
+        }
+        const seconds = Math.floor(v / 1000);
+        const nanos = Math.min(Math.round((v - seconds * 1000) * 1000000), 999999999);
+        const offset = this._reserve(12);
+        this.view.setBigUint64(offset, BigInt(seconds));
+        this.view.setUint32(offset + 8, nanos);
+    }
+
+    {% when Type::Object with (object_name) -%}
+    // The Object type {{ object_name }}.
+    // Objects cannot currently be serialized, but we can produce a helpful error.
+
+    write{{ canonical_type_name }}() {
+        throw new InternalError("RustBufferBuilder.write() not implemented yet for {{ canonical_type_name }}");
+    }
+
+    {% when Type::CallbackInterface with (object_name) -%}
+    // The Callback Interface type {{ object_name }}.
+    // Objects cannot currently be serialized, but we can produce a helpful error.
+
+    write{{ canonical_type_name }}() {
+        throw new InternalError("RustBufferBuilder.write() not implemented yet for {{ canonical_type_name }}");
+    }
+
+    {% when Type::Error with (error_name) -%}
+    // The Error type {{ error_name }}.
+    // Errors cannot currently be serialized, but we can produce a helpful error.
+
+    write{{ canonical_type_name }}() {
+        throw new InternalError("RustBufferBuilder.write() not implemented yet for {{ canonical_type_name }}");
+    }
+
+    {% when Type::External with { name, crate_name } -%}
+    {#- External types aren't supported yet, so `generate_bindings` refuses to get this far. -#}
+
+    {% when Type::Enum with (enum_name) -%}
+    // The Enum type {{ enum_name }}.
+
+    write{{ canonical_type_name }}(v) {
+        const offset = this._reserve(4);
+        this.view.setInt32(offset, v);
+    }
+
+    {% when Type::Record with (record_name) -%}
+    {%- let rec = ci.get_record_definition(record_name).unwrap() -%}
+    // The Record type {{ record_name }}.
+
+    write{{ canonical_type_name }}(v) {
+        {%- for field in rec.fields() %}
+        this.write{{ field.type_().canonical_name()|class_name_js }}(v.{{ field.name()|var_name_js }});
+        {%- endfor %}
+    }
+
+    {% when Type::Custom with { name, builtin } -%}
+    // The custom type {{ name }}, which is serialized as the builtin type {{ builtin.canonical_name() }}.
+
+    write{{ canonical_type_name }}(v) {
+        this.write{{ builtin.canonical_name()|class_name_js }}(_UniFFI{{ canonical_type_name }}.fromCustom(v));
+    }
+
+    {% when Type::Optional with (inner_type) -%}
+    // The Optional<T> type for {{ inner_type.canonical_name() }}.
+
+    write{{ canonical_type_name }}(v) {
+        if (v === null || v === undefined) {
+            const offset = this._reserve(1);
+            this.view.setInt8(offset, 0);
+        } else {
+            const offset = this._reserve(1);
+            this.view.setInt8(offset, 1);
+            this.write{{ inner_type.canonical_name()|class_name_js }}(v);
+        }
+    }
+
+    {% when Type::Sequence with (inner_type) -%}
+    // The Sequence<T> type for {{ inner_type.canonical_name() }}.
+
+    write{{ canonical_type_name }}(items) {
+        const offset = this._reserve(4);
+        this.view.setInt32(offset, items.length);
+        for (const item of items) {
+            this.write{{ inner_type.canonical_name()|class_name_js }}(item);
+        }
+    }
+
+    {% when Type::Union with (name, members) -%}
+    {%- let union_name = typ.union_name()|class_name_js %}
+    // The union type {{ union_name }}.
+
+    write{{ canonical_type_name }}(v) {
+        {%- for (variant, member_type) in typ.union_variants() %}
+        if (v instanceof {{ union_name }}.{{ variant }}) {
+            const offset = this._reserve(1);
+            this.view.setUint8(offset, {{ loop.index }});
+            this.write{{ member_type.canonical_name()|class_name_js }}(v.value);
+            return;
+        }
+        {%- endfor %}
+        throw new TypeError(`Expected a variant of {{ union_name }}, not ${v}`);
+    }
+
+    {% when Type::Tuple with { name, elements } -%}
+    // The tuple type {{ name }}, which is represented as an array.
+
+    write{{ canonical_type_name }}(v) {
+        if (v.length !== {{ elements.len() }}) {
+            throw new TypeError(`Expected an array of length {{ elements.len() }}, not ${v.length}`);
+        }
+        {%- for element_type in elements %}
+        this.write{{ element_type.canonical_name()|class_name_js }}(v[{{ loop.index0 }}]);
+        {%- endfor %}
+    }
+
+    {% when Type::Map with (key_type, value_type) -%}
+    // The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
+
+    write{{ canonical_type_name }}(items) {
+        const offset = this._reserve(4);
+        this.view.setInt32(offset, items.size);
+        for (const [k, v] of items) {
+            this.write{{ key_type.canonical_name()|class_name_js }}(k);
+            this.write{{ value_type.canonical_name()|class_name_js }}(v);
+        }
+    }
+
+    {%- endmatch -%}
+    {%- endfor %}
+}
@@ -0,0 +1,280 @@
+
+// Helper for structured reading of values from a RustBuffer.
+class RustBufferStream {
+    constructor(bytes) {
+        this.bytes = bytes;
+        this.view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+        this.offset = 0;
+    }
+
+    remaining() {
+        return this.bytes.length - this.offset;
+    }
+
+    _advance(size) {
+        if (this.offset + size > this.bytes.length) {
+            throw new InternalError("read past end of rust buffer");
+        }
+        const offset = this.offset;
+        this.offset += size;
+        return offset;
+    }
+
+    read(size) {
+        const offset = this._advance(size);
+        return this.bytes.subarray(offset, offset + size);
+    }
+
+    // For every type used in the interface, we provide helper methods for conveniently
+    // reading that type in a buffer. Putting them on this internal helper object (rather
+    // than, say, as methods on the public classes) makes it easier for us to hide these
+    // implementation details from consumers.
+
+    {%- for typ in ci.iter_types() -%}
+    {%- let canonical_type_name = typ.canonical_name()|class_name_js -%}
+    {%- match typ -%}
+
+    {% when Type::Int8 -%}
+
+    readI8() {
+        return this.view.getInt8(this._advance(1));
+    }
+
+    {% when Type::UInt8 -%}
+
+    readU8() {
+        return this.view.getUint8(this._advance(1));
+    }
+
+    {% when Type::Int16 -%}
+
+    readI16() {
+        return this.view.getInt16(this._advance(2));
+    }
+
+    {% when Type::UInt16 -%}
+
+    readU16() {
+        return this.view.getUint16(this._advance(2));
+    }
+
+    {% when Type::Int32 -%}
+
+    readI32() {
+        return this.view.getInt32(this._advance(4));
+    }
+
+    {% when Type::UInt32 -%}
+
+    readU32() {
+        return this.view.getUint32(this._advance(4));
+    }
+
+    {% when Type::Int64 -%}
+
+    readI64() {
+        return this.view.getBigInt64(this._advance(8));
+    }
+
+    {% when Type::UInt64 -%}
+
+    readU64() {
+        return this.view.getBigUint64(this._advance(8));
+    }
+
+    {% when Type::Float32 -%}
+
+    readF32() {
+        return this.view.getFloat32(this._advance(4));
+    }
+
+    {% when Type::Float64 -%}
+
+    readF64() {
+        return this.view.getFloat64(this._advance(8));
+    }
+
+    {% when Type::Boolean -%}
+
+    readBool() {
+        const v = this.view.getInt8(this._advance(1));
+        if (v === 0) {
+            return false;
+        }
+        if (v === 1) {
+            return true;
+        }
+        throw new InternalError("Unexpected byte for Boolean type");
+    }
+
+    {% when Type::String -%}
+
+    readString() {
+        const size = this.view.getInt32(this._advance(4));
+        if (size < 0) {
+            throw new InternalError("Unexpected negative string length");
+        }
+        return new TextDecoder().decode(this.read(size));
+    }
+
+    {% when Type::Bytes -%}
+
+    readBytes() {
+        const size = this.view.getInt32(this._advance(4));
+        if (size < 0) {
+            throw new InternalError("Unexpected negative byte string length");
+        }
+        // Copy the bytes out, since the buffer will be freed once we're done reading from it.
+        return this.read(size).slice();
+    }
+
+    {% when Type::Timestamp -%}
+
+    readTimestamp() {
+        const seconds = this.view.getBigInt64(this._advance(8));
+        const nanos = this.view.getUint32(this._advance(4));
+        if (nanos >= 1000000000) {
+            throw new InternalError("Unexpected nanoseconds in timestamp");
+        }
+        // A `Date` only has millisecond precision.
+        return new Date(Number(seconds) * 1000 + Math.floor(nanos / 1000000));
+    }
+
+    {% when Type::Duration -%}
+
+    readDuration() {
+        const seconds = this.view.getBigUint64(this._advance(8));
+        const nanos = this.view.getUint32(this._advance(4));
+        if (nanos >= 1000000000) {
+            throw new InternalError("Unexpected nanoseconds in duration");
+        }
+        return Number(seconds) * 1000 + nanos / 1000000;
+    }
+
+    {% when Type::Object with (object_name) -%}
+    // The Object type {{ object_name }}.
+    // Objects cannot currently be serialized, but we can produce a helpful error.
+
+    read{{ canonical_type_name }}() {
+        throw new InternalError("RustBufferStream.read not implemented yet for {{ canonical_type_name }}");
+    }
+
+    {% when Type::CallbackInterface with (object_name) -%}
+    // The Callback Interface type {{ object_name }}.
+    // Objects cannot currently be serialized, but we can produce a helpful error.
+
+    read{{ canonical_type_name }}() {
+        throw new InternalError("RustBufferStream.read not implemented yet for {{ canonical_type_name }}");
+    }
+
+    {% when Type::Error with (error_name) -%}
+    // The Error type {{ error_name }}.
+    // Errors cannot currently be serialized, but we can produce a helpful error.
+
+    read{{ canonical_type_name }}() {
+        throw new InternalError("RustBufferStream.read not implemented yet for {{ canonical_type_name }}");
+    }
+
+    {% when Type::External with { name, crate_name } -%}
+    {#- External types aren't supported yet, so `generate_bindings` refuses to get this far. -#}
+
+    {% when Type::Enum with (enum_name) -%}
+    // The Enum type {{ enum_name }}.
+
+    read{{ canonical_type_name }}() {
+        return this.view.getInt32(this._advance(4));
+    }
+
+    {% when Type::Record with (record_name) -%}
+    {%- let rec = ci.get_record_definition(record_name).unwrap() -%}
+    // The Record type {{ record_name }}.
+
+    read{{ canonical_type_name }}() {
+        return new {{ rec.name()|class_name_js }}(
+            {%- for field in rec.fields() %}
+            this.read{{ field.type_().canonical_name()|class_name_js }}(){% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        );
+    }
+
+    {% when Type::Custom with { name, builtin } -%}
+    // The custom type {{ name }}, which is serialized as the builtin type {{ builtin.canonical_name() }}.
+
+    read{{ canonical_type_name }}() {
+        return _UniFFI{{ canonical_type_name }}.intoCustom(this.read{{ builtin.canonical_name()|class_name_js }}());
+    }
+
+    {% when Type::Optional with (inner_type) -%}
+    // The Optional<T> type for {{ inner_type.canonical_name() }}.
+
+    read{{ canonical_type_name }}() {
+        const flag = this.view.getInt8(this._advance(1));
+        if (flag === 0) {
+            return null;
+        } else if (flag === 1) {
+            return this.read{{ inner_type.canonical_name()|class_name_js }}();
+        }
+        throw new InternalError("Unexpected flag byte for {{ canonical_type_name }}");
+    }
+
+    {% when Type::Sequence with (inner_type) -%}
+    // The Sequence<T> type for {{ inner_type.canonical_name() }}.
+
+    read{{ canonical_type_name }}() {
+        let count = this.view.getInt32(this._advance(4));
+        if (count < 0) {
+            throw new InternalError("Unexpected negative sequence length");
+        }
+        const items = [];
+        while (count > 0) {
+            items.push(this.read{{ inner_type.canonical_name()|class_name_js }}());
+            count -= 1;
+        }
+        return items;
+    }
+
+    {% when Type::Union with (name, members) -%}
+    {%- let union_name = typ.union_name()|class_name_js %}
+    // The union type {{ union_name }}.
+
+    read{{ canonical_type_name }}() {
+        const tag = this.view.getUint8(this._advance(1));
+        {%- for (variant, member_type) in typ.union_variants() %}
+        if (tag === {{ loop.index }}) {
+            return new {{ union_name }}.{{ variant }}(this.read{{ member_type.canonical_name()|class_name_js }}());
+        }
+        {%- endfor %}
+        throw new InternalError("Unexpected tag byte for {{ union_name }}");
+    }
+
+    {% when Type::Tuple with { name, elements } -%}
+    // The tuple type {{ name }}, which is represented as an array.
+
+    read{{ canonical_type_name }}() {
+        return [
+            {%- for element_type in elements %}
+            this.read{{ element_type.canonical_name()|class_name_js }}(),
+            {%- endfor %}
+        ];
+    }
+
+    {% when Type::Map with (key_type, value_type) -%}
+    // The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
+
+    read{{ canonical_type_name }}() {
+        let count = this.view.getInt32(this._advance(4));
+        if (count < 0) {
+            throw new InternalError("Unexpected negative map size");
+        }
+        const items = new Map();
+        while (count > 0) {
+            const key = this.read{{ key_type.canonical_name()|class_name_js }}();
+            items.set(key, this.read{{ value_type.canonical_name()|class_name_js }}());
+            count -= 1;
+        }
+        return items;
+    }
+
+    {%- endmatch -%}
+    {%- endfor %}
+}
@@ -0,0 +1,229 @@
+class RustBuffer {
+    constructor(capacity, len, data) {
+        this.capacity = capacity;
+        this.len = len;
+        // A pointer into the module's linear memory.
+        this.data = data;
+    }
+
+    static alloc(size) {
+        return rustCallWithError(InternalError, _UniFFILib.{{ ci.ffi_rustbuffer_alloc().name() }}, size);
+    }
+
+    static reserve(rbuf, additional) {
+        return rustCallWithError(InternalError, _UniFFILib.{{ ci.ffi_rustbuffer_reserve().name() }}, rbuf, additional);
+    }
+
+    free() {
+        return rustCallWithError(InternalError, _UniFFILib.{{ ci.ffi_rustbuffer_free().name() }}, this);
+    }
+
+    static readFrom(ptr) {
+        const view = memoryView();
+        return new RustBuffer(
+            view.getInt32(ptr, true),
+            view.getInt32(ptr + 4, true),
+            view.getUint32(ptr + 8, true),
+        );
+    }
+
+    writeTo(ptr) {
+        const view = memoryView();
+        view.setInt32(ptr, this.capacity, true);
+        view.setInt32(ptr + 4, this.len, true);
+        view.setUint32(ptr + 8, this.data, true);
+        // Ref https://github.com/mozilla/uniffi-rs/issues/334 for this weird "padding" field.
+        view.setBigInt64(ptr + 16, 0n, true);
+    }
+
+    // Copy the contents of the buffer out of linear memory.
+    toBytes() {
+        return memoryBytes(this.data, this.len).slice();
+    }
+
+    toString() {
+        return `RustBuffer(capacity=${this.capacity}, len=${this.len}, data=${this.data})`;
+    }
+
+    // Allocate a buffer using a RustBufferBuilder.
+    //
+    // The allocated buffer will be automatically freed if an error occurs, ensuring that
+    // we don't accidentally leak it.
+    static allocWithBuilder(callback) {
+        const builder = new RustBufferBuilder();
+        try {
+            callback(builder);
+            return builder.finalize();
+        } catch (e) {
+            builder.discard();
+            throw e;
+        }
+    }
+
+    // Consume a buffer using a RustBufferStream.
+    //
+    // The RustBuffer will be freed once the callback returns, ensuring that we don't
+    // leak it even if an error occurs.
+    consumeWithStream(callback) {
+        try {
+            const stream = new RustBufferStream(this.toBytes());
+            const value = callback(stream);
+            if (stream.remaining() !== 0) {
+                throw new InternalError("junk data left in buffer after consuming");
+            }
+            return value;
+        } finally {
+            this.free();
+        }
+    }
+
+    // For every type that lowers into a RustBuffer, we provide helper methods for
+    // conveniently doing the lifting and lowering. Putting them on this internal
+    // helper object (rather than, say, as methods on the public classes) makes it
+    // easier for us to hide these implementation details from consumers.
+
+    {%- for typ in ci.iter_types() -%}
+    {%- let canonical_type_name = typ.canonical_name()|class_name_js -%}
+    {%- match typ -%}
+
+    {% when Type::String -%}
+    // The primitive String type.
+
+    static allocFromString(value) {
+        const bytes = new TextEncoder().encode(value);
+        const rbuf = RustBuffer.alloc(bytes.length);
+        memoryBytes(rbuf.data, bytes.length).set(bytes);
+        rbuf.len = bytes.length;
+        return rbuf;
+    }
+
+    consumeIntoString() {
+        try {
+            return new TextDecoder().decode(this.toBytes());
+        } finally {
+            this.free();
+        }
+    }
+
+    {% when Type::Record with (record_name) -%}
+    // The Record type {{ record_name }}.
+
+    static allocFrom{{ canonical_type_name }}(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.write{{ canonical_type_name }}(v));
+    }
+
+    consumeInto{{ canonical_type_name }}() {
+        return this.consumeWithStream(stream => stream.read{{ canonical_type_name }}());
+    }
+
+    {% when Type::Optional with (inner_type) -%}
+    // The Optional<T> type for {{ inner_type.canonical_name() }}.
+
+    static allocFrom{{ canonical_type_name }}(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.write{{ canonical_type_name }}(v));
+    }
+
+    consumeInto{{ canonical_type_name }}() {
+        return this.consumeWithStream(stream => stream.read{{ canonical_type_name }}());
+    }
+
+    {% when Type::Sequence with (inner_type) -%}
+    // The Sequence<T> type for {{ inner_type.canonical_name() }}.
+
+    static allocFrom{{ canonical_type_name }}(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.write{{ canonical_type_name }}(v));
+    }
+
+    consumeInto{{ canonical_type_name }}() {
+        return this.consumeWithStream(stream => stream.read{{ canonical_type_name }}());
+    }
+
+    {% when Type::Bytes -%}
+    // The primitive Bytes type.
+
+    static allocFromBytes(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.writeBytes(v));
+    }
+
+    consumeIntoBytes() {
+        return this.consumeWithStream(stream => stream.readBytes());
+    }
+
+    {% when Type::Timestamp -%}
+    // The primitive Timestamp type.
+
+    static allocFromTimestamp(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.writeTimestamp(v));
+    }
+
+    consumeIntoTimestamp() {
+        return this.consumeWithStream(stream => stream.readTimestamp());
+    }
+
+    {% when Type::Duration -%}
+    // The primitive Duration type.
+
+    static allocFromDuration(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.writeDuration(v));
+    }
+
+    consumeIntoDuration() {
+        return this.consumeWithStream(stream => stream.readDuration());
+    }
+
+    {% when Type::Union with (name, members) -%}
+    {%- let union_name = typ.union_name()|class_name_js %}
+    // The union type {{ union_name }}.
+
+    static allocFrom{{ canonical_type_name }}(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.write{{ canonical_type_name }}(v));
+    }
+
+    consumeInto{{ canonical_type_name }}() {
+        return this.consumeWithStream(stream => stream.read{{ canonical_type_name }}());
+    }
+
+    {% when Type::Tuple with { name, elements } -%}
+    // The tuple type {{ name }}.
+
+    static allocFrom{{ canonical_type_name }}(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.write{{ canonical_type_name }}(v));
+    }
+
+    consumeInto{{ canonical_type_name }}() {
+        return this.consumeWithStream(stream => stream.read{{ canonical_type_name }}());
+    }
+
+    {% when Type::Map with (key_type, value_type) -%}
+    // The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
+
+    static allocFrom{{ canonical_type_name }}(v) {
+        return RustBuffer.allocWithBuilder(builder => builder.write{{ canonical_type_name }}(v));
+    }
+
+    consumeInto{{ canonical_type_name }}() {
+        return this.consumeWithStream(stream => stream.read{{ canonical_type_name }}());
+    }
+
+    {%- else -%}
+    {#- No code emitted for types that don't lower into a RustBuffer -#}
+    {%- endmatch -%}
+    {%- endfor %}
+}
+
+class ForeignBytes {
+    constructor(len, data) {
+        this.len = len;
+        // A pointer into the module's linear memory.
+        this.data = data;
+    }
+
+    writeTo(ptr) {
+        const view = memoryView();
+        view.setInt32(ptr, this.len, true);
+        view.setUint32(ptr + 4, this.data, true);
+        // Ref https://github.com/mozilla/uniffi-rs/issues/334 for these weird "padding" fields.
+        view.setBigInt64(ptr + 8, 0n, true);
+        view.setInt32(ptr + 16, 0, true);
+    }
+}
@@ -0,0 +1,14 @@
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}
+
+export function {{ func.name()|fn_name_js }}({%- call js::arg_list_decl(func) -%}) {
+    const _retval = {% call js::to_ffi_call(func) %};
+    return {{ "_retval"|lift_js(return_type, ci) }};
+}
+
+{% when None -%}
+
+export function {{ func.name()|fn_name_js }}({%- call js::arg_list_decl(func) -%}) {
+    {% call js::to_ffi_call(func) %};
+}
+{% endmatch %}
@@ -0,0 +1,24 @@
+// Sum types for the unions used in the UDL.
+// Each member type of a union gets a variant subclass holding a value of that type,
+// named positionally since union members don't have names of their own.
+
+{%- for typ in ci.iter_types() %}
+{%- match typ %}
+{%- when Type::Union with (name, members) %}
+{%- let union_name = typ.union_name()|class_name_js %}
+
+export class {{ union_name }} {
+    constructor(value) {
+        this.value = value;
+    }
+
+    toString() {
+        return `{{ union_name }}.${this.constructor.name}(${this.value})`;
+    }
+}
+{%- for (variant, member_type) in typ.union_variants() %}
+{{ union_name }}.{{ variant }} = class {{ variant }} extends {{ union_name }} {};
+{%- endfor %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
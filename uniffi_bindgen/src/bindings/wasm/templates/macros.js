@@ -0,0 +1,73 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `_arg_list_ffi_call` (we use  `var_name_js` in `lower_js`)
+#}
+
+{%- macro to_ffi_call(func) -%}
+rustCallWithError(
+    {%- match func.throws() -%}
+    {%- when Some with (e) -%}
+    {{ e|class_name_js }},
+    {%- else -%}
+    InternalError,
+    {%- endmatch -%}
+    _UniFFILib.{{ func.ffi_func().name() }}
+    {%- if func.arguments().len() > 0 %},{% endif -%}
+    {%- call _arg_list_ffi_call(func) -%}
+)
+{%- endmacro -%}
+
+{%- macro to_ffi_call_with_prefix(prefix, func) -%}
+rustCallWithError(
+    {%- match func.throws() -%}
+    {%- when Some with (e) -%}
+    {{ e|class_name_js }},
+    {%- else -%}
+    InternalError,
+    {%- endmatch -%}
+    _UniFFILib.{{ func.ffi_func().name() }},
+    {{- prefix }}
+    {%- if func.arguments().len() > 0 %},{% endif -%}
+    {%- call _arg_list_ffi_call(func) -%}
+)
+{%- endmacro -%}
+
+{%- macro _arg_list_ffi_call(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}
+        {{- arg.name()|lower_borrowed_js(arg.type_()) }}
+        {%- else %}
+        {{- arg.name()|lower_js(arg.type_(), ci) }}
+        {%- endif %}
+        {%- if !loop.last %},{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// Arglist as used in JS declarations of methods, functions and constructors.
+// Note the var_name_js filter.
+-#}
+
+{% macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name_js }}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|literal_js }}
+        {%- else %}
+        {%- endmatch %}
+        {%- if !loop.last %},{{ " " }}{% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#-
+// Arglist as used in the _UniFFILib function declations.
+// Note type_ffi filters. The trailing `RustError` out-param is passed by
+// pointer and needs no special handling, so it is not listed here.
+-#}
+{%- macro arg_list_ffi_decl(func) %}
+    {%- for arg in func.arguments() %}
+        {{- arg.type_()|type_ffi }}
+        {%- if !loop.last %}{{ ", " }}{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
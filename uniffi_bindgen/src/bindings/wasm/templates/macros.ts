@@ -0,0 +1,19 @@
+{#
+// Arguments in TypeScript declarations. Arguments with a default value are optional.
+// This works for both function arguments and record fields.
+#}
+{%- macro arg_list_decl(args) -%}
+    {%- for arg in args -%}
+        {{ arg.name()|var_name_js }}{% if arg.default_value().is_some() %}?{% endif %}: {{ arg.type_()|type_ts }}
+        {%- if !loop.last %},{{ " " }}{% endif -%}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro return_type(func) -%}
+    {%- match func.return_type() -%}
+    {%- when Some with (return_type) -%}
+    {{ return_type|type_ts }}
+    {%- when None -%}
+    void
+    {%- endmatch -%}
+{%- endmacro -%}
@@ -0,0 +1,114 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// TypeScript declarations for the public interface of the accompanying `.mjs` module.
+
+export class InternalError extends Error {}
+
+/**
+ * Thrown when Rust couldn't lift one of the arguments that it was passed, which means that
+ * these bindings have a bug or are out of date.
+ */
+export class InvalidArgumentError extends InternalError {}
+
+{% for typ in ci.iter_types() -%}
+{%- match typ -%}
+{%- when Type::Custom with { name, builtin } -%}
+{%- match config.custom_type(name).type_name() -%}
+{%- when Some with (type_name) -%}
+export type {{ typ|type_ts }} = {{ type_name }};
+{% when None -%}
+export type {{ typ|type_ts }} = {{ builtin|type_ts }};
+{% endmatch -%}
+{%- else -%}
+{%- endmatch -%}
+{%- endfor %}
+
+{% for e in ci.iter_error_definitions() -%}
+{{ e.docs()|doc_comment_ts(0) }}export class {{ e.name()|class_name_js }} extends Error {
+    {%- for value in e.values() %}
+    static {{ value|class_name_js }}: typeof {{ e.name()|class_name_js }};
+    {%- endfor %}
+}
+
+{% endfor -%}
+
+{%- for e in ci.iter_enum_definitions() -%}
+{{ e.docs()|doc_comment_ts(0) }}export declare const {{ e.name()|class_name_js }}: {
+    {%- for variant in e.variants() %}
+    readonly {{ variant|enum_variant_js }}: {{ loop.index }};
+    {%- endfor %}
+};
+export type {{ e.name()|class_name_js }} = {% for variant in e.variants() %}{{ loop.index }}{% if !loop.last %}{{ " | " }}{% endif %}{% endfor %};
+
+{% endfor -%}
+
+{%- for rec in ci.iter_record_definitions() -%}
+{{ rec.docs()|doc_comment_ts(0) }}export class {{ rec.name()|class_name_js }} {
+    constructor({% call ts::arg_list_decl(rec.fields()) %});
+    {%- for field in rec.fields() %}
+    {{ field.docs()|doc_comment_ts(4) }}{{ field.name()|var_name_js }}: {{ field.type_()|type_ts }};
+    {%- endfor %}
+}
+
+{% endfor -%}
+
+{%- for typ in ci.iter_types() -%}
+{%- match typ -%}
+{%- when Type::Union with (name, members) -%}
+export class {{ typ|type_ts }} {
+    {%- for (variant, member_type) in typ.union_variants() %}
+    static {{ variant }}: new (value: {{ member_type|type_ts }}) => {{ typ|type_ts }};
+    {%- endfor %}
+    value: {% for member_type in members %}{{ member_type|type_ts }}{% if !loop.last %}{{ " | " }}{% endif %}{% endfor %};
+}
+
+{# Whitespace at the end of a match arm goes missing when the next type is a union too. #}
+{% else -%}
+{%- endmatch -%}
+{%- endfor -%}
+
+{%- for c in ci.iter_constant_definitions() -%}
+export declare const {{ c.name()|const_name_js }}: {{ c.type_()|type_ts }};
+
+{% endfor -%}
+
+{%- for func in ci.iter_function_definitions() -%}
+{{ func.docs()|doc_comment_ts(0) }}export function {{ func.name()|fn_name_js }}({% call ts::arg_list_decl(func.arguments()) %}): {% call ts::return_type(func) %};
+
+{% endfor -%}
+
+{%- for obj in ci.iter_object_definitions() -%}
+{{ obj.class_docs().as_deref()|doc_comment_ts(0) }}export class {{ obj.name()|class_name_js }} {
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    constructor({% call ts::arg_list_decl(cons.arguments()) %});
+    {%- when None %}
+    private constructor();
+    {%- endmatch %}
+    {%- for cons in obj.alternate_constructors() %}
+    static {{ cons.name()|fn_name_js }}({% call ts::arg_list_decl(cons.arguments()) %}): {{ obj.name()|class_name_js }};
+    {%- endfor %}
+    {%- for meth in obj.static_methods() %}
+    {{ meth.docs()|doc_comment_ts(4) }}static {{ meth.name()|fn_name_js }}({% call ts::arg_list_decl(meth.arguments()) %}): {% call ts::return_type(meth) %};
+    {%- endfor %}
+    destroy(): void;
+    {%- for prop in obj.properties() %}
+    {{ prop.docs()|doc_comment_ts(4) }}{% if prop.readonly() %}{{ "readonly " }}{% endif %}{{ prop.name()|var_name_js }}: {{ prop.type_()|type_ts }};
+    {%- endfor %}
+    {%- for meth in obj.methods() %}
+    {{ meth.docs()|doc_comment_ts(4) }}{{ meth.name()|fn_name_js }}({% call ts::arg_list_decl(meth.arguments()) %}): {% call ts::return_type(meth) %};
+    {%- endfor %}
+}
+
+{% endfor -%}
+
+/**
+ * Check that every object and buffer passed across the FFI has been freed.
+ *
+ * This is intended for use in tests, and only works if the Rust code was built with
+ * the `leak-tracking` feature of the `uniffi` crate.
+ */
+export function assertNoLeaks(): void;
+
+{%- import "macros.ts" as ts -%}
@@ -0,0 +1,67 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// Common helper code.
+//
+// Like the other foreign-language bindings, the helpers that marshal values across
+// the FFI are bundled inline here, so that they always exactly match what's expected
+// by the Rust code on the other side of the interface.
+//
+// This module talks to the component compiled for `wasm32-unknown-unknown`, which it
+// loads when first imported (using top-level `await`). It works in node and in (module)
+// web workers, without any further dependencies.
+{%- for imp in config.custom_type_imports() %}
+import {{ imp }};
+{%- endfor %}
+
+{% include "RustBufferTemplate.js" %}
+{% include "RustBufferStream.js" %}
+{% include "RustBufferBuilder.js" %}
+{% include "CustomTypeTemplate.js" %}
+
+// Error definitions
+{% include "ErrorTemplate.js" %}
+
+{%- for rec in ci.iter_record_definitions() %}
+{% include "RecordFfiStructTemplate.js" %}
+{%- endfor %}
+
+{% include "NamespaceLibraryTemplate.js" %}
+
+// Public interface members begin here.
+
+{% for e in ci.iter_enum_definitions() %}
+{% include "EnumTemplate.js" %}
+{%- endfor -%}
+
+{%- for rec in ci.iter_record_definitions() %}
+{% include "RecordTemplate.js" %}
+{% endfor %}
+
+{% include "UnionTemplate.js" %}
+
+{%- for c in ci.iter_constant_definitions() %}
+export const {{ c.name()|const_name_js }} = {{ c.value()|literal_js }};
+{%- endfor %}
+
+{% for func in ci.iter_function_definitions() %}
+{% include "TopLevelFunctionTemplate.js" %}
+{% endfor %}
+
+{% for obj in ci.iter_object_definitions() %}
+{% include "ObjectTemplate.js" %}
+{% endfor %}
+
+// Check that every object and buffer passed across the FFI has been freed.
+//
+// This is intended for use in tests, and only works if the Rust code was built with
+// the `leak-tracking` feature of the `uniffi` crate.
+export function assertNoLeaks() {
+    const _retval = rustCallWithError(InternalError, _UniFFILib.{{ ci.ffi_leak_report().name() }});
+    const leaks = {{ "_retval"|lift_js(Type::String, ci) }};
+    if (leaks.length > 0) {
+        throw new Error(`Leaked across the FFI: ${leaks}`);
+    }
+}
+
+{% import "macros.js" as js %}
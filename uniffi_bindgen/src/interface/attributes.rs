@@ -26,10 +26,20 @@ use anyhow::{bail, Result};
 #[derive(Debug, Clone, Hash)]
 pub(super) enum Attribute {
     ByRef,
+    Custom,
     Error,
+    External(String),
+    Map,
     Name(String),
+    PointerHandle,
+    RwLock,
+    SelfType(SelfType),
+    ThreadConfined,
     Threadsafe,
     Throws(String),
+    Traits(Vec<ObjectTrait>),
+    Tuple,
+    Value(String),
 }
 
 impl Attribute {
@@ -38,6 +48,51 @@ impl Attribute {
     }
 }
 
+/// Represents the type of the `self` receiver of a method, as given by the `[Self=...]` attribute.
+///
+/// `[Self=ByArc]` means the method takes `self: Arc<Self>` (or, on a constructor, that it
+/// returns `Arc<Self>`), and `[Self=ByRef]` means that a method of an `[RwLock]` interface
+/// takes `&self` rather than `&mut self`, and so only needs the read lock.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(super) enum SelfType {
+    ByArc,
+    ByRef,
+}
+
+impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for SelfType {
+    type Error = anyhow::Error;
+    fn try_from(nm: &weedle::attribute::IdentifierOrString<'_>) -> Result<Self, Self::Error> {
+        Ok(match name_from_id_or_string(nm).as_str() {
+            "ByArc" => SelfType::ByArc,
+            "ByRef" => SelfType::ByRef,
+            other => bail!("Unsupported Self Type: {:?}", other),
+        })
+    }
+}
+
+/// Represents a Rust trait that an interface exposes to the foreign language bindings,
+/// as listed in its `[Traits=(...)]` attribute.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub(super) enum ObjectTrait {
+    Debug,
+    Display,
+    Eq,
+    Hash,
+}
+
+impl TryFrom<&weedle::common::Identifier<'_>> for ObjectTrait {
+    type Error = anyhow::Error;
+    fn try_from(nm: &weedle::common::Identifier<'_>) -> Result<Self, Self::Error> {
+        Ok(match nm.0 {
+            "Debug" => ObjectTrait::Debug,
+            "Display" => ObjectTrait::Display,
+            "Eq" => ObjectTrait::Eq,
+            "Hash" => ObjectTrait::Hash,
+            other => bail!("Unsupported Trait: {:?}", other),
+        })
+    }
+}
+
 /// Convert a weedle `ExtendedAttribute` into an `Attribute` for a `ComponentInterface` member,
 /// or error out if the attribute is not supported.
 impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
@@ -49,21 +104,50 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
             // Matches plain named attributes like "[ByRef"].
             weedle::attribute::ExtendedAttribute::NoArgs(attr) => match (attr.0).0 {
                 "ByRef" => Ok(Attribute::ByRef),
+                "Custom" => Ok(Attribute::Custom),
                 "Error" => Ok(Attribute::Error),
+                "Map" => Ok(Attribute::Map),
+                "PointerHandle" => Ok(Attribute::PointerHandle),
+                "RwLock" => Ok(Attribute::RwLock),
+                "ThreadConfined" => Ok(Attribute::ThreadConfined),
                 "Threadsafe" => Ok(Attribute::Threadsafe),
+                "Tuple" => Ok(Attribute::Tuple),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
             weedle::attribute::ExtendedAttribute::Ident(identity) => {
                 match identity.lhs_identifier.0 {
+                    "External" => Ok(Attribute::External(name_from_id_or_string(&identity.rhs))),
                     "Name" => Ok(Attribute::Name(name_from_id_or_string(&identity.rhs))),
+                    "Self" => Ok(Attribute::SelfType(SelfType::try_from(&identity.rhs)?)),
                     "Throws" => Ok(Attribute::Throws(name_from_id_or_string(&identity.rhs))),
+                    "Value" => Ok(Attribute::Value(name_from_id_or_string(&identity.rhs))),
                     _ => anyhow::bail!(
                         "Attribute identity Identifier not supported: {:?}",
                         identity.lhs_identifier.0
                     ),
                 }
             }
+            // Matches list-style attributes like ["Traits=(Display, Eq)"]
+            weedle::attribute::ExtendedAttribute::IdentList(attr_list) => {
+                match attr_list.identifier.0 {
+                    "Traits" => {
+                        let mut traits = Vec::new();
+                        for nm in &attr_list.list.body.list {
+                            let trait_ = ObjectTrait::try_from(nm)?;
+                            if traits.contains(&trait_) {
+                                bail!("Duplicated Trait: {:?}", trait_);
+                            }
+                            traits.push(trait_);
+                        }
+                        Ok(Attribute::Traits(traits))
+                    }
+                    _ => anyhow::bail!(
+                        "Attribute identity list not supported: {:?}",
+                        attr_list.identifier.0
+                    ),
+                }
+            }
             _ => anyhow::bail!("Attribute not supported: {:?}", weedle_attribute),
         }
     }
@@ -197,6 +281,32 @@ impl InterfaceAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::Threadsafe))
     }
+
+    pub fn pointer_handle(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::PointerHandle))
+    }
+
+    pub fn rwlock(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::RwLock))
+    }
+
+    pub fn thread_confined(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ThreadConfined))
+    }
+
+    pub fn traits(&self) -> &[ObjectTrait] {
+        self.0
+            .iter()
+            .find_map(|attr| match attr {
+                Attribute::Traits(traits) => Some(traits.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttributes {
@@ -205,22 +315,167 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttribu
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Threadsafe => Ok(()),
+            Attribute::Threadsafe
+            | Attribute::PointerHandle
+            | Attribute::RwLock
+            | Attribute::ThreadConfined
+            | Attribute::Traits(_) => Ok(()),
             _ => bail!(format!("{:?} not supported for interface classes", attr)),
         })?;
+        let attrs = Self(attrs);
+        // Objects with pointer handles can be called from any thread at once, without locking.
+        if attrs.pointer_handle() && !attrs.threadsafe() {
+            bail!("PointerHandle is only supported for Threadsafe interface classes");
+        }
+        // Threadsafe objects do their own locking, so there's nothing for the `RwLock` to do.
+        if attrs.rwlock() && attrs.threadsafe() {
+            bail!("RwLock is not supported for Threadsafe interface classes");
+        }
+        // Thread-confined objects are only ever used from one thread, so they don't need locking.
+        if attrs.thread_confined() && (attrs.threadsafe() || attrs.rwlock()) {
+            bail!("ThreadConfined is not supported for Threadsafe or RwLock interface classes");
+        }
+        // Some foreign languages (like Swift) can only hash things that they can compare.
+        if attrs.traits().contains(&ObjectTrait::Hash) && !attrs.traits().contains(&ObjectTrait::Eq)
+        {
+            bail!("The Hash trait is only supported together with the Eq trait");
+        }
+        Ok(attrs)
+    }
+}
+
+/// Represents UDL attributes that might appear on a `typedef`.
+///
+/// This supports the `[Custom]` attribute, for declaring a custom type that wraps
+/// one of the builtin types rather than simply giving another name to it, the
+/// `[Tuple]` attribute, for declaring a tuple of the types listed in the typedef, the
+/// `[Map]` attribute, for declaring a map from the first type listed to the second, and the
+/// `[External="crate_name"]` attribute, for using a record defined by another component.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct TypedefAttributes(Vec<Attribute>);
+
+impl TypedefAttributes {
+    pub fn is_custom(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Custom))
+    }
+
+    pub fn is_tuple(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Tuple))
+    }
+
+    pub fn is_map(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Map))
+    }
+
+    pub fn external_crate_name(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::External(crate_name) => Some(crate_name.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Custom | Attribute::External(_) | Attribute::Map | Attribute::Tuple => {
+                Ok(())
+            }
+            _ => bail!(format!("{:?} not supported for typedefs", attr)),
+        })?;
+        if attrs.len() > 1 {
+            bail!("typedefs cannot have more than one attribute");
+        }
+        Ok(Self(attrs))
+    }
+}
+
+/// Represents UDL attributes that might appear on a constant.
+///
+/// This supports the `[Value=...]` attribute, which gives the value of the constant.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct ConstantAttributes(Vec<Attribute>);
+
+impl ConstantAttributes {
+    pub(super) fn get_value(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Value(value) => Some(value.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstantAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Value(_) => Ok(()),
+            _ => bail!(format!("{:?} not supported for constants", attr)),
+        })?;
         Ok(Self(attrs))
     }
 }
 
-// There may be some divergence between Methods and Functions at some point,
-// but not yet.
-pub(super) type MethodAttributes = FunctionAttributes;
+/// Represents UDL attributes that might appear on a method.
+///
+/// This supports the `[Throws=ErrorName]` attribute for methods that can produce
+/// an error, and the `[Self=...]` attribute for declaring the type of the method's receiver.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct MethodAttributes(Vec<Attribute>);
+
+impl MethodAttributes {
+    /// The attributes of a method that only needs to read the object, like a property getter.
+    pub(super) fn self_by_ref() -> Self {
+        Self(vec![Attribute::SelfType(SelfType::ByRef)])
+    }
+
+    pub(super) fn get_throws_err(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            // This will hopefully return a helpful compilation error
+            // if the error is not defined.
+            Attribute::Throws(inner) => Some(inner.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub(super) fn get_self_by_arc(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByArc)))
+    }
+
+    pub(super) fn get_self_by_ref(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByRef)))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::SelfType(_) => Ok(()),
+            Attribute::Throws(_) => Ok(()),
+            _ => bail!(format!("{:?} not supported for methods", attr)),
+        })?;
+        Ok(Self(attrs))
+    }
+}
 
 // Constructors can have a `Name` attribute, so need their own implementation.
-/// Represents UDL attributes that might appear on a function.
+/// Represents UDL attributes that might appear on a constructor.
 ///
-/// This supports the `[Throws=ErrorName]` attribute for functions that
-/// can produce an error.
+/// This supports the `[Throws=ErrorName]` attribute for constructors that
+/// can produce an error, the `[Name=...]` attribute for alternate constructors,
+/// and the `[Self=ByArc]` attribute for constructors that return `Arc<Self>`.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct ConstructorAttributes(Vec<Attribute>);
 
@@ -239,6 +494,12 @@ impl ConstructorAttributes {
             _ => None,
         })
     }
+
+    pub(super) fn get_self_by_arc(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByArc)))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttributes {
@@ -249,6 +510,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::Throws(_) => Ok(()),
             Attribute::Name(_) => Ok(()),
+            Attribute::SelfType(SelfType::ByArc) => Ok(()),
             _ => bail!(format!("{:?} not supported for constructors", attr)),
         })?;
         Ok(Self(attrs))
@@ -316,6 +578,23 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_self_type() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Self=ByArc").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::SelfType(SelfType::ByArc)));
+
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Self=ByRef").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::SelfType(SelfType::ByRef)));
+
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Self=ByBox").unwrap();
+        let err = Attribute::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported Self Type: \"ByBox\"");
+
+        Ok(())
+    }
+
     #[test]
     fn test_unsupported() -> Result<()> {
         let (_, node) =
@@ -370,6 +649,44 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_method_attributes() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Throws=Error]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_throws_err(), Some("Error")));
+        assert!(!attrs.get_self_by_arc());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Throws=Error, Self=ByArc]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_throws_err(), Some("Error")));
+        assert!(attrs.get_self_by_arc());
+        assert!(!attrs.get_self_by_ref());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Self=ByRef]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(!attrs.get_self_by_arc());
+        assert!(attrs.get_self_by_ref());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_attributes_not_supported_for_methods() -> Result<()> {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Self=ByArc, ByRef]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "ByRef not supported for methods");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Self=ByArc]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "SelfType(ByArc) not supported for functions or methods"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_constructor_attributes() -> Result<()> {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Throws=Error]").unwrap();
@@ -389,6 +706,11 @@ mod test {
         let attrs = ConstructorAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), Some("Error")));
         assert!(matches!(attrs.get_name(), Some("MyFactory")));
+        assert!(!attrs.get_self_by_arc());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Self=ByArc]").unwrap();
+        let attrs = ConstructorAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_self_by_arc());
 
         Ok(())
     }
@@ -440,6 +762,112 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_pointer_handle_attribute() -> Result<()> {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Threadsafe, PointerHandle]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.pointer_handle());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Threadsafe]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.pointer_handle());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[PointerHandle]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "PointerHandle is only supported for Threadsafe interface classes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rwlock_attribute() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[RwLock]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.rwlock());
+        assert!(!attrs.threadsafe());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.rwlock());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Threadsafe, RwLock]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "RwLock is not supported for Threadsafe interface classes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_thread_confined_attribute() -> Result<()> {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ThreadConfined]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.thread_confined());
+        assert!(!attrs.threadsafe());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.thread_confined());
+
+        for list in &["[Threadsafe, ThreadConfined]", "[RwLock, ThreadConfined]"] {
+            let (_, node) = weedle::attribute::ExtendedAttributeList::parse(list).unwrap();
+            let err = InterfaceAttributes::try_from(&node).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ThreadConfined is not supported for Threadsafe or RwLock interface classes"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_traits_attribute() -> Result<()> {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Traits=(Display, Eq, Hash)]")
+                .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(
+            attrs.traits(),
+            &[ObjectTrait::Display, ObjectTrait::Eq, ObjectTrait::Hash]
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Threadsafe]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.traits().is_empty());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Traits=(Debug, Clone)]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported Trait: \"Clone\"");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Traits=(Debug, Debug)]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Duplicated Trait: Debug");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Traits=(Hash)]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The Hash trait is only supported together with the Eq trait"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Traits=(Display)]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Traits([Display]) not supported for functions or methods"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_other_attributes_not_supported_for_interfaces() -> Result<()> {
         let (_, node) =
@@ -448,4 +876,78 @@ mod test {
         assert_eq!(err.to_string(), "Error not supported for interface classes");
         Ok(())
     }
+
+    #[test]
+    fn test_custom_attribute() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_custom());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_custom());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
+        let err = TypedefAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "ByRef not supported for typedefs");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tuple_attribute() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Tuple]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_tuple());
+        assert!(!attrs.is_custom());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_tuple());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom, Tuple]").unwrap();
+        let err = TypedefAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "typedefs cannot have more than one attribute"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_attribute() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Map]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_map());
+        assert!(!attrs.is_tuple());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Map, Tuple]").unwrap();
+        let err = TypedefAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "typedefs cannot have more than one attribute"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_external_attribute() -> Result<()> {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[External=\"other-crate\"]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.external_crate_name(), Some("other-crate"));
+        assert!(!attrs.is_custom());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.external_crate_name(), None);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[External=other]").unwrap();
+        let err = ConstantAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "External(\"other\") not supported for constants"
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,204 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Constant definitions for a `ComponentInterface`.
+//!
+//! This module converts constants declared in the UDL namespace into [`Constant`]
+//! structures that can be added to a `ComponentInterface`. A [`Constant`] has a name,
+//! a type and a literal value, which the foreign language bindings can emit directly
+//! without needing to call into the Rust code over the FFI.
+//!
+//! The WebIDL grammar doesn't allow `const` members in a namespace, so constants are
+//! declared as `readonly attribute` members with a `[Value]` attribute giving their value.
+//! Since WebIDL attributes can't contain a number, the value is written as a string:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! namespace example {
+//!   [Value="50"] readonly attribute u32 MAX_TABS;
+//!   [Value="1.2.3"] readonly attribute string VERSION;
+//! };
+//! # "##)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Will result in two [`Constant`] members being added to the resulting [`ComponentInterface`]:
+//!
+//! ```
+//! # use uniffi_bindgen::interface::{Literal, Radix, Type};
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! # namespace example {
+//! #   [Value="50"] readonly attribute u32 MAX_TABS;
+//! #   [Value="1.2.3"] readonly attribute string VERSION;
+//! # };
+//! # "##)?;
+//! let max_tabs = ci.get_constant_definition("MAX_TABS").unwrap();
+//! assert_eq!(max_tabs.type_(), Type::UInt32);
+//! assert!(matches!(max_tabs.value(), Literal::UInt(50, Radix::Decimal, Type::UInt32)));
+//! let version = ci.get_constant_definition("VERSION").unwrap();
+//! assert!(matches!(version.value(), Literal::String(v) if v == "1.2.3"));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::convert::TryFrom;
+
+use anyhow::{bail, Result};
+use weedle::Parse;
+
+use super::attributes::ConstantAttributes;
+use super::literal::{convert_default_value, Literal};
+use super::types::Type;
+use super::{APIConverter, ComponentInterface};
+
+/// Represents a named constant value, declared in the namespace.
+#[derive(Debug, Clone, Hash)]
+pub struct Constant {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) value: Literal,
+}
+
+impl Constant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn type_(&self) -> Type {
+        self.type_.clone()
+    }
+    pub fn value(&self) -> Literal {
+        self.value.clone()
+    }
+}
+
+impl APIConverter<Constant> for weedle::namespace::AttributeNamespaceMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Constant> {
+        let name = self.identifier.0.to_string();
+        let attributes = match &self.attributes {
+            Some(attrs) => ConstantAttributes::try_from(attrs)?,
+            None => Default::default(),
+        };
+        let value = match attributes.get_value() {
+            Some(v) => v,
+            None => bail!("constant {} must have a value like `[Value=\"50\"]`", name),
+        };
+        let type_ = ci.resolve_type_expression(&self.type_)?;
+        Ok(Constant {
+            value: convert_constant_value(&name, value, &type_)?,
+            name,
+            type_,
+        })
+    }
+}
+
+/// Interpret the string given in a `[Value]` attribute as a literal of the given type.
+///
+/// Strings are taken exactly as written. Anything else is parsed the same way as the
+/// default value of an argument, so e.g. numbers can be given in hex.
+fn convert_constant_value(name: &str, value: &str, type_: &Type) -> Result<Literal> {
+    Ok(match type_ {
+        Type::String => Literal::String(value.to_string()),
+        Type::UInt8
+        | Type::Int8
+        | Type::UInt16
+        | Type::Int16
+        | Type::UInt32
+        | Type::Int32
+        | Type::UInt64
+        | Type::Int64
+        | Type::Float32
+        | Type::Float64
+        | Type::Boolean => match weedle::literal::DefaultValue::parse(value) {
+            Ok(("", literal)) => convert_default_value(&literal, type_)?,
+            _ => bail!("invalid value for constant {}: {}", name, value),
+        },
+        _ => bail!(
+            "constant {} must be a number, boolean or string, not {}",
+            name,
+            type_.canonical_name()
+        ),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::literal::Radix;
+    use super::*;
+
+    #[test]
+    fn test_constants() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{
+                [Value="-1"] readonly attribute i8 NEGATIVE;
+                [Value="0xFF"] readonly attribute u64 HEX;
+                [Value="2.5"] readonly attribute f64 FLOAT;
+                [Value=true] readonly attribute boolean ENABLED;
+                [Value="hello world"] readonly attribute string GREETING;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(ci.iter_constant_definitions().len(), 5);
+        assert!(matches!(
+            ci.get_constant_definition("NEGATIVE").unwrap().value(),
+            Literal::Int(-1, Radix::Decimal, Type::Int8)
+        ));
+        assert!(matches!(
+            ci.get_constant_definition("HEX").unwrap().value(),
+            Literal::UInt(255, Radix::Hexadecimal, Type::UInt64)
+        ));
+        assert!(
+            matches!(ci.get_constant_definition("FLOAT").unwrap().value(), Literal::Float(v, Type::Float64) if v == "2.5")
+        );
+        assert!(matches!(
+            ci.get_constant_definition("ENABLED").unwrap().value(),
+            Literal::Boolean(true)
+        ));
+        assert!(
+            matches!(ci.get_constant_definition("GREETING").unwrap().value(), Literal::String(v) if v == "hello world")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_on_invalid_constants() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{
+                readonly attribute u32 MISSING;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "constant MISSING must have a value like `[Value=\"50\"]`"
+        );
+
+        const UDL2: &str = r#"
+            namespace test{
+                [Value="fifty"] readonly attribute u32 INVALID;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(err.to_string(), "invalid value for constant INVALID: fifty");
+
+        const UDL3: &str = r#"
+            namespace test{
+                [Value="1"] readonly attribute sequence<u32> NUMBERS;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "constant NUMBERS must be a number, boolean or string, not Sequenceu32"
+        );
+
+        const UDL4: &str = r#"
+            namespace test{
+                [Value="1"] readonly attribute u32 TWICE;
+                [Value="2"] readonly attribute u32 TWICE;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL4).unwrap_err();
+        assert_eq!(err.to_string(), "duplicate constant definition: TWICE");
+        Ok(())
+    }
+}
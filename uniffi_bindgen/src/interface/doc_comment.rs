@@ -0,0 +1,213 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Doc comments for items in a `ComponentInterface`.
+//!
+//! The WebIDL parser discards comments, so this module finds `///`-style doc comments
+//! in the UDL source and matches them back up with the items they document, allowing
+//! the foreign language bindings to pass them on to their users:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! namespace example {
+//!   /// Add two numbers together.
+//!   /// Overflow wraps around.
+//!   u32 add(u32 a, u32 b);
+//! };
+//! # "##)?;
+//! let func = ci.get_function_definition("add").unwrap();
+//! assert_eq!(func.docs(), Some("Add two numbers together.\nOverflow wraps around."));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! A doc comment applies to the item whose name follows it, up until the end of the
+//! enclosing declaration (i.e. the next `;`, `{` or `}`).
+//!
+//! Since weedle's syntax nodes borrow their identifiers from the UDL source, we can find
+//! where an item was declared by comparing the address of its identifier to the address of
+//! the source, without needing any help from the parser.
+
+use std::hash::{Hash, Hasher};
+
+/// The doc comment attached to an item in the UDL, if any.
+///
+/// Doc comments don't affect the FFI, so they are deliberately left out of the hash that
+/// is used to checksum the interface.
+#[derive(Debug, Clone, Default)]
+pub(super) struct DocComment(Option<String>);
+
+impl DocComment {
+    pub(super) fn as_deref(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+
+impl Hash for DocComment {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// The doc comments found in a UDL source string, indexed by where they apply.
+#[derive(Debug, Default)]
+pub(super) struct DocComments {
+    // The address of the start of the source string.
+    base: usize,
+    // The range of offsets into the source to which each comment applies, and its text.
+    comments: Vec<(usize, usize, String)>,
+}
+
+impl DocComments {
+    pub(super) fn from_source(source: &str) -> Self {
+        let mut comments = Vec::new();
+        let mut block: Vec<&str> = Vec::new();
+        let mut offset = 0;
+        for line in source.split_inclusive('\n') {
+            offset += line.len();
+            let trimmed = line.trim();
+            if trimmed.starts_with("///") && !trimmed.starts_with("////") {
+                let text = &trimmed[3..];
+                block.push(text.strip_prefix(' ').unwrap_or(text));
+            } else if !block.is_empty() {
+                // The first line after the comment starts the documented declaration,
+                // so the comment applies from here until the end of that declaration.
+                let start = offset - line.len();
+                let end = start + declaration_len(&source[start..]);
+                comments.push((start, end, block.join("\n")));
+                block.clear();
+            }
+        }
+        DocComments {
+            base: source.as_ptr() as usize,
+            comments,
+        }
+    }
+
+    /// Get the doc comment for the item with the given identifier, which must be borrowed
+    /// from the source string that these comments were found in.
+    pub(super) fn get(&self, ident: &str) -> DocComment {
+        let offset = match (ident.as_ptr() as usize).checked_sub(self.base) {
+            Some(offset) => offset,
+            None => return DocComment::default(),
+        };
+        DocComment(
+            self.comments
+                .iter()
+                .find(|(start, end, _)| *start <= offset && offset < *end)
+                .map(|(_, _, text)| text.clone()),
+        )
+    }
+}
+
+/// Find the length of the declaration at the start of the given source, skipping over
+/// any comments since they might contain punctuation.
+fn declaration_len(source: &str) -> usize {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let code = match line.find("//") {
+            Some(idx) => &line[..idx],
+            None => line,
+        };
+        if let Some(idx) = code.find(&[';', '{', '}'][..]) {
+            return offset + idx;
+        }
+        offset += line.len();
+    }
+    offset
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::ComponentInterface;
+    use super::*;
+
+    #[test]
+    fn test_doc_comments() {
+        const UDL: &str = r#"
+            /// The namespace.
+            namespace test {
+                /// A function.
+                void function();
+                // Not a doc comment.
+                void undocumented();
+            };
+
+            /// A record,
+            /// with two lines.
+            dictionary Record {
+                ///A field; with punctuation.
+                u32 field;
+                u32 other_field;
+            };
+
+            /// An enum.
+            enum Enum { "one", "two" };
+
+            /// An error.
+            [Error]
+            enum Error { "Oops" };
+
+            /// An object.
+            interface Object {
+                /// A method.
+                [Throws=Error]
+                void method(u32 arg);
+                void other_method();
+                /// A static method.
+                static void static_method();
+                /// A property.
+                attribute u32 property;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.get_function_definition("function").unwrap().docs(),
+            Some("A function.")
+        );
+        assert_eq!(
+            ci.get_function_definition("undocumented").unwrap().docs(),
+            None
+        );
+
+        let record = ci.get_record_definition("Record").unwrap();
+        assert_eq!(record.docs(), Some("A record,\nwith two lines."));
+        assert_eq!(
+            record.fields()[0].docs(),
+            Some("A field; with punctuation.")
+        );
+        assert_eq!(record.fields()[1].docs(), None);
+
+        assert_eq!(
+            ci.get_enum_definition("Enum").unwrap().docs(),
+            Some("An enum.")
+        );
+        assert_eq!(
+            ci.get_error_definition("Error").unwrap().docs(),
+            Some("An error.")
+        );
+
+        let obj = ci.get_object_definition("Object").unwrap();
+        assert_eq!(obj.docs(), Some("An object."));
+        assert_eq!(obj.methods()[0].docs(), Some("A method."));
+        assert_eq!(obj.methods()[1].docs(), None);
+        assert_eq!(obj.static_methods()[0].docs(), Some("A static method."));
+        assert_eq!(obj.properties()[0].docs(), Some("A property."));
+    }
+
+    #[test]
+    fn test_doc_comments_dont_affect_the_checksum() {
+        const UDL: &str = r#"
+            namespace test {
+                void function();
+            };
+        "#;
+        const UDL2: &str = r#"
+            namespace test {
+                /// A function.
+                void function();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let ci2 = ComponentInterface::from_webidl(UDL2).unwrap();
+        assert_eq!(ci.checksum(), ci2.checksum());
+    }
+}
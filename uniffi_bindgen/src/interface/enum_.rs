@@ -38,6 +38,7 @@
 
 use anyhow::Result;
 
+use super::doc_comment::DocComment;
 use super::{APIConverter, ComponentInterface};
 
 /// Represents a simple C-style enum, with named variants.
@@ -48,6 +49,7 @@ use super::{APIConverter, ComponentInterface};
 pub struct Enum {
     pub(super) name: String,
     pub(super) variants: Vec<String>,
+    pub(super) docs: DocComment,
 }
 
 impl Enum {
@@ -57,10 +59,13 @@ impl Enum {
     pub fn variants(&self) -> Vec<&str> {
         self.variants.iter().map(|v| v.as_str()).collect()
     }
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
 }
 
 impl APIConverter<Enum> for weedle::EnumDefinition<'_> {
-    fn convert(&self, _ci: &mut ComponentInterface) -> Result<Enum> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Enum> {
         Ok(Enum {
             name: self.identifier.0.to_string(),
             variants: self
@@ -70,6 +75,7 @@ impl APIConverter<Enum> for weedle::EnumDefinition<'_> {
                 .iter()
                 .map(|v| v.0.to_string())
                 .collect(),
+            docs: ci.get_doc_comment(self.identifier.0),
         })
     }
 }
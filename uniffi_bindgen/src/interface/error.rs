@@ -40,6 +40,7 @@
 
 use anyhow::Result;
 
+use super::doc_comment::DocComment;
 use super::{APIConverter, ComponentInterface};
 
 /// Represents an Error that might be thrown by functions/methods in the component interface.
@@ -51,6 +52,7 @@ use super::{APIConverter, ComponentInterface};
 pub struct Error {
     pub(super) name: String,
     pub(super) values: Vec<String>,
+    pub(super) docs: DocComment,
 }
 
 impl Error {
@@ -61,10 +63,14 @@ impl Error {
     pub fn values(&self) -> Vec<&str> {
         self.values.iter().map(|v| v.as_str()).collect()
     }
+
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
 }
 
 impl APIConverter<Error> for weedle::EnumDefinition<'_> {
-    fn convert(&self, _ci: &mut ComponentInterface) -> Result<Error> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Error> {
         Ok(Error {
             name: self.identifier.0.to_string(),
             values: self
@@ -74,6 +80,7 @@ impl APIConverter<Error> for weedle::EnumDefinition<'_> {
                 .iter()
                 .map(|v| v.0.to_string())
                 .collect(),
+            docs: ci.get_doc_comment(self.identifier.0),
         })
     }
 }
@@ -46,6 +46,10 @@ pub enum FFIType {
     /// The string is owned by rust and allocated on the rust heap, and must be freed by
     /// passing it to the appropriate `string_free` FFI function.
     RustError,
+    /// A `#[repr(C)]` struct passed by value, with the same fields as the named record.
+    /// This is only used for records whose fields are all numbers or booleans, which can
+    /// be represented directly in C without any allocation or serialization.
+    Struct(String),
     /// A pointer to a single function in to the foreign language.
     /// This function contains all the machinery to make callbacks work on the foreign language side.
     ForeignCallback,
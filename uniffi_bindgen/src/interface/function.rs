@@ -37,7 +37,8 @@ use std::hash::{Hash, Hasher};
 use anyhow::{bail, Result};
 
 use super::attributes::{ArgumentAttributes, FunctionAttributes};
-use super::ffi::{FFIArgument, FFIFunction};
+use super::doc_comment::DocComment;
+use super::ffi::{FFIArgument, FFIFunction, FFIType};
 use super::literal::{convert_default_value, Literal};
 use super::types::Type;
 use super::{APIConverter, ComponentInterface};
@@ -55,6 +56,7 @@ pub struct Function {
     pub(super) return_type: Option<Type>,
     pub(super) ffi_func: FFIFunction,
     pub(super) attributes: FunctionAttributes,
+    pub(super) docs: DocComment,
 }
 
 impl Function {
@@ -75,12 +77,24 @@ impl Function {
         self.attributes.get_throws_err()
     }
 
-    pub fn derive_ffi_func(&mut self, ci_prefix: &str) -> Result<()> {
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    pub(super) fn derive_ffi_func(
+        &mut self,
+        ci_prefix: &str,
+        ci: &ComponentInterface,
+    ) -> Result<()> {
         self.ffi_func.name.push_str(ci_prefix);
         self.ffi_func.name.push_str("_");
         self.ffi_func.name.push_str(&self.name);
-        self.ffi_func.arguments = self.arguments.iter().map(|arg| arg.into()).collect();
-        self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.into());
+        self.ffi_func.arguments = self
+            .arguments
+            .iter()
+            .map(|arg| arg.ffi_argument(ci))
+            .collect();
+        self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.ffi_type(ci));
         Ok(())
     }
 }
@@ -115,11 +129,12 @@ impl APIConverter<Function> for weedle::namespace::OperationNamespaceMember<'_>
         if let Some(Type::Object(_)) = return_type {
             bail!("Objects cannot currently be returned from functions");
         }
+        let id = match self.identifier {
+            None => bail!("anonymous functions are not supported {:?}", self),
+            Some(id) => id,
+        };
         Ok(Function {
-            name: match self.identifier {
-                None => bail!("anonymous functions are not supported {:?}", self),
-                Some(id) => id.0.to_string(),
-            },
+            name: id.0.to_string(),
             return_type,
             arguments: self.args.body.list.convert(ci)?,
             ffi_func: Default::default(),
@@ -127,6 +142,7 @@ impl APIConverter<Function> for weedle::namespace::OperationNamespaceMember<'_>
                 Some(attr) => FunctionAttributes::try_from(attr)?,
                 None => Default::default(),
             },
+            docs: ci.get_doc_comment(id.0),
         })
     }
 }
@@ -153,16 +169,33 @@ impl Argument {
     pub fn by_ref(&self) -> bool {
         self.by_ref
     }
+    /// Is this argument passed as a `ForeignBytes` that borrows memory owned by the
+    /// foreign-language code, rather than being copied into a new `RustBuffer`?
+    ///
+    /// That's the case for `[ByRef]` strings and byte arrays, which the Rust code
+    /// receives as a `&str` or `&[u8]` pointing directly at the foreign bytes.
+    pub fn is_borrowed_bytes(&self) -> bool {
+        self.by_ref
+            && match &self.type_ {
+                Type::String | Type::Bytes => true,
+                Type::Sequence(t) => matches!(**t, Type::UInt8),
+                _ => false,
+            }
+    }
     pub fn default_value(&self) -> Option<Literal> {
         self.default.clone()
     }
 }
 
-impl Into<FFIArgument> for &Argument {
-    fn into(self: Self) -> FFIArgument {
+impl Argument {
+    pub(super) fn ffi_argument(&self, ci: &ComponentInterface) -> FFIArgument {
         FFIArgument {
             name: self.name.clone(),
-            type_: (&self.type_).into(),
+            type_: if self.is_borrowed_bytes() {
+                FFIType::ForeignBytes
+            } else {
+                self.type_.ffi_type(ci)
+            },
         }
     }
 }
@@ -244,4 +277,36 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_borrowed_bytes_arguments() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                void borrowed([ByRef] string s, [ByRef] sequence<u8> b, [ByRef] sequence<u32> v, [ByRef] bytes bb);
+                void owned(string s, sequence<u8> b);
+            };
+        "##,
+        )?;
+
+        let func1 = ci.get_function_definition("borrowed").unwrap();
+        let args = func1.arguments();
+        assert!(args[0].is_borrowed_bytes());
+        assert!(args[1].is_borrowed_bytes());
+        assert!(!args[2].is_borrowed_bytes());
+        assert!(args[3].is_borrowed_bytes());
+        let ffi_args = func1.ffi_func().arguments();
+        assert!(matches!(ffi_args[0].type_(), FFIType::ForeignBytes));
+        assert!(matches!(ffi_args[1].type_(), FFIType::ForeignBytes));
+        assert!(matches!(ffi_args[2].type_(), FFIType::RustBuffer));
+        assert!(matches!(ffi_args[3].type_(), FFIType::ForeignBytes));
+
+        let func2 = ci.get_function_definition("owned").unwrap();
+        assert!(!func2.arguments()[0].is_borrowed_bytes());
+        assert!(!func2.arguments()[1].is_borrowed_bytes());
+        let ffi_args = func2.ffi_func().arguments();
+        assert!(matches!(ffi_args[0].type_(), FFIType::RustBuffer));
+        assert!(matches!(ffi_args[1].type_(), FFIType::RustBuffer));
+        Ok(())
+    }
 }
@@ -60,6 +60,10 @@ use types::TypeUniverse;
 mod attributes;
 mod callbacks;
 pub use callbacks::CallbackInterface;
+mod constant;
+pub use constant::Constant;
+mod doc_comment;
+use doc_comment::{DocComment, DocComments};
 mod enum_;
 pub use enum_::Enum;
 mod error;
@@ -71,7 +75,7 @@ pub use literal::{Literal, Radix};
 mod namespace;
 pub use namespace::Namespace;
 mod object;
-pub use object::{Constructor, Method, Object};
+pub use object::{Constructor, Method, Object, Property, StaticMethod};
 mod record;
 pub use record::{Field, Record};
 
@@ -95,9 +99,12 @@ pub struct ComponentInterface {
     enums: Vec<Enum>,
     records: Vec<Record>,
     functions: Vec<Function>,
+    constants: Vec<Constant>,
     objects: Vec<Object>,
     callback_interfaces: Vec<CallbackInterface>,
     errors: Vec<Error>,
+    /// The doc comments from the UDL, which are attached to the items above as they're built.
+    doc_comments: DocComments,
 }
 
 impl<'ci> ComponentInterface {
@@ -105,6 +112,7 @@ impl<'ci> ComponentInterface {
     pub fn from_webidl(idl: &str) -> Result<Self> {
         let mut ci = Self::default();
         ci.uniffi_version = env!("CARGO_PKG_VERSION").to_string();
+        ci.doc_comments = DocComments::from_source(idl);
         // There's some lifetime thing with the errors returned from weedle::parse
         // that my own lifetime is too short to worry about figuring out; unwrap and move on.
         let defns = weedle::parse(idl.trim()).unwrap();
@@ -118,6 +126,10 @@ impl<'ci> ComponentInterface {
         }
         // Now that the high-level API is settled, we can derive the low-level FFI.
         ci.derive_ffi_funcs()?;
+        // The bindings lift the string returned by the builtin leak report function, and read
+        // the strings and integers in the report returned by the builtin panic report function.
+        ci.types.add_known_type(Type::String)?;
+        ci.types.add_known_type(Type::Int32)?;
         Ok(ci)
     }
 
@@ -162,6 +174,16 @@ impl<'ci> ComponentInterface {
         self.functions.iter().find(|f| f.name == name)
     }
 
+    /// List the definitions for every Constant in the interface.
+    pub fn iter_constant_definitions(&self) -> Vec<Constant> {
+        self.constants.to_vec()
+    }
+
+    /// Get a Constant definition by name, or None if no such Constant is defined.
+    pub fn get_constant_definition(&self, name: &str) -> Option<&Constant> {
+        self.constants.iter().find(|c| c.name == name)
+    }
+
     /// List the definitions for every Object type in the interface.
     pub fn iter_object_definitions(&self) -> Vec<Object> {
         self.objects.to_vec()
@@ -252,9 +274,14 @@ impl<'ci> ComponentInterface {
     /// Builtin FFI function for allocating a new `RustBuffer`.
     /// This is needed so that the foreign language bindings can create buffers in which to pass
     /// complex data types across the FFI.
+    ///
+    /// The builtin FFI functions are the same for every component, so they're defined once in
+    /// the `uniffi` crate rather than in each component's scaffolding, and have fixed names
+    /// (see `builtin_ffi_name`). This lets several components be bundled into a single library
+    /// that contains just one copy of each of them.
     pub fn ffi_rustbuffer_alloc(&self) -> FFIFunction {
         FFIFunction {
-            name: format!("ffi_{}_rustbuffer_alloc", self.ffi_namespace()),
+            name: builtin_ffi_name("uniffi_rustbuffer_alloc"),
             arguments: vec![FFIArgument {
                 name: "size".to_string(),
                 type_: FFIType::Int32,
@@ -268,7 +295,7 @@ impl<'ci> ComponentInterface {
     /// complex data types across the FFI.
     pub fn ffi_rustbuffer_from_bytes(&self) -> FFIFunction {
         FFIFunction {
-            name: format!("ffi_{}_rustbuffer_from_bytes", self.ffi_namespace()),
+            name: builtin_ffi_name("uniffi_rustbuffer_from_bytes"),
             arguments: vec![FFIArgument {
                 name: "bytes".to_string(),
                 type_: FFIType::ForeignBytes,
@@ -282,7 +309,7 @@ impl<'ci> ComponentInterface {
     /// complex data types returned across the FFI.
     pub fn ffi_rustbuffer_free(&self) -> FFIFunction {
         FFIFunction {
-            name: format!("ffi_{}_rustbuffer_free", self.ffi_namespace()),
+            name: builtin_ffi_name("uniffi_rustbuffer_free"),
             arguments: vec![FFIArgument {
                 name: "buf".to_string(),
                 type_: FFIType::RustBuffer,
@@ -296,7 +323,7 @@ impl<'ci> ComponentInterface {
     /// complex data types across the FFI.
     pub fn ffi_rustbuffer_reserve(&self) -> FFIFunction {
         FFIFunction {
-            name: format!("ffi_{}_rustbuffer_reserve", self.ffi_namespace()),
+            name: builtin_ffi_name("uniffi_rustbuffer_reserve"),
             arguments: vec![
                 FFIArgument {
                     name: "buf".to_string(),
@@ -318,7 +345,7 @@ impl<'ci> ComponentInterface {
     /// the exisiting bytebuffer-freeing function rather than a special one.
     pub fn ffi_string_free(&self) -> FFIFunction {
         FFIFunction {
-            name: format!("ffi_{}_string_free", self.ffi_namespace()),
+            name: builtin_ffi_name("uniffi_string_free"),
             arguments: vec![FFIArgument {
                 name: "cstr".to_string(),
                 type_: FFIType::RustCString,
@@ -327,6 +354,76 @@ impl<'ci> ComponentInterface {
         }
     }
 
+    /// Builtin FFI function for reporting the objects and buffers that are still live.
+    /// This is used by the `assertNoLeaks()` helper in the foreign language bindings, and
+    /// returns a string in a `RustBuffer`, which is empty if nothing has leaked. It fails
+    /// unless the `uniffi` crate was built with the `leak-tracking` feature.
+    pub fn ffi_leak_report(&self) -> FFIFunction {
+        FFIFunction {
+            name: builtin_ffi_name("uniffi_leak_report"),
+            arguments: vec![],
+            return_type: Some(FFIType::RustBuffer),
+        }
+    }
+
+    /// Builtin FFI function for installing the panic hook that records structured reports
+    /// of Rust panics. The bindings call this when they load the library.
+    pub fn ffi_install_panic_hook(&self) -> FFIFunction {
+        FFIFunction {
+            name: builtin_ffi_name("uniffi_install_panic_hook"),
+            arguments: vec![],
+            return_type: None,
+        }
+    }
+
+    /// Builtin FFI function for taking the structured report of the most recent Rust panic
+    /// on the current thread, so that the bindings can throw a `RustPanic` exception with its
+    /// fields. The report is serialized into a `RustBuffer`, which is empty if there isn't one.
+    pub fn ffi_take_panic_report(&self) -> FFIFunction {
+        FFIFunction {
+            name: builtin_ffi_name("uniffi_take_panic_report"),
+            arguments: vec![],
+            return_type: Some(FFIType::RustBuffer),
+        }
+    }
+
+    /// Builtin FFI function for allocating scratch memory when compiled to WebAssembly.
+    /// This is needed so that the JS bindings can pass structs and error out-params into
+    /// Rust, since those must live in the module's linear memory. Pointers in linear memory
+    /// are 32 bits wide, hence the `Int32` return type.
+    ///
+    /// This function is only exported on `wasm32`, so it is deliberately not included in
+    /// `iter_ffi_function_definitions`.
+    pub fn ffi_wasm_scratch_alloc(&self) -> FFIFunction {
+        FFIFunction {
+            name: builtin_ffi_name("uniffi_wasm_scratch_alloc"),
+            arguments: vec![FFIArgument {
+                name: "size".to_string(),
+                type_: FFIType::Int32,
+            }],
+            return_type: Some(FFIType::Int32),
+        }
+    }
+
+    /// Builtin FFI function for freeing scratch memory when compiled to WebAssembly.
+    /// Like `ffi_wasm_scratch_alloc`, this is only exported on `wasm32`.
+    pub fn ffi_wasm_scratch_free(&self) -> FFIFunction {
+        FFIFunction {
+            name: builtin_ffi_name("uniffi_wasm_scratch_free"),
+            arguments: vec![
+                FFIArgument {
+                    name: "ptr".to_string(),
+                    type_: FFIType::Int32,
+                },
+                FFIArgument {
+                    name: "size".to_string(),
+                    type_: FFIType::Int32,
+                },
+            ],
+            return_type: None,
+        }
+    }
+
     /// List the definitions of all FFI functions in the interface.
     ///
     /// The set of FFI functions is derived automatically from the set of higher-level types
@@ -337,8 +434,16 @@ impl<'ci> ComponentInterface {
             .map(|obj| {
                 vec![obj.ffi_object_free().clone()]
                     .into_iter()
+                    .chain(obj.ffi_trait_funcs.iter().map(|(_, f)| f.clone()))
                     .chain(obj.constructors.iter().map(|f| f.ffi_func.clone()))
                     .chain(obj.methods.iter().map(|f| f.ffi_func.clone()))
+                    .chain(obj.static_methods.iter().map(|f| f.ffi_func.clone()))
+                    .chain(
+                        obj.properties
+                            .iter()
+                            .flat_map(|p| p.methods())
+                            .map(|f| f.ffi_func.clone()),
+                    )
             })
             .flatten()
             .chain(
@@ -354,6 +459,9 @@ impl<'ci> ComponentInterface {
                     self.ffi_rustbuffer_free(),
                     self.ffi_rustbuffer_reserve(),
                     self.ffi_string_free(),
+                    self.ffi_leak_report(),
+                    self.ffi_install_panic_hook(),
+                    self.ffi_take_panic_report(),
                 ]
                 .iter()
                 .cloned(),
@@ -365,6 +473,14 @@ impl<'ci> ComponentInterface {
     // Private methods for building a ComponentInterface.
     //
 
+    /// Find the doc comment for the item declared with the given identifier.
+    ///
+    /// The identifier must be borrowed from the weedle syntax tree, so we can tell where in
+    /// the UDL it was declared.
+    fn get_doc_comment(&self, ident: &str) -> DocComment {
+        self.doc_comments.get(ident)
+    }
+
     /// Resolve a weedle type expression into a `Type`.
     ///
     /// This method uses the current state of our `TypeUniverse` to turn a weedle type expression
@@ -430,6 +546,15 @@ impl<'ci> ComponentInterface {
         Ok(())
     }
 
+    /// Called by `APIBuilder` impls to add a newly-parsed constant definition to the `ComponentInterface`.
+    fn add_constant_definition(&mut self, defn: Constant) -> Result<()> {
+        if self.constants.iter().any(|c| c.name == defn.name) {
+            bail!("duplicate constant definition: {}", defn.name);
+        }
+        self.constants.push(defn);
+        Ok(())
+    }
+
     /// Called by `APIBuilder` impls to add a newly-parsed object definition to the `ComponentInterface`.
     fn add_object_definition(&mut self, defn: Object) -> Result<()> {
         // Note that there will be no duplicates thanks to the previous type-finding pass.
@@ -457,12 +582,19 @@ impl<'ci> ComponentInterface {
     /// the resulting set will be missing some entries.
     fn derive_ffi_funcs(&mut self) -> Result<()> {
         let ci_prefix = self.ffi_namespace();
-        for func in self.functions.iter_mut() {
-            func.derive_ffi_func(&ci_prefix)?;
+        // How each type is lowered may depend on other parts of the interface (for example,
+        // on the fields of a record) so we temporarily take ownership of the functions and
+        // objects, leaving the rest of the interface available for reference while we work.
+        let mut functions = std::mem::take(&mut self.functions);
+        for func in functions.iter_mut() {
+            func.derive_ffi_func(&ci_prefix, self)?;
         }
-        for obj in self.objects.iter_mut() {
-            obj.derive_ffi_funcs(&ci_prefix)?;
+        self.functions = functions;
+        let mut objects = std::mem::take(&mut self.objects);
+        for obj in objects.iter_mut() {
+            obj.derive_ffi_funcs(&ci_prefix, self)?;
         }
+        self.objects = objects;
         for callback in self.callback_interfaces.iter_mut() {
             callback.derive_ffi_funcs(&ci_prefix)?;
         }
@@ -470,6 +602,21 @@ impl<'ci> ComponentInterface {
     }
 }
 
+/// The name of one of the builtin FFI functions that are defined in the `uniffi` crate.
+///
+/// These end with the major and minor version of `uniffi` (which is released in lockstep with
+/// this crate), so that components built with incompatible versions of uniffi can be linked into
+/// the same library without their builtin functions clashing. They must match the names in
+/// `uniffi`'s `ffi::builtins` module.
+fn builtin_ffi_name(name: &str) -> String {
+    format!(
+        "{}_{}_{}",
+        name,
+        env!("CARGO_PKG_VERSION_MAJOR"),
+        env!("CARGO_PKG_VERSION_MINOR")
+    )
+}
+
 /// Convenience implementation for parsing a `ComponentInterface` from a string.
 impl FromStr for ComponentInterface {
     type Err = anyhow::Error;
@@ -489,6 +636,7 @@ impl Hash for ComponentInterface {
         self.enums.hash(state);
         self.records.hash(state);
         self.functions.hash(state);
+        self.constants.hash(state);
         self.objects.hash(state);
         self.callback_interfaces.hash(state);
         self.errors.hash(state);
@@ -549,6 +697,11 @@ impl APIBuilder for weedle::Definition<'_> {
                 let obj = d.convert(ci)?;
                 ci.add_callback_interface_definition(obj)
             }
+            weedle::Definition::Typedef(_) => {
+                // Typedefs (including custom types) only name a type, so they
+                // were fully dealt with in the type-finding phase.
+                Ok(())
+            }
             _ => bail!("don't know how to deal with {:?}", self),
         }
     }
@@ -631,4 +784,77 @@ mod test {
             assert_ne!(ci1.checksum(), ci2.checksum());
         }
     }
+
+    #[test]
+    fn test_custom_types_are_passed_as_their_builtin_type() {
+        const UDL: &str = r#"
+            namespace test{
+                Guid get_guid(Handle handle);
+            };
+            [Custom]
+            typedef string Guid;
+            [Custom]
+            typedef u32 Handle;
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let func = ci.get_function_definition("get_guid").unwrap();
+        assert_eq!(func.return_type().unwrap().canonical_name(), "CustomGuid");
+        assert!(matches!(
+            func.ffi_func().return_type(),
+            Some(FFIType::RustBuffer)
+        ));
+        assert!(matches!(
+            func.ffi_func().arguments()[0].type_(),
+            FFIType::UInt32
+        ));
+    }
+
+    #[test]
+    fn test_external_types_are_passed_as_a_buffer() {
+        const UDL: &str = r#"
+            [External="profiles"]
+            typedef extern ProfileInfo;
+            namespace test{
+                ProfileInfo get_profile(ProfileInfo? profile);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let func = ci.get_function_definition("get_profile").unwrap();
+        assert_eq!(
+            func.return_type().unwrap().canonical_name(),
+            "ExternalProfileInfo"
+        );
+        assert!(matches!(
+            func.ffi_func().return_type(),
+            Some(FFIType::RustBuffer)
+        ));
+        assert!(matches!(
+            func.ffi_func().arguments()[0].type_(),
+            FFIType::RustBuffer
+        ));
+    }
+
+    #[test]
+    fn test_builtin_ffi_functions_are_shared_between_components() {
+        let ci1 = ComponentInterface::from_webidl("namespace first {};").unwrap();
+        let ci2 = ComponentInterface::from_webidl("namespace second {};").unwrap();
+        assert_eq!(
+            ci1.ffi_rustbuffer_alloc().name(),
+            ci2.ffi_rustbuffer_alloc().name()
+        );
+        assert_eq!(ci1.ffi_string_free().name(), ci2.ffi_string_free().name());
+        assert_eq!(
+            ci1.ffi_rustbuffer_alloc().name(),
+            format!(
+                "uniffi_rustbuffer_alloc_{}_{}",
+                env!("CARGO_PKG_VERSION_MAJOR"),
+                env!("CARGO_PKG_VERSION_MINOR")
+            )
+        );
+        assert_eq!(ci1.ffi_leak_report().name(), ci2.ffi_leak_report().name());
+        assert_eq!(
+            ci1.ffi_take_panic_report().name(),
+            ci2.ffi_take_panic_report().name()
+        );
+    }
 }
@@ -14,7 +14,8 @@
 //! For our purposes with UDL, we expect just a single `namespace` declaration, which
 //! defines properties of the component as a whole (currently just the name). It also
 //! contains the functions that will be exposed as individual plain functions exported by
-//! the component, if any. So something like this:
+//! the component, if any, and any constants declared using `readonly attribute`
+//! with a `[Value]` attribute. So something like this:
 //!
 //! ```
 //! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
@@ -81,8 +82,18 @@ impl APIBuilder for weedle::NamespaceDefinition<'_> {
         ci.add_namespace_definition(Namespace {
             name: self.identifier.0.to_string(),
         })?;
-        for func in self.members.body.convert(ci)? {
-            ci.add_function_definition(func)?;
+        for member in &self.members.body {
+            match member {
+                weedle::namespace::NamespaceMember::Operation(f) => {
+                    let func = f.convert(ci)?;
+                    ci.add_function_definition(func)?;
+                }
+                // WebIDL doesn't allow `const` in a namespace, so we use readonly attributes instead.
+                weedle::namespace::NamespaceMember::Attribute(a) => {
+                    let constant = a.convert(ci)?;
+                    ci.add_constant_definition(constant)?;
+                }
+            }
         }
         Ok(())
     }
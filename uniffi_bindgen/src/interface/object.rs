@@ -20,13 +20,15 @@
 //! interface Example {
 //!   constructor(string? name);
 //!   string my_name();
+//!   static string default_name();
+//!   attribute u32 age;
 //! };
 //! # "##)?;
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
-//! Will result in an [`Object`] member with one [`Constructor`] and one [`Method`] being added
-//! to the resulting [`ComponentInterface`]:
+//! Will result in an [`Object`] member with one [`Constructor`], one [`Method`], one
+//! [`StaticMethod`] and one [`Property`] being added to the resulting [`ComponentInterface`]:
 //!
 //! ```
 //! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
@@ -34,6 +36,8 @@
 //! # interface Example {
 //! #   constructor(string? name);
 //! #   string my_name();
+//! #   static string default_name();
+//! #   attribute u32 age;
 //! # };
 //! # "##)?;
 //! let obj = ci.get_object_definition("Example").unwrap();
@@ -42,6 +46,11 @@
 //! assert_eq!(obj.constructors()[0].arguments()[0].name(), "name");
 //! assert_eq!(obj.methods().len(),1 );
 //! assert_eq!(obj.methods()[0].name(), "my_name");
+//! assert_eq!(obj.static_methods().len(), 1);
+//! assert_eq!(obj.static_methods()[0].name(), "default_name");
+//! assert_eq!(obj.properties().len(), 1);
+//! assert_eq!(obj.properties()[0].getter().name(), "get_age");
+//! assert_eq!(obj.properties()[0].setter().unwrap().name(), "set_age");
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
@@ -51,7 +60,10 @@ use std::hash::{Hash, Hasher};
 
 use anyhow::{bail, Result};
 
-use super::attributes::{ConstructorAttributes, InterfaceAttributes, MethodAttributes};
+use super::attributes::{
+    ConstructorAttributes, FunctionAttributes, InterfaceAttributes, MethodAttributes, ObjectTrait,
+};
+use super::doc_comment::DocComment;
 use super::ffi::{FFIArgument, FFIFunction, FFIType};
 use super::function::Argument;
 use super::types::Type;
@@ -65,10 +77,16 @@ use super::{APIConverter, ComponentInterface};
 ///
 /// At the FFI layer, objects are represented by an opaque integer handle and a set of functions
 /// a common prefix. The object's constuctors are functions that return new objects by handle,
-/// and its methods are functions that take a handle as first argument. The foreign language
+/// and its methods are functions that take a handle as first argument. Its static methods are
+/// plain functions that share the object's prefix but don't take a handle, and its properties
+/// are pairs of getter and setter methods. The foreign language
 /// binding code is expected to stitch these functions back together into an appropriate class
 /// definition (or that language's equivalent thereof).
 ///
+/// Objects can also expose some of the standard Rust traits that they implement, as listed in
+/// the `[Traits=(...)]` attribute, each through an extra FFI function that the bindings can
+/// use to implement the foreign language's equivalent, like `toString()` or `__eq__`.
+///
 /// TODO:
 ///  - maybe "Class" would be a better name than "Object" here?
 #[derive(Debug, Clone)]
@@ -76,8 +94,16 @@ pub struct Object {
     pub(super) name: String,
     pub(super) constructors: Vec<Constructor>,
     pub(super) methods: Vec<Method>,
+    pub(super) static_methods: Vec<StaticMethod>,
+    pub(super) properties: Vec<Property>,
     pub(super) ffi_func_free: FFIFunction,
+    pub(super) traits: Vec<ObjectTrait>,
+    pub(super) ffi_trait_funcs: Vec<(ObjectTrait, FFIFunction)>,
     pub(super) threadsafe: bool,
+    pub(super) pointer_handle: bool,
+    pub(super) rwlock: bool,
+    pub(super) thread_confined: bool,
+    pub(super) docs: DocComment,
 }
 
 impl Object {
@@ -86,8 +112,16 @@ impl Object {
             name,
             constructors: Default::default(),
             methods: Default::default(),
+            static_methods: Default::default(),
+            properties: Default::default(),
             ffi_func_free: Default::default(),
+            traits: Default::default(),
+            ffi_trait_funcs: Default::default(),
             threadsafe: false,
+            pointer_handle: false,
+            rwlock: false,
+            thread_confined: false,
+            docs: Default::default(),
         }
     }
 
@@ -116,26 +150,151 @@ impl Object {
         self.methods.iter().collect()
     }
 
+    pub fn static_methods(&self) -> Vec<&StaticMethod> {
+        self.static_methods.iter().collect()
+    }
+
+    pub fn properties(&self) -> Vec<&Property> {
+        self.properties.iter().collect()
+    }
+
     pub fn ffi_object_free(&self) -> &FFIFunction {
         &self.ffi_func_free
     }
 
+    /// The FFI function for calling `Debug::fmt` on the object, if it exposes that trait.
+    /// It takes the object's handle, and returns a string.
+    pub fn ffi_object_debug(&self) -> Option<&FFIFunction> {
+        self.ffi_trait_func(ObjectTrait::Debug)
+    }
+
+    /// The FFI function for calling `Display::fmt` on the object, if it exposes that trait.
+    /// It takes the object's handle, and returns a string.
+    pub fn ffi_object_display(&self) -> Option<&FFIFunction> {
+        self.ffi_trait_func(ObjectTrait::Display)
+    }
+
+    /// The FFI function for turning the object into a string, for foreign languages that only have
+    /// one way of doing that. This uses the `Display` trait if the object exposes it, and otherwise
+    /// the `Debug` trait.
+    pub fn ffi_object_to_string(&self) -> Option<&FFIFunction> {
+        self.ffi_object_display()
+            .or_else(|| self.ffi_object_debug())
+    }
+
+    /// The FFI function for calling `PartialEq::eq` on the object, if it exposes the `Eq` trait.
+    /// It takes the handles of the two objects to compare, and returns a boolean.
+    pub fn ffi_object_eq(&self) -> Option<&FFIFunction> {
+        self.ffi_trait_func(ObjectTrait::Eq)
+    }
+
+    /// The FFI function for calling `Hash::hash` on the object, if it exposes that trait.
+    /// It takes the object's handle, and returns the hash as a `u64`.
+    pub fn ffi_object_hash(&self) -> Option<&FFIFunction> {
+        self.ffi_trait_func(ObjectTrait::Hash)
+    }
+
+    fn ffi_trait_func(&self, trait_: ObjectTrait) -> Option<&FFIFunction> {
+        self.ffi_trait_funcs
+            .iter()
+            .find_map(|(t, func)| if *t == trait_ { Some(func) } else { None })
+    }
+
     pub fn threadsafe(&self) -> bool {
         self.threadsafe
     }
 
-    pub fn derive_ffi_funcs(&mut self, ci_prefix: &str) -> Result<()> {
+    /// Whether the object's handle is a pointer to it, rather than an index into a handle map.
+    pub fn pointer_handle(&self) -> bool {
+        self.pointer_handle
+    }
+
+    /// Whether the object is wrapped in an `RwLock`, rather than a `Mutex`, so that methods
+    /// that take `&self` can be called from several threads at once.
+    pub fn rwlock(&self) -> bool {
+        self.rwlock
+    }
+
+    /// Whether the object can only be used on the thread that created it, so that it
+    /// needn't be `Send`.
+    pub fn thread_confined(&self) -> bool {
+        self.thread_confined
+    }
+
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// The object's doc comment, followed by a note about any restrictions on using it,
+    /// for the foreign language bindings to put on the generated class.
+    pub fn class_docs(&self) -> Option<String> {
+        let mut docs = self.docs.as_deref().map(String::from);
+        if self.thread_confined {
+            let docs = docs.get_or_insert_with(String::new);
+            if !docs.is_empty() {
+                docs.push_str("\n\n");
+            }
+            docs.push_str(
+                "Instances of this class can only be used on the thread that created them.\n\
+                 Calling their methods on any other thread raises an error.",
+            );
+        }
+        docs
+    }
+
+    pub(super) fn derive_ffi_funcs(
+        &mut self,
+        ci_prefix: &str,
+        ci: &ComponentInterface,
+    ) -> Result<()> {
         self.ffi_func_free.name = format!("ffi_{}_{}_object_free", ci_prefix, self.name);
         self.ffi_func_free.arguments = vec![FFIArgument {
             name: "handle".to_string(),
             type_: FFIType::UInt64,
         }];
         self.ffi_func_free.return_type = None;
+        self.ffi_trait_funcs = self
+            .traits
+            .iter()
+            .map(|trait_| {
+                let suffix = match trait_ {
+                    ObjectTrait::Debug => "debug",
+                    ObjectTrait::Display => "display",
+                    ObjectTrait::Eq => "eq",
+                    ObjectTrait::Hash => "hash",
+                };
+                let mut arguments = vec![FFIArgument {
+                    name: "handle".to_string(),
+                    type_: FFIType::UInt64,
+                }];
+                if *trait_ == ObjectTrait::Eq {
+                    arguments.push(FFIArgument {
+                        name: "other".to_string(),
+                        type_: FFIType::UInt64,
+                    });
+                }
+                let func = FFIFunction {
+                    name: format!("ffi_{}_{}_uniffi_trait_{}", ci_prefix, self.name, suffix),
+                    arguments,
+                    return_type: Some(trait_return_type(*trait_).ffi_type(ci)),
+                };
+                (*trait_, func)
+            })
+            .collect();
         for cons in self.constructors.iter_mut() {
-            cons.derive_ffi_func(ci_prefix, &self.name)?
+            cons.derive_ffi_func(ci_prefix, &self.name, ci)?
         }
         for meth in self.methods.iter_mut() {
-            meth.derive_ffi_func(ci_prefix, &self.name)?
+            meth.derive_ffi_func(ci_prefix, &self.name, ci)?
+        }
+        for meth in self.static_methods.iter_mut() {
+            meth.derive_ffi_func(ci_prefix, &self.name, ci)?
+        }
+        for prop in self.properties.iter_mut() {
+            prop.getter.derive_ffi_func(ci_prefix, &self.name, ci)?;
+            if let Some(setter) = prop.setter.as_mut() {
+                setter.derive_ffi_func(ci_prefix, &self.name, ci)?
+            }
         }
         Ok(())
     }
@@ -152,6 +311,18 @@ impl Hash for Object {
         self.name.hash(state);
         self.constructors.hash(state);
         self.methods.hash(state);
+        self.static_methods.hash(state);
+        self.properties.hash(state);
+        self.traits.hash(state);
+    }
+}
+
+/// The type of value that the FFI function for calling one of an object's traits returns.
+fn trait_return_type(trait_: ObjectTrait) -> Type {
+    match trait_ {
+        ObjectTrait::Debug | ObjectTrait::Display => Type::String,
+        ObjectTrait::Eq => Type::Boolean,
+        ObjectTrait::Hash => Type::UInt64,
     }
 }
 
@@ -166,7 +337,16 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
             None => Default::default(),
         };
         object.threadsafe = attributes.threadsafe();
-        // Convert each member into a constructor or method, guarding against duplicate names.
+        object.pointer_handle = attributes.pointer_handle();
+        object.rwlock = attributes.rwlock();
+        object.thread_confined = attributes.thread_confined();
+        object.traits = attributes.traits().to_vec();
+        // The bindings will need to lift the values returned by the traits' FFI functions.
+        for trait_ in &object.traits {
+            ci.types.add_known_type(trait_return_type(*trait_))?;
+        }
+        object.docs = ci.get_doc_comment(self.identifier.0);
+        // Convert each member into a constructor, method or property, guarding against duplicate names.
         let mut member_names = HashSet::new();
         for member in &self.members.body {
             match member {
@@ -177,17 +357,54 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
                     }
                     object.constructors.push(cons);
                 }
+                weedle::interface::InterfaceMember::Operation(t)
+                    if matches!(
+                        t.modifier,
+                        Some(weedle::interface::StringifierOrStatic::Static(_))
+                    ) =>
+                {
+                    let meth: StaticMethod = t.convert(ci)?;
+                    if !member_names.insert(meth.name.clone()) {
+                        bail!("Duplicate interface member name: \"{}\"", meth.name())
+                    }
+                    object.static_methods.push(meth);
+                }
                 weedle::interface::InterfaceMember::Operation(t) => {
-                    let mut method = t.convert(ci)?;
+                    let mut method: Method = t.convert(ci)?;
                     if !member_names.insert(method.name.clone()) {
                         bail!("Duplicate interface member name: \"{}\"", method.name())
                     }
                     method.object_name.push_str(object.name.as_str());
                     object.methods.push(method);
                 }
+                weedle::interface::InterfaceMember::Attribute(t) => {
+                    let mut prop: Property = t.convert(ci)?;
+                    if !member_names.insert(prop.name.clone()) {
+                        bail!("Duplicate interface member name: \"{}\"", prop.name())
+                    }
+                    // The accessor methods share a namespace with the other methods at the FFI layer.
+                    for meth in prop.methods_mut() {
+                        if !member_names.insert(meth.name.clone()) {
+                            bail!("Duplicate interface member name: \"{}\"", meth.name())
+                        }
+                        meth.object_name.push_str(object.name.as_str());
+                    }
+                    object.properties.push(prop);
+                }
                 _ => bail!("no support for interface member type {:?} yet", member),
             }
         }
+        // Only the `ArcHandleMap` and pointer handles store the object in an `Arc` that we can pass around.
+        if !object.threadsafe
+            && (object.constructors.iter().any(|cons| cons.returns_arc())
+                || object.methods.iter().any(|meth| meth.takes_self_by_arc()))
+        {
+            bail!("Self=ByArc is only supported for Threadsafe interface classes");
+        }
+        // Other objects either always take `&self`, or always take `&mut self`.
+        if !object.rwlock && object.methods.iter().any(|meth| meth.takes_self_by_ref()) {
+            bail!("Self=ByRef is only supported for RwLock interface classes");
+        }
         // Everyone gets a primary constructor, even if not declared explicitly.
         if object.primary_constructor().is_none() {
             object.constructors.push(Default::default());
@@ -196,6 +413,13 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
     }
 }
 
+// The name of the FFI function for a constructor, method or static method of an object.
+//
+// Property getters and setters are methods, so they're named this way too.
+fn member_ffi_func_name(ci_prefix: &str, obj_prefix: &str, name: &str) -> String {
+    format!("{}_{}_{}", ci_prefix, obj_prefix, name)
+}
+
 // Represents a constructor for an object type.
 //
 // In the FFI, this will be a function that returns a handle for an instance
@@ -225,13 +449,23 @@ impl Constructor {
         self.attributes.get_throws_err()
     }
 
-    fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) -> Result<()> {
-        self.ffi_func.name.push_str(ci_prefix);
-        self.ffi_func.name.push_str("_");
-        self.ffi_func.name.push_str(obj_prefix);
-        self.ffi_func.name.push_str("_");
-        self.ffi_func.name.push_str(&self.name);
-        self.ffi_func.arguments = self.arguments.iter().map(Into::into).collect();
+    /// Whether the constructor returns an `Arc<Self>`, rather than a plain `Self`.
+    pub fn returns_arc(&self) -> bool {
+        self.attributes.get_self_by_arc()
+    }
+
+    fn derive_ffi_func(
+        &mut self,
+        ci_prefix: &str,
+        obj_prefix: &str,
+        ci: &ComponentInterface,
+    ) -> Result<()> {
+        self.ffi_func.name = member_ffi_func_name(ci_prefix, obj_prefix, &self.name);
+        self.ffi_func.arguments = self
+            .arguments
+            .iter()
+            .map(|arg| arg.ffi_argument(ci))
+            .collect();
         self.ffi_func.return_type = Some(FFIType::UInt64);
         Ok(())
     }
@@ -293,6 +527,7 @@ pub struct Method {
     pub(super) arguments: Vec<Argument>,
     pub(super) ffi_func: FFIFunction,
     pub(super) attributes: MethodAttributes,
+    pub(super) docs: DocComment,
 }
 
 impl Method {
@@ -328,18 +563,36 @@ impl Method {
         self.attributes.get_throws_err()
     }
 
-    pub fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) -> Result<()> {
-        self.ffi_func.name.push_str(ci_prefix);
-        self.ffi_func.name.push_str("_");
-        self.ffi_func.name.push_str(obj_prefix);
-        self.ffi_func.name.push_str("_");
-        self.ffi_func.name.push_str(&self.name);
+    /// Whether the method takes `self: Arc<Self>`, rather than `&self`.
+    pub fn takes_self_by_arc(&self) -> bool {
+        self.attributes.get_self_by_arc()
+    }
+
+    /// Whether the method has been declared to take `&self`, rather than `&mut self`.
+    ///
+    /// This only makes a difference for objects with an `RwLock`, where such methods
+    /// only need to take the read lock.
+    pub fn takes_self_by_ref(&self) -> bool {
+        self.attributes.get_self_by_ref()
+    }
+
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    pub(super) fn derive_ffi_func(
+        &mut self,
+        ci_prefix: &str,
+        obj_prefix: &str,
+        ci: &ComponentInterface,
+    ) -> Result<()> {
+        self.ffi_func.name = member_ffi_func_name(ci_prefix, obj_prefix, &self.name);
         self.ffi_func.arguments = vec![self.first_argument()]
             .iter()
             .chain(self.arguments.iter())
-            .map(Into::into)
+            .map(|arg| arg.ffi_argument(ci))
             .collect();
-        self.ffi_func.return_type = self.return_type.as_ref().map(Into::into);
+        self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.ffi_type(ci));
         Ok(())
     }
 }
@@ -372,17 +625,15 @@ impl APIConverter<Method> for weedle::interface::OperationInterfaceMember<'_> {
         if let Some(Type::Object(_)) = return_type {
             bail!("Objects cannot currently be returned from functions");
         }
+        let id = match self.identifier {
+            None => bail!("anonymous methods are not supported {:?}", self),
+            Some(id) => id,
+        };
+        if id.0 == "new" {
+            bail!("the method name \"new\" is reserved for the default constructor");
+        }
         Ok(Method {
-            name: match self.identifier {
-                None => bail!("anonymous methods are not supported {:?}", self),
-                Some(id) => {
-                    let name = id.0.to_string();
-                    if name == "new" {
-                        bail!("the method name \"new\" is reserved for the default constructor");
-                    }
-                    name
-                }
-            },
+            name: id.0.to_string(),
             // We don't know the name of the containing `Object` at this point, fill it in later.
             object_name: Default::default(),
             arguments: self.args.body.list.convert(ci)?,
@@ -392,6 +643,214 @@ impl APIConverter<Method> for weedle::interface::OperationInterfaceMember<'_> {
                 Some(attr) => MethodAttributes::try_from(attr)?,
                 None => Default::default(),
             },
+            docs: ci.get_doc_comment(id.0),
+        })
+    }
+}
+
+// Represents a static method for an object type.
+//
+// In the FFI, this will be a function with the same prefix as the object's methods,
+// but which doesn't take a handle since there's no instance to call it on.
+#[derive(Debug, Clone)]
+pub struct StaticMethod {
+    pub(super) name: String,
+    pub(super) return_type: Option<Type>,
+    pub(super) arguments: Vec<Argument>,
+    pub(super) ffi_func: FFIFunction,
+    pub(super) attributes: FunctionAttributes,
+    pub(super) docs: DocComment,
+}
+
+impl StaticMethod {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn arguments(&self) -> Vec<&Argument> {
+        self.arguments.iter().collect()
+    }
+
+    pub fn return_type(&self) -> Option<&Type> {
+        self.return_type.as_ref()
+    }
+
+    pub fn ffi_func(&self) -> &FFIFunction {
+        &self.ffi_func
+    }
+
+    pub fn throws(&self) -> Option<&str> {
+        self.attributes.get_throws_err()
+    }
+
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    fn derive_ffi_func(
+        &mut self,
+        ci_prefix: &str,
+        obj_prefix: &str,
+        ci: &ComponentInterface,
+    ) -> Result<()> {
+        self.ffi_func.name = member_ffi_func_name(ci_prefix, obj_prefix, &self.name);
+        self.ffi_func.arguments = self
+            .arguments
+            .iter()
+            .map(|arg| arg.ffi_argument(ci))
+            .collect();
+        self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.ffi_type(ci));
+        Ok(())
+    }
+}
+
+impl Hash for StaticMethod {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the FFIFunc in the hash calculation, for the same reasons as `Method`.
+        self.name.hash(state);
+        self.arguments.hash(state);
+        self.return_type.hash(state);
+        self.attributes.hash(state);
+    }
+}
+
+impl APIConverter<StaticMethod> for weedle::interface::OperationInterfaceMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<StaticMethod> {
+        if self.special.is_some() {
+            bail!("special operations not supported");
+        }
+        let return_type = ci.resolve_return_type_expression(&self.return_type)?;
+        if let Some(Type::Object(_)) = return_type {
+            bail!("Objects cannot currently be returned from functions");
+        }
+        let id = match self.identifier {
+            None => bail!("anonymous methods are not supported {:?}", self),
+            Some(id) => id,
+        };
+        Ok(StaticMethod {
+            name: id.0.to_string(),
+            arguments: self.args.body.list.convert(ci)?,
+            return_type,
+            ffi_func: Default::default(),
+            attributes: match &self.attributes {
+                Some(attr) => FunctionAttributes::try_from(attr)?,
+                None => Default::default(),
+            },
+            docs: ci.get_doc_comment(id.0),
+        })
+    }
+}
+
+// Represents a property of an object type, declared as an `attribute` in the UDL.
+//
+// In the FFI, this will be a getter method named `get_{name}` that returns the property's
+// value and, unless the property is readonly, a setter method named `set_{name}` that takes
+// the new value. These call through to Rust methods of the same name.
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) getter: Method,
+    pub(super) setter: Option<Method>,
+    pub(super) docs: DocComment,
+}
+
+impl Property {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_(&self) -> Type {
+        self.type_.clone()
+    }
+
+    pub fn readonly(&self) -> bool {
+        self.setter.is_none()
+    }
+
+    pub fn getter(&self) -> &Method {
+        &self.getter
+    }
+
+    pub fn setter(&self) -> Option<&Method> {
+        self.setter.as_ref()
+    }
+
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// The getter, followed by the setter if there is one.
+    pub fn methods(&self) -> Vec<&Method> {
+        std::iter::once(&self.getter)
+            .chain(self.setter.iter())
+            .collect()
+    }
+
+    fn methods_mut(&mut self) -> Vec<&mut Method> {
+        std::iter::once(&mut self.getter)
+            .chain(self.setter.iter_mut())
+            .collect()
+    }
+}
+
+impl Hash for Property {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The accessor methods are entirely determined by the other fields.
+        self.name.hash(state);
+        self.type_.hash(state);
+        self.readonly().hash(state);
+    }
+}
+
+impl APIConverter<Property> for weedle::interface::AttributeInterfaceMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Property> {
+        if self.modifier.is_some() {
+            bail!("attribute modifiers are not supported")
+        }
+        if self.attributes.is_some() {
+            bail!("extended attributes on properties are not supported yet");
+        }
+        let name = self.identifier.0.to_string();
+        let type_ = ci.resolve_type_expression(&self.type_)?;
+        if let Type::Object(_) = type_ {
+            bail!("Objects cannot currently be used as property types");
+        }
+        let getter = Method {
+            name: format!("get_{}", name),
+            // We don't know the name of the containing `Object` at this point, fill it in later.
+            object_name: Default::default(),
+            arguments: Vec::new(),
+            return_type: Some(type_.clone()),
+            ffi_func: Default::default(),
+            // Getters only read the object, so they take `&self`.
+            attributes: MethodAttributes::self_by_ref(),
+            docs: Default::default(),
+        };
+        let setter = match self.readonly {
+            Some(_) => None,
+            None => Some(Method {
+                name: format!("set_{}", name),
+                object_name: Default::default(),
+                arguments: vec![Argument {
+                    name: "value".to_string(),
+                    type_: type_.clone(),
+                    by_ref: false,
+                    optional: false,
+                    default: None,
+                }],
+                return_type: None,
+                ffi_func: Default::default(),
+                attributes: Default::default(),
+                docs: Default::default(),
+            }),
+        };
+        Ok(Property {
+            docs: ci.get_doc_comment(self.identifier.0),
+            name,
+            type_,
+            getter,
+            setter,
         })
     }
 }
@@ -413,7 +872,8 @@ mod test {
         assert_eq!(ci.iter_object_definitions().len(), 1);
         ci.get_object_definition("Testing").unwrap();
 
-        assert_eq!(ci.iter_types().len(), 6);
+        // The builtin panic report also makes `i32` known.
+        assert_eq!(ci.iter_types().len(), 7);
         assert!(ci
             .iter_types()
             .iter()
@@ -549,4 +1009,234 @@ mod test {
         let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
         assert_eq!(err.to_string(), "Duplicate interface member name: \"new\"");
     }
+
+    #[test]
+    fn test_static_methods() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                constructor();
+                u32 instance_method();
+                static u32 static_method(u32 v);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert_eq!(obj.methods().len(), 1);
+        assert_eq!(obj.static_methods().len(), 1);
+
+        // Static methods don't take a handle, unlike instance methods.
+        let meth = obj.static_methods()[0];
+        assert_eq!(meth.name(), "static_method");
+        assert_eq!(meth.ffi_func().arguments().len(), 1);
+        assert_eq!(meth.ffi_func().arguments()[0].name(), "v");
+        assert!(meth.ffi_func().name().ends_with("_Testing_static_method"));
+        assert_eq!(obj.methods()[0].ffi_func().arguments().len(), 1);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == meth.ffi_func().name()));
+
+        const UDL2: &str = r#"
+            namespace test{};
+            interface Testing {
+                void method();
+                static void method();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate interface member name: \"method\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_properties() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                attribute string name;
+                readonly attribute u32 age;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert_eq!(obj.methods().len(), 0);
+        assert_eq!(obj.properties().len(), 2);
+
+        let prop = obj.properties()[0];
+        assert_eq!(prop.name(), "name");
+        assert_eq!(prop.type_(), Type::String);
+        assert!(!prop.readonly());
+        assert_eq!(prop.getter().name(), "get_name");
+        assert_eq!(prop.getter().ffi_func().arguments().len(), 1);
+        let setter = prop.setter().unwrap();
+        assert_eq!(setter.name(), "set_name");
+        assert_eq!(setter.ffi_func().arguments().len(), 2);
+        assert!(setter.ffi_func().name().ends_with("_Testing_set_name"));
+
+        let prop = obj.properties()[1];
+        assert!(prop.readonly());
+        assert!(prop.setter().is_none());
+        assert_eq!(prop.methods().len(), 1);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == prop.getter().ffi_func().name()));
+
+        const UDL2: &str = r#"
+            namespace test{};
+            interface Testing {
+                attribute string name;
+                string get_name();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate interface member name: \"get_name\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_self_by_arc() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{};
+            [Threadsafe]
+            interface Testing {
+                [Self=ByArc]
+                constructor();
+                [Self=ByArc]
+                void by_arc();
+                void by_ref();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(obj.primary_constructor().unwrap().returns_arc());
+        assert!(obj.methods()[0].takes_self_by_arc());
+        assert!(!obj.methods()[1].takes_self_by_arc());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            interface Testing {
+                [Self=ByArc]
+                void by_arc();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Self=ByArc is only supported for Threadsafe interface classes"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Threadsafe]
+            interface Testing {
+                [Self=ByArc]
+                static void by_arc();
+            };
+        "#;
+        assert!(ComponentInterface::from_webidl(UDL3).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rwlock() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{};
+            [RwLock]
+            interface Testing {
+                [Self=ByRef]
+                u32 read();
+                void write(u32 value);
+                attribute u32 property;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(obj.rwlock());
+        assert!(!obj.threadsafe());
+        assert!(obj.methods()[0].takes_self_by_ref());
+        assert!(!obj.methods()[1].takes_self_by_ref());
+        let prop = obj.properties()[0];
+        assert!(prop.getter().takes_self_by_ref());
+        assert!(!prop.setter().unwrap().takes_self_by_ref());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            interface Testing {
+                [Self=ByRef]
+                u32 read();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Self=ByRef is only supported for RwLock interface classes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_thread_confined() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{};
+            /// An object.
+            [ThreadConfined]
+            interface Testing {
+                void method();
+            };
+            interface Other {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(obj.thread_confined());
+        assert_eq!(obj.docs(), Some("An object."));
+        let docs = obj.class_docs().unwrap();
+        assert!(docs.starts_with("An object.\n\nInstances of this class can only be used"));
+
+        let obj = ci.get_object_definition("Other").unwrap();
+        assert!(!obj.thread_confined());
+        assert_eq!(obj.class_docs(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_traits() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{};
+            [Traits=(Display, Eq, Hash)]
+            interface Testing {};
+            interface Other {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(obj.ffi_object_debug().is_none());
+        let display = obj.ffi_object_display().unwrap();
+        assert!(display.name().ends_with("_Testing_uniffi_trait_display"));
+        assert_eq!(display.arguments().len(), 1);
+        assert!(matches!(display.return_type(), Some(FFIType::RustBuffer)));
+        assert_eq!(obj.ffi_object_to_string().unwrap().name(), display.name());
+        let eq = obj.ffi_object_eq().unwrap();
+        assert_eq!(eq.arguments().len(), 2);
+        assert!(matches!(eq.return_type(), Some(FFIType::Int8)));
+        let hash = obj.ffi_object_hash().unwrap();
+        assert!(matches!(hash.return_type(), Some(FFIType::UInt64)));
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|func| func.name() == eq.name()));
+
+        assert!(ci.iter_types().contains(&Type::Boolean));
+
+        let obj = ci.get_object_definition("Other").unwrap();
+        assert!(obj.ffi_object_to_string().is_none());
+        assert!(obj.ffi_object_eq().is_none());
+        Ok(())
+    }
 }
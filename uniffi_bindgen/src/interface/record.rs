@@ -46,19 +46,24 @@
 
 use anyhow::{bail, Result};
 
+use super::doc_comment::DocComment;
 use super::literal::{convert_default_value, Literal};
 use super::types::Type;
 use super::{APIConverter, ComponentInterface};
 
 /// Represents a "data class" style object, for passing around complex values.
 ///
-/// In the FFI these are represented as a byte buffer, which one side explicitly
+/// In the FFI these are usually represented as a byte buffer, which one side explicitly
 /// serializes the data into and the other serializes it out of. So I guess they're
 /// kind of like "pass by clone" values.
+///
+/// Records that contain only plain-old-data (numbers and booleans) are instead passed
+/// as a C-style struct, which saves an allocation and some serialization on every call.
 #[derive(Debug, Clone, Hash)]
 pub struct Record {
     pub(super) name: String,
     pub(super) fields: Vec<Field>,
+    pub(super) docs: DocComment,
 }
 
 impl Record {
@@ -68,6 +73,33 @@ impl Record {
     pub fn fields(&self) -> Vec<&Field> {
         self.fields.iter().collect()
     }
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// Can this record be passed over the FFI as a C-style struct?
+    ///
+    /// That's the case if it has at least one field, and every field is a number or a
+    /// boolean. (C does not allow empty structs, so empty records are always serialized).
+    pub fn is_plain_old_data(&self) -> bool {
+        !self.fields.is_empty()
+            && self.fields.iter().all(|f| {
+                matches!(
+                    f.type_,
+                    Type::UInt8
+                        | Type::Int8
+                        | Type::UInt16
+                        | Type::Int16
+                        | Type::UInt32
+                        | Type::Int32
+                        | Type::UInt64
+                        | Type::Int64
+                        | Type::Float32
+                        | Type::Float64
+                        | Type::Boolean
+                )
+            })
+    }
 }
 
 impl APIConverter<Record> for weedle::DictionaryDefinition<'_> {
@@ -81,6 +113,7 @@ impl APIConverter<Record> for weedle::DictionaryDefinition<'_> {
         Ok(Record {
             name: self.identifier.0.to_string(),
             fields: self.members.body.convert(ci)?,
+            docs: ci.get_doc_comment(self.identifier.0),
         })
     }
 }
@@ -92,6 +125,7 @@ pub struct Field {
     type_: Type,
     required: bool,
     default: Option<Literal>,
+    docs: DocComment,
 }
 
 impl Field {
@@ -104,6 +138,9 @@ impl Field {
     pub fn default_value(&self) -> Option<Literal> {
         self.default.clone()
     }
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
 }
 
 impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
@@ -124,12 +161,14 @@ impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
             type_,
             required: self.required.is_some(),
             default,
+            docs: ci.get_doc_comment(self.identifier.0),
         })
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::super::ffi::FFIType;
     use super::super::literal::Radix;
     use super::*;
 
@@ -203,7 +242,8 @@ mod test {
         assert_eq!(record.fields()[0].name(), "maybe_name");
         assert_eq!(record.fields()[1].name(), "value");
 
-        assert_eq!(ci.iter_types().len(), 4);
+        // The builtin panic report also makes `i32` known.
+        assert_eq!(ci.iter_types().len(), 5);
         assert!(ci
             .iter_types()
             .iter()
@@ -227,4 +267,50 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_plain_old_data_records_are_passed_as_structs() -> Result<()> {
+        const UDL: &str = r#"
+            namespace test{
+                Point make_point(Point p);
+                Named make_named(Named n);
+            };
+            dictionary Empty {};
+            dictionary Point {
+                f64 x;
+                f64 y;
+                boolean visible;
+            };
+            dictionary Named {
+                string name;
+                u32 value;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(!ci
+            .get_record_definition("Empty")
+            .unwrap()
+            .is_plain_old_data());
+        assert!(ci
+            .get_record_definition("Point")
+            .unwrap()
+            .is_plain_old_data());
+        assert!(!ci
+            .get_record_definition("Named")
+            .unwrap()
+            .is_plain_old_data());
+
+        let ffi_func = ci.get_function_definition("make_point").unwrap().ffi_func();
+        assert!(matches!(ffi_func.arguments()[0].type_(), FFIType::Struct(nm) if nm == "Point"));
+        assert!(matches!(ffi_func.return_type(), Some(FFIType::Struct(nm)) if nm == "Point"));
+
+        let ffi_func = ci.get_function_definition("make_named").unwrap().ffi_func();
+        assert!(matches!(
+            ffi_func.arguments()[0].type_(),
+            FFIType::RustBuffer
+        ));
+        assert!(matches!(ffi_func.return_type(), Some(FFIType::RustBuffer)));
+
+        Ok(())
+    }
 }
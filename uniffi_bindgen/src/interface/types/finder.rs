@@ -21,7 +21,8 @@ use std::convert::TryFrom;
 
 use anyhow::{bail, Result};
 
-use super::super::attributes::EnumAttributes;
+use super::super::attributes::{EnumAttributes, TypedefAttributes};
+use super::resolver::{resolve_map_type, resolve_tuple_type, resolve_union_type};
 use super::{Type, TypeUniverse};
 
 /// Trait to help with an early "type discovery" phase when processing the UDL.
@@ -85,8 +86,90 @@ impl TypeFinder for weedle::EnumDefinition<'_> {
 
 impl TypeFinder for weedle::TypedefDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
-        if self.attributes.is_some() {
-            bail!("no typedef attributes are currently supported");
+        // Custom types are declared using a `typedef` with a special attribute.
+        if let Some(attrs) = &self.attributes {
+            let attrs = TypedefAttributes::try_from(attrs)?;
+            if attrs.is_custom() {
+                let builtin = types.resolve_type_expression(&self.type_)?;
+                if !is_valid_custom_type_builtin(&builtin) {
+                    bail!(
+                        "custom type {} must wrap a builtin type, not {}",
+                        self.identifier.0,
+                        builtin.canonical_name()
+                    );
+                }
+                let name = self.identifier.0.to_string();
+                return types.add_type_definition(
+                    self.identifier.0,
+                    Type::Custom {
+                        name,
+                        builtin: Box::new(builtin),
+                    },
+                );
+            }
+            // External types are declared using a `typedef` of the special `extern` type, since
+            // we don't know anything about them except the crate that defines them.
+            if let Some(crate_name) = attrs.external_crate_name() {
+                match &self.type_.type_ {
+                    weedle::types::Type::Single(weedle::types::SingleType::NonAny(
+                        weedle::types::NonAnyType::Identifier(weedle::types::MayBeNull {
+                            type_,
+                            q_mark: None,
+                        }),
+                    )) if type_.0 == "extern" => {}
+                    _ => bail!(
+                        "external type {} must be declared like `typedef extern {}`",
+                        self.identifier.0,
+                        self.identifier.0
+                    ),
+                }
+                let name = self.identifier.0.to_string();
+                return types.add_type_definition(
+                    self.identifier.0,
+                    Type::External {
+                        name,
+                        crate_name: crate_name.to_string(),
+                    },
+                );
+            }
+            // Tuple types are declared using a `typedef` that lists their element types.
+            if attrs.is_tuple() {
+                let t = match &self.type_.type_ {
+                    weedle::types::Type::Union(weedle::types::MayBeNull {
+                        type_,
+                        q_mark: None,
+                    }) => resolve_tuple_type(self.identifier.0, type_, types)?,
+                    _ => bail!(
+                        "tuple type {} must list its element types like `(string or u32)`",
+                        self.identifier.0
+                    ),
+                };
+                return types.add_type_definition(self.identifier.0, t);
+            }
+            // Map types with keys other than strings are declared using a `typedef` that lists
+            // their key and value types, since WebIDL `record`s can only have string keys.
+            if attrs.is_map() {
+                let t = match &self.type_.type_ {
+                    weedle::types::Type::Union(weedle::types::MayBeNull {
+                        type_,
+                        q_mark: None,
+                    }) => resolve_map_type(type_, types)?,
+                    _ => bail!(
+                        "map type {} must list its key and value types like `(u32 or string)`",
+                        self.identifier.0
+                    ),
+                };
+                return types.add_type_definition(self.identifier.0, t);
+            }
+        }
+        // A typedef of a union gives the union a name, rather than just being an alias for it.
+        if let weedle::types::Type::Union(weedle::types::MayBeNull {
+            type_,
+            q_mark: None,
+        }) = &self.type_.type_
+        {
+            let t = resolve_union_type(Some(self.identifier.0), type_, types)?;
+            return types.add_type_definition(self.identifier.0, t);
         }
         // For now, we assume that the typedef must refer to an already-defined type, which means
         // we can look it up in the TypeUniverse. This should suffice for our needs for
@@ -96,6 +179,29 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
     }
 }
 
+/// Custom types must wrap one of the builtin primitive types, since the bindings need to know
+/// how to pass it over the FFI before they can convert it to and from the custom type.
+fn is_valid_custom_type_builtin(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Float32
+            | Type::Float64
+            | Type::Boolean
+            | Type::String
+            | Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+    )
+}
+
 impl TypeFinder for weedle::CallbackInterfaceDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
         if self.attributes.is_some() {
@@ -173,4 +279,209 @@ mod test {
         assert_eq!(err.to_string(), "unknown type reference: TestRecord");
         Ok(())
     }
+
+    #[test]
+    fn test_custom_type_finding() -> Result<()> {
+        const UDL: &str = r#"
+            [Custom]
+            typedef string Guid;
+
+            [Custom]
+            typedef i64 Handle;
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        types.add_type_definitions_from(idl.as_ref())?;
+        // The builtin types are known too, since they're what gets passed over the FFI.
+        assert_eq!(types.iter_known_types().count(), 4);
+        assert!(
+            matches!(types.get_type_definition("Guid").unwrap(), Type::Custom { name, builtin } if name == "Guid" && *builtin == Type::String)
+        );
+        assert!(
+            matches!(types.get_type_definition("Handle").unwrap(), Type::Custom { name, builtin } if name == "Handle" && *builtin == Type::Int64)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_on_custom_type_wrapping_non_builtin() -> Result<()> {
+        const UDL: &str = r#"
+            dictionary TestRecord {
+                u32 field;
+            };
+
+            [Custom]
+            typedef TestRecord Wrapper;
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "custom type Wrapper must wrap a builtin type, not RecordTestRecord"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_external_type_finding() -> Result<()> {
+        const UDL: &str = r#"
+            [External="profiles"]
+            typedef extern ProfileInfo;
+
+            dictionary TestRecord {
+                ProfileInfo? profile;
+            };
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        types.add_type_definitions_from(idl.as_ref())?;
+        assert!(
+            matches!(types.get_type_definition("ProfileInfo").unwrap(), Type::External { name, crate_name } if name == "ProfileInfo" && crate_name == "profiles")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_on_external_type_with_definition() -> Result<()> {
+        const UDL: &str = r#"
+            [External="profiles"]
+            typedef string ProfileInfo;
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "external type ProfileInfo must be declared like `typedef extern ProfileInfo`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_typedef_names_the_union() -> Result<()> {
+        const UDL: &str = r#"
+            typedef (string or u32) NameOrSize;
+            typedef sequence<(string or u32)> NamesOrSizes;
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        types.add_type_definitions_from(idl.as_ref())?;
+        let t = types.get_type_definition("NameOrSize").unwrap();
+        assert_eq!(t.canonical_name(), "UnionNameOrSize");
+        assert_eq!(t.union_name(), "NameOrSize");
+        // Unions inside other types stay anonymous.
+        assert_eq!(
+            types
+                .get_type_definition("NamesOrSizes")
+                .unwrap()
+                .canonical_name(),
+            "SequenceUnionstringOru32"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_type_finding() -> Result<()> {
+        const UDL: &str = r#"
+            enum TestEnum { "one", "two" };
+
+            [Map]
+            typedef (u64 or string) NamesById;
+
+            [Map]
+            typedef (TestEnum or sequence<u32>) NumbersByEnum;
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        types.add_type_definitions_from(idl.as_ref())?;
+        assert_eq!(
+            types
+                .get_type_definition("NamesById")
+                .unwrap()
+                .canonical_name(),
+            "Mapu64string"
+        );
+        assert_eq!(
+            types
+                .get_type_definition("NumbersByEnum")
+                .unwrap()
+                .canonical_name(),
+            "MapEnumTestEnumSequenceu32"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_on_invalid_map_type() -> Result<()> {
+        let mut types = TypeUniverse::default();
+        let idl = weedle::parse("[Map] typedef record<DOMString, u32> Numbers;").unwrap();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "map type Numbers must list its key and value types like `(u32 or string)`"
+        );
+        let idl = weedle::parse("[Map] typedef (u8 or u8 or u8) Three;").unwrap();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "map types must list exactly one key type and one value type"
+        );
+        let idl = weedle::parse("[Map] typedef (f32 or string) NamesByFloat;").unwrap();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(err.to_string(), "map types cannot have f32 keys");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tuple_type_finding() -> Result<()> {
+        const UDL: &str = r#"
+            dictionary TestRecord {
+                u32 field;
+            };
+
+            [Tuple]
+            typedef (string or u32 or u32) NameAndSize;
+
+            [Tuple]
+            typedef (TestRecord? or sequence<NameAndSize>) RecordAndSizes;
+        "#;
+        let idl = weedle::parse(UDL).unwrap();
+        let mut types = TypeUniverse::default();
+        types.add_type_definitions_from(idl.as_ref())?;
+        assert_eq!(
+            types
+                .get_type_definition("NameAndSize")
+                .unwrap()
+                .canonical_name(),
+            "TupleNameAndSize"
+        );
+        assert_eq!(
+            types
+                .get_type_definition("RecordAndSizes")
+                .unwrap()
+                .canonical_name(),
+            "TupleRecordAndSizes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_on_invalid_tuple_type() -> Result<()> {
+        let mut types = TypeUniverse::default();
+        let idl = weedle::parse("[Tuple] typedef sequence<u32> Numbers;").unwrap();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "tuple type Numbers must list its element types like `(string or u32)`"
+        );
+        let idl = weedle::parse("[Tuple] typedef (u8 or u8 or u8 or u8 or u8 or u8 or u8) Seven;")
+            .unwrap();
+        let err = types.add_type_definitions_from(idl.as_ref()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "tuple types must have between 2 and 6 elements"
+        );
+        Ok(())
+    }
 }
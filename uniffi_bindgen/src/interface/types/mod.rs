@@ -26,6 +26,7 @@ use std::{collections::hash_map::Entry, collections::HashMap, collections::HashS
 use anyhow::{bail, Result};
 
 use super::ffi::FFIType;
+use super::ComponentInterface;
 
 mod finder;
 pub(super) use finder::TypeFinder;
@@ -50,16 +51,32 @@ pub enum Type {
     Float64,
     Boolean,
     String,
+    Bytes,
+    Timestamp,
+    Duration,
     // Types defined in the component API, each of which has a string name.
     Object(String),
     Record(String),
     Enum(String),
     Error(String),
     CallbackInterface(String),
+    // A user-defined type that is passed over the FFI as one of the builtin types above,
+    // converting to and from it using code supplied by the component and the bindings.
+    Custom { name: String, builtin: Box<Type> },
+    // A record defined by another component, which we pass over the FFI using that
+    // component's code for reading and writing it.
+    External { name: String, crate_name: String },
     // Structurally recursive types.
     Optional(Box<Type>),
     Sequence(Box<Type>),
-    Map(/* String, */ Box<Type>),
+    Map(/* key */ Box<Type>, /* value */ Box<Type>),
+    // A tagged union, holding a value of exactly one of the member types. Unions declared
+    // by a `typedef` are named after it, so that bindings can use that name for the type
+    // they declare, and other unions are anonymous.
+    Union(/* name */ Option<String>, /* members */ Vec<Type>),
+    // A tuple declared by a `[Tuple] typedef`, holding a value of each of the element types
+    // in turn. The name is only used by bindings that need to declare a type for it.
+    Tuple { name: String, elements: Vec<Type> },
 }
 
 impl Type {
@@ -84,6 +101,9 @@ impl Type {
             Type::Float64 => "f64".into(),
             Type::String => "string".into(),
             Type::Boolean => "bool".into(),
+            Type::Bytes => "bytes".into(),
+            Type::Timestamp => "timestamp".into(),
+            Type::Duration => "duration".into(),
             // API defined types.
             // Note that these all get unique names, and the parser ensures that the names do not
             // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -93,6 +113,10 @@ impl Type {
             Type::Enum(nm) => format!("Enum{}", nm),
             Type::Record(nm) => format!("Record{}", nm),
             Type::CallbackInterface(nm) => format!("CallbackInterface{}", nm),
+            Type::Custom { name, .. } => format!("Custom{}", name),
+            Type::External { name, .. } => format!("External{}", name),
+            Type::Tuple { name, .. } => format!("Tuple{}", name),
+            Type::Union(Some(name), _) => format!("Union{}", name),
             // Recursive types.
             // These add a prefix to the name of the underlying type.
             // The component API definition cannot give names to recursive types, so as long as the
@@ -100,18 +124,57 @@ impl Type {
             // acccidentally generating name collisions.
             Type::Optional(t) => format!("Optional{}", t.canonical_name()),
             Type::Sequence(t) => format!("Sequence{}", t.canonical_name()),
-            Type::Map(t) => format!("Map{}", t.canonical_name()),
+            Type::Map(k, v) => format!("Map{}{}", k.canonical_name(), v.canonical_name()),
+            Type::Union(None, ts) => format!(
+                "Union{}",
+                ts.iter()
+                    .map(|t| t.canonical_name())
+                    .collect::<Vec<_>>()
+                    .join("Or")
+            ),
         }
     }
 }
 
-/// When passing data across the FFI, each `Type` value will be lowered into a corresponding
-/// `FFIType` value. This conversion tells you which one.
-///
-/// Note that the conversion is one-way - given an FFIType, it is not in general possible to
-/// tell what the corresponding Type is that it's being used to represent.
-impl Into<FFIType> for &Type {
-    fn into(self: Self) -> FFIType {
+impl Type {
+    /// Get the variants of a union type, as `(name, member_type)` pairs in declaration order.
+    ///
+    /// The variant names are positional (`A`, `B`, `C` and so on) to match the generic union
+    /// types in the `uniffi` crate, since the members of an anonymous union don't have names.
+    /// Each variant is tagged with its 1-based position when serialized.
+    pub fn union_variants(&self) -> Vec<(String, Type)> {
+        match self {
+            Type::Union(_, ts) => ts
+                .iter()
+                .zip(b'A'..)
+                .map(|(t, letter)| ((letter as char).to_string(), t.clone()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Get the name that bindings should use when declaring a type for a union.
+    ///
+    /// This is the name of the union's typedef if it has one, or else its canonical name.
+    pub fn union_name(&self) -> String {
+        match self {
+            Type::Union(Some(name), _) => name.clone(),
+            _ => self.canonical_name(),
+        }
+    }
+}
+
+impl Type {
+    /// When passing data across the FFI, each `Type` value will be lowered into a corresponding
+    /// `FFIType` value. This method tells you which one.
+    ///
+    /// Most types always lower in the same way, but records containing only plain-old-data
+    /// are passed as C-style structs rather than being serialized, so we need to consult the
+    /// containing `ComponentInterface` to find out what kind of record we're dealing with.
+    ///
+    /// Note that the conversion is one-way - given an FFIType, it is not in general possible to
+    /// tell what the corresponding Type is that it's being used to represent.
+    pub fn ffi_type(&self, ci: &ComponentInterface) -> FFIType {
         match self {
             // Types that are the same map to themselves, naturally.
             Type::UInt8 => FFIType::UInt8,
@@ -129,6 +192,10 @@ impl Into<FFIType> for &Type {
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FFIType::RustBuffer,
+            // Byte arrays are passed as a serialized buffer, which we can copy in bulk.
+            Type::Bytes => FFIType::RustBuffer,
+            // Timestamps and durations are serialized as seconds plus nanoseconds.
+            Type::Timestamp | Type::Duration => FFIType::RustBuffer,
             // Objects are passed as opaque integer handles.
             Type::Object(_) => FFIType::UInt64,
             // Callback interfaces are passed as opaque integer handles.
@@ -137,10 +204,23 @@ impl Into<FFIType> for &Type {
             Type::Enum(_) => FFIType::UInt32,
             // Errors have their own special type.
             Type::Error(_) => FFIType::RustError,
+            // Custom types are passed in exactly the same way as the builtin type that they wrap.
+            Type::Custom { builtin, .. } => builtin.ffi_type(ci),
+            // Records of plain-old-data are cheap to pass by value, as a C-style struct.
+            // Other records are serialized into a bytebuffer like the types below.
+            Type::Record(name) => match ci.get_record_definition(name) {
+                Some(rec) if rec.is_plain_old_data() => FFIType::Struct(name.clone()),
+                _ => FFIType::RustBuffer,
+            },
             // Other types are serialized into a bytebuffer and deserialized on the other side.
-            Type::Record(_) | Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
-                FFIType::RustBuffer
-            }
+            // For external types, that's true even if they're plain-old-data, since we can't
+            // know that without the other component's interface definition.
+            Type::External { .. }
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(_, _)
+            | Type::Union(..)
+            | Type::Tuple { .. } => FFIType::RustBuffer,
         }
     }
 }
@@ -239,6 +319,34 @@ mod test_type {
             .canonical_name(),
             "OptionalSequenceObjectExample"
         );
+        assert_eq!(
+            Type::Map(
+                Box::new(Type::UInt64),
+                Box::new(Type::Record("Item".into()))
+            )
+            .canonical_name(),
+            "Mapu64RecordItem"
+        );
+        assert_eq!(
+            Type::Union(None, vec![Type::String, Type::Record("Item".into())]).canonical_name(),
+            "UnionstringOrRecordItem"
+        );
+        assert_eq!(
+            Type::Union(
+                Some("NameOrItem".into()),
+                vec![Type::String, Type::Record("Item".into())]
+            )
+            .canonical_name(),
+            "UnionNameOrItem"
+        );
+        assert_eq!(
+            Type::Tuple {
+                name: "NameAndCounts".into(),
+                elements: vec![Type::String, Type::UInt32, Type::UInt32]
+            }
+            .canonical_name(),
+            "TupleNameAndCounts"
+        );
     }
 }
 
@@ -47,7 +47,7 @@ impl TypeResolver for weedle::types::Type<'_> {
                 weedle::types::SingleType::Any(_) => bail!("no support for `any` types"),
                 weedle::types::SingleType::NonAny(t) => t.resolve_type_expression(types),
             },
-            weedle::types::Type::Union(_) => bail!("no support for union types yet"),
+            weedle::types::Type::Union(t) => t.resolve_type_expression(types),
         }
     }
 }
@@ -94,6 +94,149 @@ impl<T: TypeResolver> TypeResolver for weedle::types::MayBeNull<T> {
     }
 }
 
+impl TypeResolver for weedle::types::UnionType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
+        resolve_union_type(None, self, types)
+    }
+}
+
+/// Resolve a union type, which is named if it's declared by a `typedef` and anonymous otherwise.
+pub(super) fn resolve_union_type(
+    name: Option<&str>,
+    union: &weedle::types::UnionType<'_>,
+    types: &mut TypeUniverse,
+) -> Result<Type> {
+    let mut members = Vec::new();
+    collect_union_members(union, types, &mut members)?;
+    if members.len() < 2 || members.len() > MAX_UNION_MEMBERS {
+        bail!(
+            "union types must have between 2 and {} members",
+            MAX_UNION_MEMBERS
+        );
+    }
+    types.add_known_type(Type::Union(name.map(str::to_string), members))
+}
+
+/// The largest number of member types supported in a union, which is limited by
+/// the number of generic union types provided by the `uniffi` crate.
+const MAX_UNION_MEMBERS: usize = 6;
+
+/// Resolve the member types of a union, flattening any nested unions so that
+/// e.g. `(A or (B or C))` is the same type as `(A or B or C)`.
+fn collect_union_members(
+    union: &weedle::types::UnionType<'_>,
+    types: &mut TypeUniverse,
+    members: &mut Vec<Type>,
+) -> Result<()> {
+    for member in &union.body.list {
+        let t = match member {
+            weedle::types::UnionMemberType::Union(weedle::types::MayBeNull {
+                type_,
+                q_mark: None,
+            }) => {
+                collect_union_members(type_, types, members)?;
+                continue;
+            }
+            weedle::types::UnionMemberType::Union(t) => t.resolve_type_expression(types)?,
+            weedle::types::UnionMemberType::Single(t) => t.resolve_type_expression(types)?,
+        };
+        if members.contains(&t) {
+            bail!("union types cannot contain the same type more than once");
+        }
+        if matches!(
+            t,
+            Type::Object(_) | Type::Error(_) | Type::CallbackInterface(_)
+        ) {
+            bail!("union types cannot contain {}", t.canonical_name());
+        }
+        members.push(t);
+    }
+    Ok(())
+}
+
+/// The largest number of elements supported in a tuple, which is limited by
+/// the largest tuples that have a `ViaFfi` impl in the `uniffi` crate.
+const MAX_TUPLE_ELEMENTS: usize = 6;
+
+/// Resolve a tuple type from the list of element types in a `[Tuple] typedef`.
+///
+/// WebIDL doesn't have any syntax for tuples, so they borrow the syntax of a union
+/// type to list their elements, e.g. `[Tuple] typedef (string or u32) NameAndCount;`.
+/// Unlike unions, the elements don't need to be distinct and nested unions are left as-is.
+pub(super) fn resolve_tuple_type(
+    name: &str,
+    tuple: &weedle::types::UnionType<'_>,
+    types: &mut TypeUniverse,
+) -> Result<Type> {
+    let elements = tuple
+        .body
+        .list
+        .iter()
+        .map(|element| match element {
+            weedle::types::UnionMemberType::Single(t) => t.resolve_type_expression(types),
+            weedle::types::UnionMemberType::Union(t) => t.resolve_type_expression(types),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if elements.len() < 2 || elements.len() > MAX_TUPLE_ELEMENTS {
+        bail!(
+            "tuple types must have between 2 and {} elements",
+            MAX_TUPLE_ELEMENTS
+        );
+    }
+    if let Some(t) = elements.iter().find(|t| {
+        matches!(
+            t,
+            Type::Object(_) | Type::Error(_) | Type::CallbackInterface(_)
+        )
+    }) {
+        bail!("tuple types cannot contain {}", t.canonical_name());
+    }
+    types.add_known_type(Type::Tuple {
+        name: name.to_string(),
+        elements,
+    })
+}
+
+/// Resolve a map type from the key and value types listed in a `[Map] typedef`.
+///
+/// WebIDL `record`s can only have string keys, so maps with other key types borrow the
+/// syntax of a union type to list their key and value types, e.g.
+/// `[Map] typedef (u32 or string) NamesById;`.
+pub(super) fn resolve_map_type(
+    map: &weedle::types::UnionType<'_>,
+    types: &mut TypeUniverse,
+) -> Result<Type> {
+    let (key, value) = match map.body.list.as_slice() {
+        [key, value] => (key, value),
+        _ => bail!("map types must list exactly one key type and one value type"),
+    };
+    let resolve = |t: &weedle::types::UnionMemberType<'_>, types: &mut TypeUniverse| match t {
+        weedle::types::UnionMemberType::Single(t) => t.resolve_type_expression(types),
+        weedle::types::UnionMemberType::Union(t) => t.resolve_type_expression(types),
+    };
+    let key = resolve(key, types)?;
+    // Keys need to be hashable and comparable for equality in every language, which
+    // rules out floats and any compound types.
+    if !matches!(
+        key,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Boolean
+            | Type::String
+            | Type::Enum(_)
+    ) {
+        bail!("map types cannot have {} keys", key.canonical_name());
+    }
+    let value = resolve(value, types)?;
+    types.add_known_type(Type::Map(Box::new(key), Box::new(value)))
+}
+
 impl TypeResolver for weedle::types::IntegerType {
     fn resolve_type_expression(&self, _types: &mut TypeUniverse) -> Result<Type> {
         bail!(
@@ -121,10 +264,11 @@ impl TypeResolver for weedle::types::SequenceType<'_> {
 
 impl TypeResolver for weedle::types::RecordType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
-        let t = (&self.generics.body.2).resolve_type_expression(types)?;
-        // Maps always have string keys, make sure the `String` type is known.
-        types.add_known_type(Type::String)?;
-        types.add_known_type(Type::Map(Box::new(t)))
+        // The WebIDL grammar only allows string types as record keys, so that's all we
+        // can get from here. Maps with other key types are declared with a `[Map] typedef`.
+        let k = types.add_known_type(Type::String)?;
+        let v = (&self.generics.body.2).resolve_type_expression(types)?;
+        types.add_known_type(Type::Map(Box::new(k), Box::new(v)))
     }
 }
 
@@ -171,6 +315,9 @@ impl TypeResolver for weedle::types::DoubleType {
 pub(in super::super) fn resolve_builtin_type(name: &str) -> Option<Type> {
     match name {
         "string" => Some(Type::String),
+        "bytes" => Some(Type::Bytes),
+        "timestamp" => Some(Type::Timestamp),
+        "duration" => Some(Type::Duration),
         "u8" => Some(Type::UInt8),
         "i8" => Some(Type::Int8),
         "u16" => Some(Type::UInt16),
@@ -230,6 +377,34 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_resolving_bytes_type() -> Result<()> {
+        let mut types = TypeUniverse::default();
+        let (_, expr) = weedle::types::Type::parse("bytes").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert!(matches!(t, Type::Bytes));
+        // It's a builtin type in its own right, not a sequence of `u8`.
+        assert_eq!(types.iter_known_types().count(), 1);
+        let (_, expr) = weedle::types::Type::parse("bytes?").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t.canonical_name(), "Optionalbytes");
+        assert_eq!(types.iter_known_types().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolving_time_types() -> Result<()> {
+        let mut types = TypeUniverse::default();
+        let (_, expr) = weedle::types::Type::parse("timestamp").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert!(matches!(t, Type::Timestamp));
+        let (_, expr) = weedle::types::Type::parse("sequence<duration>").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t.canonical_name(), "Sequenceduration");
+        assert_eq!(types.iter_known_types().count(), 3);
+        Ok(())
+    }
+
     #[test]
     fn test_resolving_sequence_type_adds_inner_type() -> Result<()> {
         let mut types = TypeUniverse::default();
@@ -255,11 +430,11 @@ mod test {
         assert_eq!(types.iter_known_types().count(), 0);
         let (_, expr) = weedle::types::Type::parse("record<DOMString, float>").unwrap();
         let t = types.resolve_type_expression(expr).unwrap();
-        assert_eq!(t.canonical_name(), "Mapf32");
+        assert_eq!(t.canonical_name(), "Mapstringf32");
         assert_eq!(types.iter_known_types().count(), 3);
         assert!(types
             .iter_known_types()
-            .find(|t| t.canonical_name() == "Mapf32")
+            .find(|t| t.canonical_name() == "Mapstringf32")
             .is_some());
         assert!(types
             .iter_known_types()
@@ -284,12 +459,58 @@ mod test {
     }
 
     #[test]
-    fn test_error_on_union_type() -> Result<()> {
+    fn test_resolving_union_type_adds_member_types() -> Result<()> {
         let mut types = TypeUniverse::default();
         types.add_type_definition("TestRecord", Type::Record("TestRecord".into()))?;
         let (_, expr) = weedle::types::Type::parse("(TestRecord or u32)").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t.canonical_name(), "UnionRecordTestRecordOru32");
+        assert_eq!(types.iter_known_types().count(), 3);
+        assert!(types
+            .iter_known_types()
+            .find(|t| t.canonical_name() == "u32")
+            .is_some());
+
+        // Nested unions are flattened, but nullable ones are not.
+        let (_, expr) = weedle::types::Type::parse("(string or (u32 or TestRecord))").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t.canonical_name(), "UnionstringOru32OrRecordTestRecord");
+        let (_, expr) = weedle::types::Type::parse("(string or (u32 or TestRecord)?)").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(
+            t.canonical_name(),
+            "UnionstringOrOptionalUnionu32OrRecordTestRecord"
+        );
+
+        let (_, expr) = weedle::types::Type::parse("(string or u32)?").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t.canonical_name(), "OptionalUnionstringOru32");
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_on_invalid_union_type() -> Result<()> {
+        let mut types = TypeUniverse::default();
+        types.add_type_definition("TestObject", Type::Object("TestObject".into()))?;
+        let (_, expr) = weedle::types::Type::parse("(u32 or (string or u32))").unwrap();
+        let err = types.resolve_type_expression(expr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "union types cannot contain the same type more than once"
+        );
+        let (_, expr) = weedle::types::Type::parse("(u32 or TestObject)").unwrap();
+        let err = types.resolve_type_expression(expr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "union types cannot contain ObjectTestObject"
+        );
+        let (_, expr) =
+            weedle::types::Type::parse("(u8 or u16 or u32 or u64 or i8 or i16 or i32)").unwrap();
         let err = types.resolve_type_expression(expr).unwrap_err();
-        assert_eq!(err.to_string(), "no support for union types yet");
+        assert_eq!(
+            err.to_string(),
+            "union types must have between 2 and 6 members"
+        );
         Ok(())
     }
 }
@@ -218,18 +218,28 @@ pub fn generate_bindings<P: AsRef<Path>>(
 
 // Run tests against the foreign language bindings (generated and compiled at the same time).
 // Note that the cdylib we're testing against must be built already.
+// If `check_leaks` is set, each script fails if it leaves any objects or buffers from the first
+// component alive, which needs the cdylib to have been built with uniffi's `leak-tracking` feature.
 pub fn run_tests<P: AsRef<Path>>(
     cdylib_dir: P,
-    udl_file: P,
+    udl_files: &[P],
     test_scripts: Vec<&str>,
     config_file_override: Option<P>,
+    check_leaks: bool,
 ) -> Result<()> {
     let cdylib_dir = cdylib_dir.as_ref();
-    let udl_file = udl_file.as_ref();
     let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
 
-    let component = parse_udl(&udl_file)?;
-    let config = get_config(&component, udl_file, config_file_override)?;
+    // The cdylib may contain several components, and the scripts can use any of them.
+    let components = udl_files
+        .iter()
+        .map(|udl_file| {
+            let udl_file = udl_file.as_ref();
+            let component = parse_udl(udl_file)?;
+            let config = get_config(&component, udl_file, config_file_override)?;
+            Ok((component, config))
+        })
+        .collect::<Result<Vec<_>>>()?;
 
     // Group the test scripts by language first.
     let mut language_tests: HashMap<TargetLanguage, Vec<String>> = HashMap::new();
@@ -245,10 +255,24 @@ pub fn run_tests<P: AsRef<Path>>(
     }
 
     for (lang, test_scripts) in language_tests {
-        bindings::write_bindings(&config.bindings, &component, &cdylib_dir, lang, true, true)?;
-        bindings::compile_bindings(&config.bindings, &component, &cdylib_dir, lang)?;
+        for (component, config) in &components {
+            bindings::write_bindings(&config.bindings, component, &cdylib_dir, lang, true, true)?;
+            bindings::compile_bindings(&config.bindings, component, &cdylib_dir, lang)?;
+        }
         for test_script in test_scripts {
-            bindings::run_script(cdylib_dir, &test_script, lang)?;
+            if check_leaks {
+                let (component, config) = &components[0];
+                let script = bindings::write_leak_checking_script(
+                    &config.bindings,
+                    component,
+                    cdylib_dir,
+                    &test_script,
+                    lang,
+                )?;
+                bindings::run_script(cdylib_dir, script, lang)?;
+            } else {
+                bindings::run_script(cdylib_dir, &test_script, lang)?;
+            }
         }
     }
     Ok(())
@@ -366,6 +390,16 @@ impl<T: Clone> MergeWith for Option<T> {
     }
 }
 
+// Tables of config options (like custom types) are merged entry by entry, with
+// any entries in `self` taking precedence over those in `other`.
+impl<V: Clone> MergeWith for HashMap<String, V> {
+    fn merge_with(&self, other: &Self) -> Self {
+        let mut merged = other.clone();
+        merged.extend(self.iter().map(|(k, v)| (k.clone(), v.clone())));
+        merged
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -387,4 +421,20 @@ mod test {
         let not_a_crate_root = &this_crate_root.join("./src/templates");
         assert!(guess_crate_root(&not_a_crate_root.join("./src/example.udl")).is_err());
     }
+
+    #[test]
+    fn test_merging_of_config_tables() {
+        let mut loaded = HashMap::new();
+        loaded.insert("a".to_string(), Some(1));
+        loaded.insert("b".to_string(), None);
+        let mut default = HashMap::new();
+        default.insert("b".to_string(), Some(2));
+        default.insert("c".to_string(), Some(3));
+        let merged = loaded.merge_with(&default);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged["a"], Some(1));
+        // Entries are merged as a whole, rather than recursively.
+        assert_eq!(merged["b"], None);
+        assert_eq!(merged["c"], Some(3));
+    }
 }
@@ -4,7 +4,7 @@
 
 use anyhow::{bail, Result};
 
-const POSSIBLE_LANGUAGES: &[&str] = &["kotlin", "python", "swift", "gecko_js"];
+const POSSIBLE_LANGUAGES: &[&str] = &["kotlin", "python", "swift", "gecko_js", "wasm"];
 
 fn main() -> Result<()> {
     let matches = clap::App::new("uniffi-bindgen")
@@ -79,12 +79,25 @@ fn main() -> Result<()> {
             .arg(clap::Arg::with_name("cdylib_dir").required(true).help("Path to the directory containing the cdylib the scripts will be testing against."))
             .arg(clap::Arg::with_name("udl_file").required(true))
             .arg(clap::Arg::with_name("test_scripts").required(true).multiple(true).help("Foreign language(s) test scripts to run"))
+            .arg(
+                clap::Arg::with_name("component")
+                .long("--component")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Path to the UDL file of another component in the same cdylib, whose bindings the scripts will also use. May be given more than once.")
+            )
             .arg(
                 clap::Arg::with_name("config")
                 .long("--config-path")
                 .takes_value(true)
                 .help("Path to the optional uniffi config file. If not provided, uniffi-bindgen will try to guess from the UDL's file location.")
             )
+            .arg(
+                clap::Arg::with_name("check_leaks")
+                .long("--check-leaks")
+                .help("Fail the scripts if they leave any objects or buffers alive. The cdylib must have been built with uniffi's `leak-tracking` feature.")
+            )
         )
         .get_matches();
     match matches.subcommand() {
@@ -102,12 +115,17 @@ fn main() -> Result<()> {
             m.value_of_os("manifest"),
             !m.is_present("no_format"),
         )?,
-        ("test", Some(m)) => uniffi_bindgen::run_tests(
-            m.value_of_os("cdylib_dir").unwrap(),           // Required
-            m.value_of_os("udl_file").unwrap(),             // Required
-            m.values_of("test_scripts").unwrap().collect(), // Required
-            m.value_of_os("config"),
-        )?,
+        ("test", Some(m)) => {
+            let mut udl_files = vec![m.value_of_os("udl_file").unwrap()]; // Required
+            udl_files.extend(m.values_of_os("component").into_iter().flatten());
+            uniffi_bindgen::run_tests(
+                m.value_of_os("cdylib_dir").unwrap(), // Required
+                &udl_files,
+                m.values_of("test_scripts").unwrap().collect(), // Required
+                m.value_of_os("config"),
+                m.is_present("check_leaks"),
+            )?
+        }
         _ => bail!("No command specified; try `--help` for some help."),
     }
     Ok(())
@@ -48,13 +48,42 @@ mod filters {
             Type::Float64 => "f64".into(),
             Type::Boolean => "bool".into(),
             Type::String => "String".into(),
+            Type::Bytes => "Vec<u8>".into(),
+            Type::Timestamp => "std::time::SystemTime".into(),
+            Type::Duration => "std::time::Duration".into(),
             Type::Enum(name) | Type::Record(name) | Type::Object(name) | Type::Error(name) => {
                 name.clone()
             }
             Type::CallbackInterface(name) => format!("Box<dyn {}>", name),
+            Type::Custom { name, .. } => name.clone(),
+            // The other component's crate must be a dependency, with the type at its root.
+            Type::External { name, crate_name } => {
+                format!("{}::{}", crate_name.replace('-', "_"), name)
+            }
             Type::Optional(t) => format!("Option<{}>", type_rs(t)?),
             Type::Sequence(t) => format!("Vec<{}>", type_rs(t)?),
-            Type::Map(t) => format!("std::collections::HashMap<String, {}>", type_rs(t)?),
+            Type::Tuple { elements, .. } => format!(
+                "({})",
+                elements
+                    .iter()
+                    .map(type_rs)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::Union(_, members) => format!(
+                "uniffi::Union{}<{}>",
+                members.len(),
+                members
+                    .iter()
+                    .map(type_rs)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::Map(k, v) => format!(
+                "std::collections::HashMap<{}, {}>",
+                type_rs(k)?,
+                type_rs(v)?
+            ),
         })
     }
 
@@ -75,6 +104,7 @@ mod filters {
             FFIType::RustError => "uniffi::deps::ffi_support::ExternError".into(),
             FFIType::ForeignBytes => "uniffi::ForeignBytes".into(),
             FFIType::ForeignCallback => "uniffi::ForeignCallback".into(),
+            FFIType::Struct(name) => format!("{}FfiStruct", name),
         })
     }
 
@@ -86,24 +116,49 @@ mod filters {
                 "uniffi::ViaFfi::lower is not supported for callback interfaces ({})",
                 type_name
             ),
+            // External types are always passed as a serialized buffer, because we can't tell
+            // whether the other component passes them as a C-style struct instead.
+            Type::External { .. } => {
+                format!("uniffi::lower_into_buffer::<{}>({})", type_rs(type_)?, nm)
+            }
             _ => format!("<{} as uniffi::ViaFfi>::lower({})", type_rs(type_)?, nm),
         })
     }
 
+    /// Borrow a `[ByRef]` string or byte array from the `ForeignBytes` that it was passed as.
+    /// If the bindings provide a string that isn't valid UTF-8, this returns an `ExternError`
+    /// from the enclosing closure.
+    pub fn lift_borrowed_rs(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::String => format!("uniffi::lift_argument(\"{0}\", {0}.as_str())?", nm),
+            Type::Bytes | Type::Sequence(_) => format!("{}.as_slice()", nm),
+            _ => unreachable!(
+                "Only strings and byte arrays can be borrowed, not {:?}",
+                type_
+            ),
+        })
+    }
+
     pub fn lift_rs(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         // By explicitly naming the type here, we help the rust compiler to type-check the user-provided
         // implementations of the functions that we're wrapping (and also to type-check our generated code).
-        // This will panic if the bindings provide an invalid value over the FFI.
-        Ok(match type_ {
-            Type::CallbackInterface(type_name) => format!(
-                "Box::new(<{}Proxy as uniffi::ViaFfi>::try_lift({}).unwrap())",
-                type_name, nm,
-            ),
-            _ => format!(
-                "<{} as uniffi::ViaFfi>::try_lift({}).unwrap()",
+        // If the bindings provide an invalid value over the FFI, this returns an `ExternError` with
+        // the name of the argument from the enclosing closure.
+        let lifted = match type_ {
+            Type::CallbackInterface(type_name) => {
+                format!("<{}Proxy as uniffi::ViaFfi>::try_lift({})", type_name, nm)
+            }
+            Type::External { .. } => format!(
+                "uniffi::try_lift_from_buffer::<{}>({})",
                 type_rs(type_)?,
                 nm
             ),
+            _ => format!("<{} as uniffi::ViaFfi>::try_lift({})", type_rs(type_)?, nm),
+        };
+        let lifted = format!("uniffi::lift_argument(\"{}\", {})?", nm, lifted);
+        Ok(match type_ {
+            Type::CallbackInterface(_) => format!("Box::new({})", lifted),
+            _ => lifted,
         })
     }
 
@@ -127,7 +182,9 @@ mod filters {
         })
     }
 
-    /// Get a Rust expression for writing a value into a byte buffer.
+    /// Get a Rust expression for reading a value from a byte buffer.
+    /// This is only used for the values returned by foreign callbacks, and will panic if
+    /// the bindings return an invalid value.
     pub fn read_rs(target: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::CallbackInterface(type_name) => unimplemented!(
@@ -135,7 +192,7 @@ mod filters {
                 type_name
             ),
             _ => format!(
-                "<{} as uniffi::ViaFfi>::try_read({}).unwrap()",
+                "uniffi::read_return_value::<{}, _>({})",
                 type_rs(type_)?,
                 target
             ),
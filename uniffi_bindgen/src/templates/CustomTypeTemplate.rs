@@ -0,0 +1,27 @@
+{#
+// For each custom type declared in the UDL, we assume the caller has provided a corresponding
+// rust type that implements `uniffi::CustomTypeConverter`. We use that to pass it across the FFI
+// as the builtin type that it wraps. If the caller's converter does not use the builtin type
+// declared in the UDL then the rust compiler will complain with a type error.
+#}
+{%- let builtin_rs = builtin|type_rs %}
+#[doc(hidden)]
+unsafe impl uniffi::ViaFfi for {{ name }} {
+    type FfiType = <{{ builtin_rs }} as uniffi::ViaFfi>::FfiType;
+
+    fn lower(self) -> Self::FfiType {
+        <{{ builtin_rs }} as uniffi::ViaFfi>::lower(<Self as uniffi::CustomTypeConverter>::from_custom(&self))
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
+        <Self as uniffi::CustomTypeConverter>::into_custom(<{{ builtin_rs }} as uniffi::ViaFfi>::try_lift(v)?)
+    }
+
+    fn write<B: uniffi::deps::bytes::BufMut>(&self, buf: &mut B) {
+        <{{ builtin_rs }} as uniffi::ViaFfi>::write(&<Self as uniffi::CustomTypeConverter>::from_custom(self), buf);
+    }
+
+    fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
+        <Self as uniffi::CustomTypeConverter>::into_custom(<{{ builtin_rs }} as uniffi::ViaFfi>::try_read(buf)?)
+    }
+}
@@ -1,6 +1,7 @@
 // For each Object definition, we assume the caller has provided an appropriately-shaped `struct`
 // with an `impl` for each method on the object. We create a `ConcurrentHandleMap` for safely handing
-// out references to these structs to foreign language code, and we provide a `pub extern "C"` function
+// out references to these structs to foreign language code (or hand out pointers to them directly,
+// for objects with the `[PointerHandle]` attribute), and we provide a `pub extern "C"` function
 // corresponding to each method.
 //
 // If the caller's implementation of the struct does not match with the methods or types specified
@@ -8,23 +9,126 @@
 // error message when processing this generated code.
 {% let handle_map = format!("UNIFFI_HANDLE_MAP_{}", obj.name().to_uppercase()) -%}
 
+{% if obj.pointer_handle() -%}
+// The handles for this object are pointers to it, so there's no actual map, but we give this
+// the same name as a handle map so that the code for calling its methods looks the same.
+#[doc(hidden)]
+static {{ handle_map }}: uniffi::ffi::pointer_handles::ArcPointerHandles<{{ obj.name() }}>
+    = uniffi::ffi::pointer_handles::ArcPointerHandles::new();
+{%- else if obj.thread_confined() -%}
+// The objects are stored in thread-local maps, so this doesn't need to be a `lazy_static`.
+#[doc(hidden)]
+static {{ handle_map }}: uniffi::ffi::thread_confined::ThreadConfinedHandleMap<{{ obj.name() }}>
+    = uniffi::ffi::thread_confined::ThreadConfinedHandleMap::new();
+{%- else -%}
 uniffi::deps::lazy_static::lazy_static! {
     {%- let handle_map_type = obj.threadsafe()|choose(
         "uniffi::ffi::handle_maps::ArcHandleMap",
-        "uniffi::ffi::handle_maps::MutexHandleMap")
+        obj.rwlock()|choose(
+            "uniffi::ffi::handle_maps::RwLockHandleMap",
+            "uniffi::ffi::handle_maps::MutexHandleMap"))
     %}
     #[doc(hidden)]
     static ref {{ handle_map }}: {{ handle_map_type }}<{{ obj.name() }}>
         = Default::default();
 }
+{%- endif %}
 
     {% let ffi_free = obj.ffi_object_free() -%}
     #[doc(hidden)]
     #[no_mangle]
     pub extern "C" fn {{ ffi_free.name() }}(handle: u64) {
-        let _ = {{ handle_map }}.delete_u64(handle);
+        {% if obj.pointer_handle() -%}
+        // Safety: the foreign-language bindings free each handle exactly once, once any calls
+        // that are using it have returned, after which they never use it again.
+        let freed = unsafe { {{ handle_map }}.delete_u64(handle) };
+        {%- else -%}
+        let freed = {{ handle_map }}.delete_u64(handle);
+        {%- endif %}
+        if freed.is_ok() {
+            uniffi::ffi::leaks::object_freed("{{ ci.namespace() }}::{{ obj.name() }}");
+        }
     }
 
+{%- match obj.ffi_object_debug() %}
+{%- when Some with (func) %}
+    uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: std::fmt::Debug);
+
+    #[doc(hidden)]
+    #[no_mangle]
+    pub extern "C" fn {{ func.name() }}(
+        {%- call rs::arg_list_ffi_decl(func) %}
+    ) -> uniffi::RustBuffer {
+        uniffi::deps::log::debug!("{{ func.name() }}");
+        {% call rs::to_rs_trait_call(obj, "format!(\"{:?}\", obj)"|lower_rs(Type::String)) %}
+    }
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.ffi_object_display() %}
+{%- when Some with (func) %}
+    uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: std::fmt::Display);
+
+    #[doc(hidden)]
+    #[no_mangle]
+    pub extern "C" fn {{ func.name() }}(
+        {%- call rs::arg_list_ffi_decl(func) %}
+    ) -> uniffi::RustBuffer {
+        uniffi::deps::log::debug!("{{ func.name() }}");
+        {% call rs::to_rs_trait_call(obj, "obj.to_string()"|lower_rs(Type::String)) %}
+    }
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.ffi_object_eq() %}
+{%- when Some with (func) %}
+    uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: Eq);
+
+    #[doc(hidden)]
+    #[no_mangle]
+    pub extern "C" fn {{ func.name() }}(
+        {%- call rs::arg_list_ffi_decl(func) %}
+    ) -> i8 {
+        uniffi::deps::log::debug!("{{ func.name() }}");
+        {% if !obj.threadsafe() -%}
+        use uniffi::UniffiMethodCall;
+        {%- endif %}
+        {% if obj.pointer_handle() -%}
+        let callback = |obj: &{{ obj.name() }}, other: &{{ obj.name() }}| {
+            {{ "obj == other"|lower_rs(Type::Boolean) }}
+        };
+        // Safety: the foreign-language bindings only pass handles for objects they haven't freed,
+        // and they don't free an object until any calls that are using its handle have returned.
+        unsafe { {{ handle_map }}.pair_call_with_output(err, handle, other, callback) }
+        {%- else -%}
+        {{ handle_map }}.pair_call_with_output(err, handle, other, |obj, other| {
+            {{ "obj == other"|lower_rs(Type::Boolean) }}
+        })
+        {%- endif %}
+    }
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.ffi_object_hash() %}
+{%- when Some with (func) %}
+    uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: std::hash::Hash);
+
+    #[doc(hidden)]
+    #[no_mangle]
+    pub extern "C" fn {{ func.name() }}(
+        {%- call rs::arg_list_ffi_decl(func) %}
+    ) -> u64 {
+        uniffi::deps::log::debug!("{{ func.name() }}");
+        {% call rs::to_rs_trait_call(obj, "{
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            obj.hash(&mut hasher);
+            hasher.finish()
+        }") %}
+    }
+{%- when None %}
+{%- endmatch %}
+
 {%- for cons in obj.constructors() %}
     #[allow(clippy::all)]
     #[doc(hidden)]
@@ -34,7 +138,8 @@ uniffi::deps::lazy_static::lazy_static! {
         uniffi::deps::log::debug!("{{ cons.ffi_func().name() }}");
         // If the constructor does not have the same signature as declared in the UDL, then
         // this attempt to call it will fail with a (somewhat) helpful compiler error.
-        {% call rs::to_rs_constructor_call(obj, cons) %}
+        let handle = {% call rs::to_rs_constructor_call(obj, cons) %};
+        uniffi::ffi::leaks::object_created("{{ ci.namespace() }}::{{ obj.name() }}", handle)
     }
 {%- endfor %}
 
@@ -51,3 +156,33 @@ uniffi::deps::lazy_static::lazy_static! {
         {% call rs::to_rs_method_call(obj, meth) %}
     }
 {% endfor %}
+
+{%- for meth in obj.static_methods() %}
+    #[allow(clippy::all)]
+    #[doc(hidden)]
+    #[no_mangle]
+    pub extern "C" fn {{ meth.ffi_func().name() }}(
+        {%- call rs::arg_list_ffi_decl(meth.ffi_func()) %}
+    ) -> {% call rs::return_type_func(meth) %} {
+        uniffi::deps::log::debug!("{{ meth.ffi_func().name() }}");
+        // If the static method does not have the same signature as declared in the UDL, then
+        // this attempt to call it will fail with a (somewhat) helpful compiler error.
+        {% call rs::to_rs_static_method_call(obj, meth) %}
+    }
+{% endfor %}
+
+{%- for prop in obj.properties() %}
+{%- for meth in prop.methods() %}
+    #[allow(clippy::all)]
+    #[doc(hidden)]
+    #[no_mangle]
+    pub extern "C" fn {{ meth.ffi_func().name() }}(
+        {%- call rs::arg_list_ffi_decl(meth.ffi_func()) %}
+    ) -> {% call rs::return_type_func(meth) %} {
+        uniffi::deps::log::debug!("{{ meth.ffi_func().name() }}");
+        // If the property accessor does not have the same signature as declared in the UDL, then
+        // this attempt to call it will fail with a (somewhat) helpful compiler error.
+        {% call rs::to_rs_method_call(obj, meth) %}
+    }
+{% endfor %}
+{%- endfor %}
@@ -4,8 +4,52 @@
 // If the caller's struct does not match the shape and types declared in the UDL then the rust
 // compiler will complain with a type error.
 #}
+{%- if rec.is_plain_old_data() %}
+// This record contains only plain-old-data, so it is passed over the FFI as a C-style struct
+// with the same fields, rather than being serialized into a `RustBuffer`.
+#[doc(hidden)]
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_snake_case)]
+pub struct {{ rec.name() }}FfiStruct {
+    {%- for field in rec.fields() %}
+    {{ field.name() }}: <{{ field.type_()|type_rs }} as uniffi::ViaFfi>::FfiType,
+    {%- endfor %}
+}
+
+#[doc(hidden)]
+unsafe impl uniffi::deps::ffi_support::IntoFfi for {{ rec.name() }}FfiStruct {
+    type Value = Self;
+    fn ffi_default() -> Self {
+        Self::default()
+    }
+    fn into_ffi_value(self) -> Self::Value {
+        self
+    }
+}
+{%- endif %}
+
 #[doc(hidden)]
 unsafe impl uniffi::ViaFfi for {{ rec.name() }} {
+    {%- if rec.is_plain_old_data() %}
+    type FfiType = {{ rec.name() }}FfiStruct;
+
+    fn lower(self) -> Self::FfiType {
+        {{ rec.name() }}FfiStruct {
+            {%- for field in rec.fields() %}
+            {{ field.name() }}: uniffi::ViaFfi::lower(self.{{ field.name() }}),
+            {%- endfor %}
+        }
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
+        Ok(Self {
+            {%- for field in rec.fields() %}
+            {{ field.name() }}: uniffi::lift_field("{{ field.name() }}", <{{ field.type_()|type_rs }} as uniffi::ViaFfi>::try_lift(v.{{ field.name() }}))?,
+            {%- endfor %}
+        })
+    }
+    {%- else %}
     type FfiType = uniffi::RustBuffer;
 
     fn lower(self) -> Self::FfiType {
@@ -15,6 +59,7 @@ unsafe impl uniffi::ViaFfi for {{ rec.name() }} {
     fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
         uniffi::try_lift_from_buffer(v)
     }
+    {%- endif %}
 
     fn write<B: uniffi::deps::bytes::BufMut>(&self, buf: &mut B) {
         // If the provided struct doesn't match the fields declared in the UDL, then
@@ -27,7 +72,7 @@ unsafe impl uniffi::ViaFfi for {{ rec.name() }} {
     fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
       Ok(Self {
         {%- for field in rec.fields() %}
-            {{ field.name() }}: <{{ field.type_()|type_rs }} as uniffi::ViaFfi>::try_read(buf)?,
+            {{ field.name() }}: uniffi::lift_field("{{ field.name() }}", <{{ field.type_()|type_rs }} as uniffi::ViaFfi>::try_read(buf))?,
         {%- endfor %}
       })
     }
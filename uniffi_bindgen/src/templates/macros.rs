@@ -14,8 +14,12 @@
 
 {%- macro _arg_list_rs_call(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}
+        {{- arg.name()|lift_borrowed_rs(arg.type_()) }}
+        {%- else %}
         {%- if arg.by_ref() %}&{% endif %}
         {{- arg.name()|lift_rs(arg.type_()) }}
+        {%- endif %}
         {%- if !loop.last %}, {% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -42,52 +46,93 @@
 
 {% macro return_type_func(func) %}{% match func.ffi_func().return_type() %}{% when Some with (return_type) %}{{ return_type|type_ffi }}{%- else -%}(){%- endmatch -%}{%- endmacro -%}
 
+{#- Propagate the errors that a function is declared to throw, as an `ExternError`. #}
+{% macro throws(func) %}{% if func.throws().is_some() %}?{% endif %}{% endmacro %}
+
 {% macro ret(func) %}{% match func.return_type() %}{% when Some with (return_type) %}{{ "_retval"|lower_rs(return_type) }}{% else %}_retval{% endmatch %}{% endmacro %}
 
+{#-
+// The calls below all return an `ExternError` from their closures, both for the errors that the
+// function is declared to throw and for arguments that the foreign language code passed us
+// but that we couldn't lift.
+-#}
 {% macro to_rs_constructor_call(obj, cons) %}
-{% match cons.throws() %}
-{% when Some with (e) %}
-UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.insert_with_result(err, || -> Result<{{obj.name()}}, {{e}}> {
-    let _retval = {{ obj.name() }}::{% call to_rs_call(cons) %}?;
+{% let insert = cons.returns_arc()|choose("insert_arc", "insert") -%}
+UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.{{ insert }}_with_result(err, || -> Result<{% if cons.returns_arc() %}std::sync::Arc<{{obj.name()}}>{% else %}{{obj.name()}}{% endif %}, uniffi::deps::ffi_support::ExternError> {
+    let _retval = {{ obj.name() }}::{% call to_rs_call(cons) %}{% call throws(cons) %};
     Ok(_retval)
 })
-{% else %}
-UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.insert_with_output(err, || {
-    {{ obj.name() }}::{% call to_rs_call(cons) %}
-})
-{% endmatch %}
 {% endmacro %}
 
 {% macro to_rs_method_call(obj, meth) -%}
 {% let this_handle_map = format!("UNIFFI_HANDLE_MAP_{}", obj.name().to_uppercase()) -%}
-{% if !obj.threadsafe() -%}
+{% let read_only = obj.rwlock() && meth.takes_self_by_ref() -%}
+{% if !obj.threadsafe() && !read_only -%}
 use uniffi::UniffiMethodCall;
 {%- endif -%}
-{% match meth.throws() -%}
-{% when Some with (e) -%}
-{{ this_handle_map }}.method_call_with_result(err, {{ meth.first_argument().name() }}, |obj| -> Result<{% call return_type_func(meth) %}, {{e}}> {
-    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%}?;
+{% let method_call = meth.takes_self_by_arc()|choose(
+    "arc_method_call",
+    read_only|choose("read_method_call", "method_call")) -%}
+{% if obj.pointer_handle() -%}
+// The method is called outside of the `unsafe` block, so that it gets the usual checks.
+let callback = {% call _method_callback(obj, meth) %};
+// Safety: the foreign-language bindings only pass handles for objects they haven't freed, and
+// they don't free an object until any calls that are using its handle have returned.
+unsafe { {{ this_handle_map }}.{{ method_call }}_with_result(err, {{ meth.first_argument().name() }}, callback) }
+{%- else -%}
+{{ this_handle_map }}.{{ method_call }}_with_result(err, {{ meth.first_argument().name() }}, {% call _method_callback(obj, meth) %})
+{%- endif %}
+{% endmacro -%}
+
+{#-
+// The closure that calls a method, given the object. For objects with pointer handles, it's bound
+// to a variable before use, which needs the type of the object to be spelled out.
+-#}
+{% macro _method_callback(obj, meth) -%}
+|obj
+{%- if obj.pointer_handle() -%}
+: {% if meth.takes_self_by_arc() %}std::sync::Arc<{{ obj.name() }}>{% else %}&{{ obj.name() }}{% endif %}
+{%- endif -%}
+| -> Result<{% call return_type_func(meth) %}, uniffi::deps::ffi_support::ExternError> {
+    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%}{% call throws(meth) %};
     Ok({% call ret(meth) %})
+}
+{%- endmacro %}
+
+{#-
+// Call one of the standard traits listed in the object's `[Traits=(...)]` attribute,
+// which only need a shared reference to the object.
+-#}
+{% macro to_rs_trait_call(obj, body) -%}
+{% let this_handle_map = format!("UNIFFI_HANDLE_MAP_{}", obj.name().to_uppercase()) -%}
+{% if !obj.threadsafe() && !obj.rwlock() -%}
+use uniffi::UniffiMethodCall;
+{%- endif %}
+{% if obj.pointer_handle() -%}
+// The trait is called outside of the `unsafe` block, so that it gets the usual checks.
+let callback = |obj: &{{ obj.name() }}| {
+    {{ body }}
+};
+// Safety: the foreign-language bindings only pass handles for objects they haven't freed, and
+// they don't free an object until any calls that are using its handle have returned.
+unsafe { {{ this_handle_map }}.method_call_with_output(err, handle, callback) }
+{%- else -%}
+{{ this_handle_map }}.{{ obj.rwlock()|choose("read_method_call", "method_call") }}_with_output(err, handle, |obj| {
+    {{ body }}
 })
-{% else -%}
-{{ this_handle_map }}.method_call_with_output(err, {{ meth.first_argument().name() }}, |obj| {
-    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%};
-    {% call ret(meth) %}
+{%- endif %}
+{%- endmacro %}
+
+{% macro to_rs_static_method_call(obj, meth) %}
+uniffi::deps::ffi_support::call_with_result(err, || -> Result<{% call return_type_func(meth) %}, uniffi::deps::ffi_support::ExternError> {
+    let _retval = {{ obj.name() }}::{% call to_rs_call(meth) %}{% call throws(meth) %};
+    Ok({% call ret(meth) %})
 })
-{% endmatch -%}
-{% endmacro -%}
+{% endmacro %}
 
 {% macro to_rs_function_call(func) %}
-{% match func.throws() %}
-{% when Some with (e) %}
-uniffi::deps::ffi_support::call_with_result(err, || -> Result<{% call return_type_func(func) %}, {{e}}> {
-    let _retval = {% call to_rs_call(func) %}?;
+uniffi::deps::ffi_support::call_with_result(err, || -> Result<{% call return_type_func(func) %}, uniffi::deps::ffi_support::ExternError> {
+    let _retval = {% call to_rs_call(func) %}{% call throws(func) %};
     Ok({% call ret(func) %})
 })
-{% else %}
-uniffi::deps::ffi_support::call_with_output(err, || {
-    let _retval = {% call to_rs_call(func) %};
-    {% call ret(func) %}
-})
-{% endmatch %}
 {% endmacro %}
@@ -2,8 +2,6 @@
 // Trust me, you don't want to mess with it!
 {% import "macros.rs" as rs %}
 
-{% include "RustBuffer.rs" %}
-
 // We generate error mappings into ffi_support::ExternErrors
 // so that the errors can propagate through the FFI
 {% for e in ci.iter_error_definitions() %}
@@ -20,6 +18,15 @@
 {% include "RecordTemplate.rs" %}
 {% endfor %}
 
+// Custom types, corresponding to `[Custom] typedef` declarations in UDL.
+{% for typ in ci.iter_types() %}
+{%- match typ %}
+{%- when Type::Custom with { name, builtin } %}
+{% include "CustomTypeTemplate.rs" %}
+{%- else %}
+{%- endmatch %}
+{% endfor %}
+
 // Top level functions, corresponding to UDL `namespace` functions.
 {%- for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.rs" %}
@@ -26,6 +26,10 @@ use syn::{bracketed, punctuated::Punctuated, LitStr, Token};
 /// one or more file paths relative to the crate root directory.
 /// It will produce one `#[test]` function per file, in a manner designed to
 /// play nicely with `cargo test` and its test filtering options.
+///
+/// If the crate bundles several components into a single library, pass a list of
+/// all their udl files as the first argument instead, and the test scripts will be
+/// able to use the bindings for any of them.
 #[proc_macro]
 pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let paths = syn::parse_macro_input!(paths as FilePaths);
@@ -33,7 +37,7 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
     let pkg_dir = env::var("CARGO_MANIFEST_DIR")
         .expect("Missing $CARGO_MANIFEST_DIR, cannot build tests for generated bindings");
     // For each file found, generate a matching testcase.
-    let udl_file = &paths.udl_file;
+    let udl_files = &paths.udl_files;
     let test_functions = paths.test_scripts
         .iter()
         .map(|file_path| {
@@ -47,10 +51,18 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
                 "uniffi_foreign_language_testcase_{}",
                 test_file_name.replace(|c: char| !c.is_alphanumeric(), "_")
             );
+            let run_testcase = match udl_files.as_slice() {
+                [udl_file] => quote! {
+                    uniffi::testing::run_foreign_language_testcase(#pkg_dir, #udl_file, #test_file_path)
+                },
+                _ => quote! {
+                    uniffi::testing::run_foreign_language_testcase_for_components(#pkg_dir, &[#(#udl_files),*], #test_file_path)
+                },
+            };
             quote! {
                 #[test]
                 fn #test_name () -> uniffi::deps::anyhow::Result<()> {
-                    uniffi::testing::run_foreign_language_testcase(#pkg_dir, #udl_file, #test_file_path)
+                    #run_testcase
                 }
             }
         })
@@ -64,23 +76,34 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
 /// Newtype to simplifying parsing a list of file paths from macro input.
 #[derive(Debug)]
 struct FilePaths {
-    udl_file: String,
+    udl_files: Vec<String>,
     test_scripts: Vec<String>,
 }
 
 impl syn::parse::Parse for FilePaths {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let udl_file: LitStr = input.parse()?;
+        let udl_files = if input.peek(LitStr) {
+            let udl_file: LitStr = input.parse()?;
+            vec![udl_file.value()]
+        } else {
+            parse_string_list(input)?
+        };
         let _comma: Token![,] = input.parse()?;
-        let array_contents;
-        bracketed!(array_contents in input);
-        let test_scripts = Punctuated::<LitStr, Token![,]>::parse_terminated(&array_contents)?
-            .iter()
-            .map(|s| s.value())
-            .collect();
+        let test_scripts = parse_string_list(input)?;
         Ok(FilePaths {
-            udl_file: udl_file.value(),
+            udl_files,
             test_scripts,
         })
     }
 }
+
+fn parse_string_list(input: syn::parse::ParseStream) -> syn::Result<Vec<String>> {
+    let array_contents;
+    bracketed!(array_contents in input);
+    Ok(
+        Punctuated::<LitStr, Token![,]>::parse_terminated(&array_contents)?
+            .iter()
+            .map(|s| s.value())
+            .collect(),
+    )
+}